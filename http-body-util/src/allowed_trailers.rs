@@ -0,0 +1,255 @@
+use http::header::{HeaderMap, HeaderName, TRAILER};
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::util::retain_fields;
+
+pin_project! {
+    /// A body that only lets through trailers that were declared up front.
+    ///
+    /// Senders should announce the trailer fields they intend to send in the `Trailer` header.
+    /// Fields missing from that set are removed from the trailers, and a trailers frame left
+    /// empty is dropped. With [`reject_undeclared`](AllowedTrailers::reject_undeclared), an
+    /// [`UndeclaredTrailerError`] is returned instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::{header::TRAILER, HeaderMap, HeaderValue};
+    /// use http_body_util::{AllowedTrailers, BodyExt, Full, StreamBody};
+    /// # use bytes::Bytes;
+    /// # use http_body::Frame;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(TRAILER, HeaderValue::from_static("grpc-status, grpc-message"));
+    ///
+    /// let mut trailers = HeaderMap::new();
+    /// trailers.insert("grpc-status", HeaderValue::from_static("0"));
+    /// trailers.insert("x-secret", HeaderValue::from_static("1"));
+    /// # let body = StreamBody::new(futures_util::stream::iter(vec![
+    /// #     Ok::<_, std::convert::Infallible>(Frame::<Bytes>::trailers(trailers)),
+    /// # ]));
+    ///
+    /// let body = AllowedTrailers::from_headers(body, &headers);
+    /// let trailers = body.collect().await.unwrap().into_trailers().unwrap();
+    /// assert!(trailers.contains_key("grpc-status"));
+    /// assert!(!trailers.contains_key("x-secret"));
+    /// # }
+    /// ```
+    #[derive(Clone, Debug)]
+    pub struct AllowedTrailers<B> {
+        allowed: HashSet<HeaderName>,
+        reject: bool,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> AllowedTrailers<B> {
+    /// Create a new `AllowedTrailers` allowing the trailer fields in `names`.
+    pub fn new<I>(inner: B, names: I) -> Self
+    where
+        I: IntoIterator<Item = HeaderName>,
+    {
+        Self {
+            allowed: names.into_iter().collect(),
+            reject: false,
+            inner,
+        }
+    }
+
+    /// Create a new `AllowedTrailers` allowing the trailer fields declared in the `Trailer` header
+    /// fields of `headers`.
+    ///
+    /// Invalid field names in the `Trailer` header are ignored.
+    pub fn from_headers(inner: B, headers: &HeaderMap) -> Self {
+        let names = headers
+            .get_all(TRAILER)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|name| HeaderName::from_bytes(name.trim().as_bytes()).ok());
+        Self::new(inner, names)
+    }
+
+    /// Return an error instead of removing undeclared trailer fields.
+    pub fn reject_undeclared(mut self, enabled: bool) -> Self {
+        self.reject = enabled;
+        self
+    }
+
+    /// Returns whether the trailer field `name` is allowed.
+    pub fn is_allowed(&self, name: &HeaderName) -> bool {
+        self.allowed.contains(name)
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for AllowedTrailers<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = B::Data;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        loop {
+            let frame = match this.inner.as_mut().poll_frame(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err.into()))),
+                Poll::Ready(Some(Ok(frame))) => frame,
+            };
+
+            let trailers = match frame.into_trailers() {
+                Ok(trailers) => trailers,
+                Err(frame) => return Poll::Ready(Some(Ok(frame))),
+            };
+
+            if *this.reject {
+                if let Some(name) = trailers.keys().find(|name| !this.allowed.contains(*name)) {
+                    debug!(name = name.as_str(), "allowed_trailers: undeclared trailer");
+                    let err = UndeclaredTrailerError { name: name.clone() };
+                    return Poll::Ready(Some(Err(err.into())));
+                }
+                return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+            }
+
+            let allowed = retain_fields(trailers, |name| {
+                let allowed = this.allowed.contains(name);
+                if !allowed {
+                    trace!(
+                        name = name.as_str(),
+                        "allowed_trailers: removed undeclared trailer"
+                    );
+                }
+                allowed
+            });
+
+            if !allowed.is_empty() {
+                return Poll::Ready(Some(Ok(Frame::trailers(allowed))));
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// An error returned when a body yields a trailer field that was not declared.
+#[derive(Debug)]
+pub struct UndeclaredTrailerError {
+    name: HeaderName,
+}
+
+impl UndeclaredTrailerError {
+    /// Returns the name of the undeclared trailer field.
+    pub fn name(&self) -> &HeaderName {
+        &self.name
+    }
+}
+
+impl fmt::Display for UndeclaredTrailerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "undeclared trailer field `{}`", self.name)
+    }
+}
+
+impl Error for UndeclaredTrailerError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_body;
+    use crate::BodyExt;
+    use http::HeaderValue;
+
+    fn trailers() -> HeaderMap {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+        trailers.append("x-extra", HeaderValue::from_static("a"));
+        trailers.append("x-extra", HeaderValue::from_static("b"));
+        trailers
+    }
+
+    #[tokio::test]
+    async fn removes_undeclared() {
+        let allowed = vec![HeaderName::from_static("x-extra")];
+        let collected = AllowedTrailers::new(test_body(&["hello"], Some(trailers())), allowed)
+            .collect()
+            .await
+            .unwrap();
+        let trailers = collected.trailers().unwrap();
+        assert!(!trailers.contains_key("grpc-status"));
+        assert_eq!(trailers.get_all("x-extra").iter().count(), 2);
+        assert_eq!(collected.to_bytes(), "hello");
+    }
+
+    #[tokio::test]
+    async fn drops_empty_trailers() {
+        let body = AllowedTrailers::new(test_body(&["hello"], Some(trailers())), None);
+        let collected = body.collect().await.unwrap();
+        assert!(collected.trailers().is_none());
+    }
+
+    #[tokio::test]
+    async fn from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.append(TRAILER, HeaderValue::from_static("Grpc-Status , x-other"));
+        headers.append(TRAILER, HeaderValue::from_static("x-extra"));
+
+        let body = AllowedTrailers::from_headers(test_body(&["hello"], Some(trailers())), &headers);
+        assert!(body.is_allowed(&HeaderName::from_static("grpc-status")));
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.trailers(), Some(&trailers()));
+    }
+
+    #[tokio::test]
+    async fn rejects_undeclared() {
+        let allowed = vec![HeaderName::from_static("grpc-status")];
+        let mut body = AllowedTrailers::new(test_body(&["hello"], Some(trailers())), allowed)
+            .reject_undeclared(true);
+        assert!(body.frame().await.unwrap().unwrap().is_data());
+
+        let err = body.frame().await.unwrap().unwrap_err();
+        let err = err.downcast_ref::<UndeclaredTrailerError>().unwrap();
+        assert_eq!(err.name(), "x-extra");
+        assert_eq!(err.to_string(), "undeclared trailer field `x-extra`");
+    }
+}
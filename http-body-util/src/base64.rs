@@ -0,0 +1,490 @@
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use base64::{
+    engine::{
+        general_purpose::{GeneralPurpose, STANDARD},
+        Config,
+    },
+    DecodeError, Engine,
+};
+use bytes::{Buf, Bytes};
+use futures_core::ready;
+use http::HeaderMap;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+pin_project! {
+    /// A body that base64-encodes the data of the wrapped body.
+    ///
+    /// Data is encoded as it is received. Bytes that don't fill a whole 3-byte group are carried
+    /// over to the next frame, so the output is the same however the input is split. The final
+    /// group is written, padded if the engine pads, before any trailers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{Base64Encode, BodyExt, StreamBody};
+    /// use http_body::Frame;
+    /// use std::convert::Infallible;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let chunks: Vec<Result<_, Infallible>> = vec![
+    ///     Ok(Frame::data(Bytes::from("he"))),
+    ///     Ok(Frame::data(Bytes::from("llo"))),
+    /// ];
+    /// let body = Base64Encode::new(StreamBody::new(futures_util::stream::iter(chunks)));
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "aGVsbG8=");
+    /// # }
+    /// ```
+    pub struct Base64Encode<B> {
+        engine: GeneralPurpose,
+        pending: Vec<u8>,
+        trailers: Option<HeaderMap>,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> Base64Encode<B> {
+    /// Create a new `Base64Encode` using the standard alphabet with padding.
+    pub fn new(inner: B) -> Self {
+        Self::with_engine(inner, STANDARD)
+    }
+
+    /// Create a new `Base64Encode` using `engine`, such as
+    /// [`URL_SAFE_NO_PAD`](base64::engine::general_purpose::URL_SAFE_NO_PAD).
+    pub fn with_engine(inner: B, engine: GeneralPurpose) -> Self {
+        Self {
+            engine,
+            pending: Vec::with_capacity(3),
+            trailers: None,
+            inner,
+        }
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+/// Encode the whole 3-byte groups of `pending` followed by `src`, keeping the rest in `pending`.
+fn encode(engine: &GeneralPurpose, pending: &mut Vec<u8>, mut src: &[u8], dst: &mut String) {
+    if !pending.is_empty() {
+        let take = (3 - pending.len()).min(src.len());
+        pending.extend_from_slice(&src[..take]);
+        src = &src[take..];
+        if pending.len() < 3 {
+            return;
+        }
+        engine.encode_string(&pending[..], dst);
+        pending.clear();
+    }
+
+    let whole = src.len() - src.len() % 3;
+    engine.encode_string(&src[..whole], dst);
+    pending.extend_from_slice(&src[whole..]);
+}
+
+impl<B> Body for Base64Encode<B>
+where
+    B: Body,
+{
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        if let Some(trailers) = this.trailers.take() {
+            return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+        }
+
+        loop {
+            let mut out = String::new();
+            match ready!(this.inner.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(mut data) => {
+                        while data.has_remaining() {
+                            let chunk = data.chunk();
+                            encode(this.engine, this.pending, chunk, &mut out);
+                            let len = chunk.len();
+                            data.advance(len);
+                        }
+                    }
+                    Err(frame) => {
+                        let trailers = match frame.into_trailers() {
+                            Ok(trailers) => trailers,
+                            Err(_) => continue,
+                        };
+                        if this.pending.is_empty() {
+                            return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+                        }
+                        this.engine.encode_string(&this.pending[..], &mut out);
+                        this.pending.clear();
+                        *this.trailers = Some(trailers);
+                    }
+                },
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => {
+                    if this.pending.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    this.engine.encode_string(&this.pending[..], &mut out);
+                    this.pending.clear();
+                }
+            }
+
+            if !out.is_empty() {
+                return Poll::Ready(Some(Ok(Frame::data(Bytes::from(out)))));
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.pending.is_empty() && self.trailers.is_none() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let padding = self.engine.config().encode_padding();
+        let encoded_len = |len: u64| {
+            usize::try_from(len)
+                .ok()
+                .and_then(|len| len.checked_add(self.pending.len()))
+                .and_then(|len| base64::encoded_len(len, padding))
+                .map(|len| len as u64)
+        };
+
+        let inner = self.inner.size_hint();
+        let mut hint = SizeHint::new();
+        if let Some(lower) = encoded_len(inner.lower()) {
+            hint.set_lower(lower);
+        }
+        if let Some(upper) = inner.upper().and_then(encoded_len) {
+            hint.set_upper(upper);
+        }
+        hint
+    }
+}
+
+impl<B> fmt::Debug for Base64Encode<B>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Base64Encode")
+            .field("pending", &self.pending.len())
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+pin_project! {
+    /// A body that base64-decodes the data of the wrapped body.
+    ///
+    /// Data is decoded as it is received. Characters that don't fill a whole 4-character group
+    /// are carried over to the next frame, so the output is the same however the input is split.
+    /// ASCII whitespace, such as the line breaks of MIME-wrapped input, is ignored.
+    ///
+    /// Invalid input, including padding followed by more data or a truncated final group, fails
+    /// the body with a [`DecodeError`](base64::DecodeError).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{Base64Decode, BodyExt, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Base64Decode::new(Full::new(Bytes::from("aGVs\r\nbG8=")));
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    /// # }
+    /// ```
+    pub struct Base64Decode<B> {
+        engine: GeneralPurpose,
+        pending: Vec<u8>,
+        padded: bool,
+        trailers: Option<HeaderMap>,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> Base64Decode<B> {
+    /// Create a new `Base64Decode` using the standard alphabet with padding.
+    pub fn new(inner: B) -> Self {
+        Self::with_engine(inner, STANDARD)
+    }
+
+    /// Create a new `Base64Decode` using `engine`, such as
+    /// [`URL_SAFE_NO_PAD`](base64::engine::general_purpose::URL_SAFE_NO_PAD).
+    pub fn with_engine(inner: B, engine: GeneralPurpose) -> Self {
+        Self {
+            engine,
+            pending: Vec::with_capacity(4),
+            padded: false,
+            trailers: None,
+            inner,
+        }
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for Base64Decode<B>
+where
+    B: Body,
+    B::Error: Into<BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        if let Some(trailers) = this.trailers.take() {
+            return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+        }
+
+        loop {
+            let mut out = Vec::new();
+            match ready!(this.inner.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(mut data) => {
+                        while data.has_remaining() {
+                            let chunk = data.chunk();
+                            this.pending
+                                .extend(chunk.iter().filter(|byte| !byte.is_ascii_whitespace()));
+                            let len = chunk.len();
+                            data.advance(len);
+                        }
+                        if *this.padded && !this.pending.is_empty() {
+                            return Poll::Ready(Some(Err(DecodeError::InvalidPadding.into())));
+                        }
+
+                        let whole = this.pending.len() - this.pending.len() % 4;
+                        if whole > 0 {
+                            this.engine.decode_vec(&this.pending[..whole], &mut out)?;
+                            *this.padded = this.pending[whole - 1] == b'=';
+                            this.pending.drain(..whole);
+                        }
+                    }
+                    Err(frame) => {
+                        let trailers = match frame.into_trailers() {
+                            Ok(trailers) => trailers,
+                            Err(_) => continue,
+                        };
+                        if this.pending.is_empty() {
+                            return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+                        }
+                        this.engine.decode_vec(&this.pending[..], &mut out)?;
+                        this.pending.clear();
+                        *this.trailers = Some(trailers);
+                    }
+                },
+                Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+                None => {
+                    if this.pending.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    this.engine.decode_vec(&this.pending[..], &mut out)?;
+                    this.pending.clear();
+                }
+            }
+
+            if !out.is_empty() {
+                return Poll::Ready(Some(Ok(Frame::data(Bytes::from(out)))));
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.pending.is_empty() && self.trailers.is_none() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        // Whitespace and padding make the decoded length unknown in advance.
+        let mut hint = SizeHint::new();
+        if let Some(upper) = self.inner.size_hint().upper() {
+            // Without padding, the last 2 or 3 characters decode to 1 or 2 bytes.
+            let len = upper.saturating_add(self.pending.len() as u64);
+            hint.set_upper(len / 4 * 3 + len % 4 * 3 / 4);
+        }
+        hint
+    }
+}
+
+impl<B> fmt::Debug for Base64Decode<B>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Base64Decode")
+            .field("pending", &self.pending.len())
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use http::HeaderValue;
+
+    use super::*;
+    use crate::test_support::test_body;
+    use crate::{BodyExt, Full};
+
+    #[tokio::test]
+    async fn encode_split_groups() {
+        let chunks: Vec<&'static [u8]> = vec![b"a", b"", b"bcd", b"e", b"fg"];
+        let body = Base64Encode::new(test_body(&chunks, None));
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "YWJjZGVmZw==");
+
+        let chunks: Vec<&'static [u8]> = vec![b"\xfb", b"\xff"];
+        let body = Base64Encode::with_engine(test_body(&chunks, None), URL_SAFE_NO_PAD);
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "-_8");
+    }
+
+    #[tokio::test]
+    async fn encode_flushes_before_trailers() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-done", HeaderValue::from_static("1"));
+
+        let mut body = Base64Encode::new(test_body(&[b"abcd"], Some(trailers.clone())));
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "YWJj");
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "ZA==");
+        let frame = body.frame().await.unwrap().unwrap();
+        assert_eq!(frame.into_trailers().unwrap(), trailers);
+        assert!(body.frame().await.is_none());
+    }
+
+    #[test]
+    fn encode_size_hint() {
+        let body = Base64Encode::new(Full::new(Bytes::from_static(b"abcd")));
+        assert_eq!(body.size_hint().exact(), Some(8));
+
+        let body =
+            Base64Encode::with_engine(Full::new(Bytes::from_static(b"abcd")), URL_SAFE_NO_PAD);
+        assert_eq!(body.size_hint().exact(), Some(6));
+    }
+
+    #[test]
+    fn decode_size_hint() {
+        let body =
+            Base64Decode::with_engine(Full::new(Bytes::from_static(b"-_8")), URL_SAFE_NO_PAD);
+        assert_eq!(body.size_hint().upper(), Some(2));
+
+        let body = Base64Decode::new(Full::new(Bytes::from_static(b"YWJjZA==")));
+        assert_eq!(body.size_hint().upper(), Some(6));
+
+        let body = Base64Decode::new(Hinted(SizeHint::with_exact(u64::MAX)));
+        assert_eq!(body.size_hint().upper(), Some(u64::MAX / 4 * 3 + 2));
+    }
+
+    /// An empty body with a fixed size hint.
+    struct Hinted(SizeHint);
+
+    impl Body for Hinted {
+        type Data = Bytes;
+        type Error = Infallible;
+
+        fn poll_frame(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+            Poll::Ready(None)
+        }
+
+        fn size_hint(&self) -> SizeHint {
+            self.0.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn decode_split_groups() {
+        let chunks: Vec<&'static [u8]> = vec![b"YW", b"JjZ", b"GVm\n", b"Zw", b"=="];
+        let body = Base64Decode::new(test_body(&chunks, None));
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "abcdefg");
+
+        let chunks: Vec<&'static [u8]> = vec![b"-", b"_8"];
+        let body = Base64Decode::with_engine(test_body(&chunks, None), URL_SAFE_NO_PAD);
+        assert_eq!(body.collect().await.unwrap().to_bytes(), &b"\xfb\xff"[..]);
+    }
+
+    #[tokio::test]
+    async fn decode_errors() {
+        let decode = |chunks: Vec<&'static [u8]>| async move {
+            let err = Base64Decode::new(test_body(&chunks, None))
+                .collect()
+                .await
+                .unwrap_err();
+            *err.downcast::<DecodeError>().unwrap()
+        };
+
+        assert!(matches!(
+            decode(vec![b"YQ==", b"YQ=="]).await,
+            DecodeError::InvalidPadding
+        ));
+        assert!(matches!(
+            decode(vec![b"YWJ"]).await,
+            DecodeError::InvalidPadding
+        ));
+        assert!(matches!(
+            decode(vec![b"YW*j"]).await,
+            DecodeError::InvalidByte(2, b'*')
+        ));
+    }
+}
@@ -0,0 +1,489 @@
+//! Bridges between bodies and blocking I/O.
+//!
+//! These are meant for synchronous code running on a thread that may block, such as inside
+//! `tokio::task::spawn_blocking`. They must not be used on an async runtime's worker threads.
+
+use std::{
+    error::Error,
+    fmt,
+    io::{self, Read, Write},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use http_body::{Body, Frame, SizeHint};
+
+use crate::util::into_io_error;
+
+/// A blocking [`Read`] reading the data of a body.
+///
+/// Each read blocks the current thread until the body yields more data. Trailers are skipped, and
+/// errors from the body are returned as [`io::Error`]s. A read can be cancelled from another
+/// thread with a [`CancelHandle`].
+///
+/// # Example
+///
+/// ```
+/// use std::io::Read;
+///
+/// use bytes::Bytes;
+/// use http_body_util::{blocking, Full};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let body = Full::new(Bytes::from("hello"));
+///
+/// let data = tokio::task::spawn_blocking(move || {
+///     let mut data = String::new();
+///     blocking::Reader::new(body).read_to_string(&mut data).unwrap();
+///     data
+/// })
+/// .await
+/// .unwrap();
+/// assert_eq!(data, "hello");
+/// # }
+/// ```
+pub struct Reader<B: Body> {
+    body: Pin<Box<B>>,
+    chunk: Option<B::Data>,
+    done: bool,
+    signal: Arc<Signal>,
+}
+
+/// Wakes the thread blocked in a read.
+#[derive(Debug, Default)]
+struct Signal {
+    cancelled: AtomicBool,
+    thread: Mutex<Option<Thread>>,
+}
+
+impl Signal {
+    fn unpark(&self) {
+        if let Some(thread) = &*self.thread.lock().unwrap() {
+            thread.unpark();
+        }
+    }
+}
+
+impl Wake for Signal {
+    fn wake(self: Arc<Self>) {
+        self.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.unpark();
+    }
+}
+
+impl<B: Body> Reader<B> {
+    /// Create a new `Reader`.
+    pub fn new(body: B) -> Self {
+        Self {
+            body: Box::pin(body),
+            chunk: None,
+            done: false,
+            signal: Arc::default(),
+        }
+    }
+
+    /// Returns a handle that can cancel reads from another thread.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle {
+            signal: self.signal.clone(),
+        }
+    }
+
+    /// Blocks until the body yields its next frame.
+    fn next_frame(&mut self) -> io::Result<Option<Frame<B::Data>>>
+    where
+        B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        *self.signal.thread.lock().unwrap() = Some(thread::current());
+        let waker = Waker::from(self.signal.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            if self.signal.cancelled.load(Ordering::Acquire) {
+                return Err(io::Error::new(io::ErrorKind::Other, "body read cancelled"));
+            }
+
+            match self.body.as_mut().poll_frame(&mut cx) {
+                Poll::Ready(Some(Ok(frame))) => return Ok(Some(frame)),
+                Poll::Ready(Some(Err(err))) => return Err(into_io_error(err.into())),
+                Poll::Ready(None) => return Ok(None),
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+}
+
+impl<B> Read for Reader<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            if let Some(chunk) = &mut self.chunk {
+                if chunk.has_remaining() {
+                    let len = chunk.chunk().len().min(buf.len());
+                    buf[..len].copy_from_slice(&chunk.chunk()[..len]);
+                    chunk.advance(len);
+                    return Ok(len);
+                }
+            }
+
+            if self.done {
+                return Ok(0);
+            }
+            match self.next_frame()? {
+                Some(frame) => self.chunk = frame.into_data().ok(),
+                None => {
+                    self.chunk = None;
+                    self.done = true;
+                }
+            }
+        }
+    }
+}
+
+impl<B: Body> fmt::Debug for Reader<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reader").field("done", &self.done).finish()
+    }
+}
+
+/// A handle to cancel the reads of a [`Reader`].
+///
+/// Once cancelled, the current and all future reads return an error.
+#[derive(Clone, Debug)]
+pub struct CancelHandle {
+    signal: Arc<Signal>,
+}
+
+impl CancelHandle {
+    /// Cancel the reads of the `Reader`.
+    pub fn cancel(&self) {
+        self.signal.cancelled.store(true, Ordering::Release);
+        self.signal.unpark();
+    }
+
+    /// Returns whether the reads have been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.signal.cancelled.load(Ordering::Acquire)
+    }
+}
+
+/// A body whose data is written by a blocking [`Writer`].
+///
+/// Writes are buffered until the body is polled, up to a fixed capacity in bytes. Once the buffer
+/// is full, writes block until the body has taken the buffered data. The body ends when the writer
+/// is dropped.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Write;
+///
+/// use http_body_util::{blocking::WriterBody, BodyExt};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let (mut writer, body) = WriterBody::new(8 * 1024);
+///
+/// tokio::task::spawn_blocking(move || {
+///     for i in 0..3 {
+///         writeln!(writer, "line {i}").unwrap();
+///     }
+/// });
+///
+/// let data = body.collect().await.unwrap().to_bytes();
+/// assert_eq!(data, "line 0\nline 1\nline 2\n");
+/// # }
+/// ```
+pub struct WriterBody {
+    shared: Arc<Shared>,
+}
+
+/// A blocking [`Write`] whose data is yielded by a [`WriterBody`].
+pub struct Writer {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    state: Mutex<WriterState>,
+    writable: Condvar,
+    capacity: usize,
+}
+
+struct WriterState {
+    buf: BytesMut,
+    waker: Option<Waker>,
+    error: Option<io::Error>,
+    writer_closed: bool,
+    body_closed: bool,
+}
+
+impl WriterBody {
+    /// Create a new `Writer` and `WriterBody` pair buffering up to `capacity` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> (Writer, WriterBody) {
+        assert!(capacity > 0, "capacity must be at least 1");
+
+        let shared = Arc::new(Shared {
+            state: Mutex::new(WriterState {
+                buf: BytesMut::new(),
+                waker: None,
+                error: None,
+                writer_closed: false,
+                body_closed: false,
+            }),
+            writable: Condvar::new(),
+            capacity,
+        });
+        let writer = Writer {
+            shared: shared.clone(),
+        };
+        (writer, WriterBody { shared })
+    }
+}
+
+impl Body for WriterBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut state = self.shared.state.lock().unwrap();
+
+        if !state.buf.is_empty() {
+            let data = state.buf.split().freeze();
+            self.shared.writable.notify_one();
+            return Poll::Ready(Some(Ok(Frame::data(data))));
+        }
+        if let Some(err) = state.error.take() {
+            return Poll::Ready(Some(Err(err)));
+        }
+        if state.writer_closed {
+            return Poll::Ready(None);
+        }
+
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    fn is_end_stream(&self) -> bool {
+        let state = self.shared.state.lock().unwrap();
+        state.writer_closed && state.buf.is_empty() && state.error.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let state = self.shared.state.lock().unwrap();
+        let buffered = state.buf.len() as u64;
+        let mut hint = SizeHint::new();
+        hint.set_lower(buffered);
+        if state.writer_closed {
+            hint.set_upper(buffered);
+        }
+        hint
+    }
+}
+
+impl Drop for WriterBody {
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().body_closed = true;
+        self.shared.writable.notify_one();
+    }
+}
+
+impl fmt::Debug for WriterBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriterBody")
+            .field("capacity", &self.shared.capacity)
+            .finish()
+    }
+}
+
+impl Writer {
+    /// Abort the body with an error instead of ending it normally.
+    ///
+    /// Data written before the error is still yielded by the body.
+    pub fn abort(self, err: io::Error) {
+        self.shared.state.lock().unwrap().error = Some(err);
+    }
+
+    /// Returns whether the body has been dropped.
+    ///
+    /// Once it has, writes fail with [`io::ErrorKind::BrokenPipe`].
+    pub fn is_closed(&self) -> bool {
+        self.shared.state.lock().unwrap().body_closed
+    }
+}
+
+impl Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let capacity = self.shared.capacity;
+        let mut state = self.shared.state.lock().unwrap();
+        while state.buf.len() >= capacity && !state.body_closed {
+            state = self.shared.writable.wait(state).unwrap();
+        }
+        if state.body_closed {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "body was dropped",
+            ));
+        }
+
+        let len = buf.len().min(capacity - state.buf.len());
+        state.buf.extend_from_slice(&buf[..len]);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for Writer {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.writer_closed = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl fmt::Debug for Writer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Writer")
+            .field("capacity", &self.shared.capacity)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use futures_util::stream;
+
+    use super::*;
+    use crate::{BodyExt, StreamBody};
+
+    #[test]
+    fn reads_from_another_thread() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Bytes>(1);
+        let body = StreamBody::new(stream::poll_fn(move |cx| {
+            rx.poll_recv(cx)
+                .map(|data| data.map(|data| Ok::<_, Infallible>(Frame::data(data))))
+        }));
+
+        let reader = thread::spawn(move || {
+            let mut data = String::new();
+            Reader::new(body).read_to_string(&mut data).unwrap();
+            data
+        });
+        for chunk in ["hello", " ", "world"] {
+            tx.blocking_send(Bytes::from(chunk)).unwrap();
+        }
+        drop(tx);
+
+        assert_eq!(reader.join().unwrap(), "hello world");
+    }
+
+    #[test]
+    fn propagates_errors() {
+        let frames = vec![Err::<Frame<Bytes>, _>(io::Error::new(
+            io::ErrorKind::ConnectionReset,
+            "reset",
+        ))];
+        let mut reader = Reader::new(StreamBody::new(stream::iter(frames)));
+
+        let err = reader.read(&mut [0; 8]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+    }
+
+    #[test]
+    fn cancel() {
+        let body = StreamBody::new(stream::pending::<Result<Frame<Bytes>, Infallible>>());
+        let mut reader = Reader::new(body);
+        let cancel = reader.cancel_handle();
+
+        let reader = thread::spawn(move || reader.read(&mut [0; 8]));
+        cancel.cancel();
+
+        let err = reader.join().unwrap().unwrap_err();
+        assert_eq!(err.to_string(), "body read cancelled");
+        assert!(cancel.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn writer_backpressure() {
+        let (mut writer, mut body) = WriterBody::new(4);
+
+        let writer = tokio::task::spawn_blocking(move || {
+            writer.write_all(b"hello world").unwrap();
+        });
+
+        let mut data = Vec::new();
+        while let Some(frame) = body.frame().await {
+            let frame = frame.unwrap().into_data().unwrap();
+            assert!(frame.len() <= 4);
+            data.extend_from_slice(&frame);
+        }
+        writer.await.unwrap();
+        assert_eq!(data, b"hello world");
+        assert!(body.is_end_stream());
+    }
+
+    #[tokio::test]
+    async fn writer_abort() {
+        let (mut writer, mut body) = WriterBody::new(64);
+        writer.write_all(b"partial").unwrap();
+        writer.abort(io::Error::new(io::ErrorKind::Other, "render failed"));
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "partial");
+        let err = body.frame().await.unwrap().unwrap_err();
+        assert_eq!(err.to_string(), "render failed");
+    }
+
+    #[test]
+    fn writer_body_dropped() {
+        let (mut writer, body) = WriterBody::new(4);
+        writer.write_all(b"abcd").unwrap();
+
+        let dropper = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(10));
+            drop(body);
+        });
+        let err = writer.write_all(b"efgh").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        assert!(writer.is_closed());
+        dropper.join().unwrap();
+    }
+}
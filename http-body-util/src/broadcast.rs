@@ -0,0 +1,434 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+};
+
+use bytes::Buf;
+use http_body::{Body, Frame, SizeHint};
+
+use crate::util::clone_frame;
+
+/// A body that forks another body into multiple consumers.
+///
+/// Every clone of a `BroadcastBody` is a separate consumer that observes every frame of the
+/// source body, starting at the position of the handle it was cloned from. Whichever consumer
+/// gets ahead drives the source body, and frames are buffered until every consumer has seen them.
+///
+/// The buffer is bounded. When it is full, [`BroadcastBody::new`] makes the fastest consumers
+/// wait for the slowest one, while [`BroadcastBody::lossy`] drops the oldest frame and reports
+/// [`BroadcastError::Lagged`] to consumers that had not seen it yet.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body_util::{BodyExt, BroadcastBody, Full};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let storage = BroadcastBody::new(Full::new(Bytes::from("upload")), 16);
+/// let scanner = storage.clone();
+///
+/// let (a, b) = tokio::join!(storage.collect(), scanner.collect());
+/// assert_eq!(a.unwrap().to_bytes(), "upload");
+/// assert_eq!(b.unwrap().to_bytes(), "upload");
+/// # }
+/// ```
+pub struct BroadcastBody<B: Body> {
+    id: usize,
+    shared: Arc<Mutex<Shared<B>>>,
+    wakers: Arc<Wakers>,
+}
+
+struct Shared<B: Body> {
+    body: Pin<Box<B>>,
+    frames: VecDeque<Frame<B::Data>>,
+    /// Absolute position of `frames[0]` in the frame sequence.
+    offset: u64,
+    capacity: usize,
+    lossy: bool,
+    cursors: Vec<Option<u64>>,
+    end: Option<Result<(), Arc<B::Error>>>,
+}
+
+#[derive(Default)]
+struct Wakers {
+    list: Mutex<Vec<(usize, Waker)>>,
+}
+
+impl<B> BroadcastBody<B>
+where
+    B: Body,
+{
+    /// Create a new `BroadcastBody`, buffering at most `capacity` frames.
+    ///
+    /// Once the buffer is full, consumers that are ahead wait until the slowest consumer has
+    /// caught up.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `capacity` is 0.
+    pub fn new(body: B, capacity: usize) -> Self {
+        Self::with_policy(body, capacity, false)
+    }
+
+    /// Create a new `BroadcastBody`, buffering at most `capacity` frames.
+    ///
+    /// Once the buffer is full, the oldest frame is dropped and consumers that have not seen it
+    /// yet return [`BroadcastError::Lagged`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `capacity` is 0.
+    pub fn lossy(body: B, capacity: usize) -> Self {
+        Self::with_policy(body, capacity, true)
+    }
+
+    fn with_policy(body: B, capacity: usize, lossy: bool) -> Self {
+        assert!(capacity > 0, "`capacity` must be at least 1");
+        let shared = Shared {
+            body: Box::pin(body),
+            frames: VecDeque::new(),
+            offset: 0,
+            capacity,
+            lossy,
+            cursors: vec![Some(0)],
+            end: None,
+        };
+        Self {
+            id: 0,
+            shared: Arc::new(Mutex::new(shared)),
+            wakers: Arc::default(),
+        }
+    }
+}
+
+impl<B: Body> Shared<B> {
+    fn cursor(&self, id: usize) -> u64 {
+        self.cursors[id].expect("consumer is registered")
+    }
+
+    /// Drop every buffered frame that all consumers have seen, returning whether any were.
+    fn trim(&mut self) -> bool {
+        let min = self.cursors.iter().flatten().min().copied();
+        let min = min.unwrap_or(self.offset + self.frames.len() as u64);
+        let mut trimmed = false;
+        while self.offset < min && self.frames.pop_front().is_some() {
+            self.offset += 1;
+            trimmed = true;
+        }
+        trimmed
+    }
+}
+
+impl<B> Body for BroadcastBody<B>
+where
+    B: Body,
+    B::Data: Clone,
+{
+    type Data = B::Data;
+    type Error = BroadcastError<B::Error>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        let mut shared = this.shared.lock().unwrap();
+
+        loop {
+            let cursor = shared.cursor(this.id);
+
+            if cursor < shared.offset {
+                let missed = shared.offset - cursor;
+                shared.cursors[this.id] = Some(shared.offset);
+                return Poll::Ready(Some(Err(BroadcastError::Lagged(missed))));
+            }
+
+            let index = (cursor - shared.offset) as usize;
+            if let Some(frame) = shared.frames.get(index) {
+                let frame = clone_frame(frame);
+                shared.cursors[this.id] = Some(cursor + 1);
+                if shared.trim() {
+                    this.wakers.wake_all();
+                }
+                return Poll::Ready(Some(Ok(frame)));
+            }
+
+            match &shared.end {
+                Some(Ok(())) => return Poll::Ready(None),
+                Some(Err(err)) => return Poll::Ready(Some(Err(BroadcastError::Body(err.clone())))),
+                None => {}
+            }
+
+            this.wakers.register(this.id, cx.waker());
+
+            let full = shared.frames.len() >= shared.capacity;
+            if full && !shared.lossy {
+                // Wait for the slowest consumer to catch up.
+                return Poll::Pending;
+            }
+
+            let waker = Waker::from(this.wakers.clone());
+            let mut source_cx = Context::from_waker(&waker);
+            match shared.body.as_mut().poll_frame(&mut source_cx) {
+                Poll::Ready(Some(Ok(frame))) => {
+                    if full {
+                        shared.frames.pop_front();
+                        shared.offset += 1;
+                    }
+                    shared.frames.push_back(frame);
+                }
+                Poll::Ready(Some(Err(err))) => shared.end = Some(Err(Arc::new(err))),
+                Poll::Ready(None) => shared.end = Some(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+            this.wakers.wake_all();
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        let shared = self.shared.lock().unwrap();
+        let cursor = shared.cursor(self.id);
+        let caught_up = cursor >= shared.offset + shared.frames.len() as u64;
+        caught_up && matches!(shared.end, Some(Ok(())))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let shared = self.shared.lock().unwrap();
+        let cursor = shared.cursor(self.id);
+        let skip = cursor.saturating_sub(shared.offset) as usize;
+        let buffered = shared
+            .frames
+            .iter()
+            .skip(skip)
+            .filter_map(|frame| frame.data_ref())
+            .map(|data| data.remaining() as u64)
+            .sum::<u64>();
+
+        let inner = if shared.end.is_some() {
+            SizeHint::with_exact(0)
+        } else {
+            shared.body.size_hint()
+        };
+        let mut hint = SizeHint::new();
+        if let Some(upper) = inner.upper() {
+            hint.set_upper(upper + buffered);
+        }
+        hint.set_lower(inner.lower() + buffered);
+        hint
+    }
+}
+
+impl<B: Body> Clone for BroadcastBody<B> {
+    fn clone(&self) -> Self {
+        let mut shared = self.shared.lock().unwrap();
+        let cursor = shared.cursor(self.id);
+        let id = match shared.cursors.iter().position(Option::is_none) {
+            Some(id) => {
+                shared.cursors[id] = Some(cursor);
+                id
+            }
+            None => {
+                shared.cursors.push(Some(cursor));
+                shared.cursors.len() - 1
+            }
+        };
+        Self {
+            id,
+            shared: self.shared.clone(),
+            wakers: self.wakers.clone(),
+        }
+    }
+}
+
+impl<B: Body> Drop for BroadcastBody<B> {
+    fn drop(&mut self) {
+        if let Ok(mut shared) = self.shared.lock() {
+            shared.cursors[self.id] = None;
+            shared.trim();
+        }
+        self.wakers.remove(self.id);
+        // The dropped consumer may have been the one holding everyone else back.
+        self.wakers.wake_all();
+    }
+}
+
+impl<B: Body> fmt::Debug for BroadcastBody<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BroadcastBody")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl Wakers {
+    fn register(&self, id: usize, waker: &Waker) {
+        let mut list = self.list.lock().unwrap();
+        match list.iter_mut().find(|(i, _)| *i == id) {
+            Some((_, existing)) if existing.will_wake(waker) => {}
+            Some((_, existing)) => *existing = waker.clone(),
+            None => list.push((id, waker.clone())),
+        }
+    }
+
+    fn remove(&self, id: usize) {
+        if let Ok(mut list) = self.list.lock() {
+            list.retain(|(i, _)| *i != id);
+        }
+    }
+
+    fn wake_all(&self) {
+        let list = std::mem::take(&mut *self.list.lock().unwrap());
+        for (_, waker) in list {
+            waker.wake();
+        }
+    }
+}
+
+impl Wake for Wakers {
+    fn wake(self: Arc<Self>) {
+        self.wake_all();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.wake_all();
+    }
+}
+
+/// An error returned by [`BroadcastBody`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BroadcastError<E> {
+    /// The source body returned an error, which is shared by every consumer.
+    Body(Arc<E>),
+    /// The consumer fell behind a [lossy] broadcast and missed this many frames.
+    ///
+    /// [lossy]: BroadcastBody::lossy
+    Lagged(u64),
+}
+
+impl<E: fmt::Display> fmt::Display for BroadcastError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BroadcastError::Body(err) => err.fmt(f),
+            BroadcastError::Lagged(missed) => write!(f, "consumer lagged by {missed} frames"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for BroadcastError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BroadcastError::Body(err) => Some(&**err),
+            BroadcastError::Lagged(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures_util::stream;
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::test_body;
+    use crate::{BodyExt, Full, StreamBody};
+
+    #[tokio::test]
+    async fn every_consumer_sees_every_frame() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("foo", "bar".parse().unwrap());
+        let body = Full::new(Bytes::from("hello")).with_trailers(async move { Some(Ok(trailers)) });
+
+        let a = BroadcastBody::new(body, 1);
+        let b = a.clone();
+        let c = b.clone();
+
+        let (a, b, c) = tokio::join!(a.collect(), b.collect(), c.collect());
+        for collected in [a.unwrap(), b.unwrap(), c.unwrap()] {
+            assert_eq!(collected.trailers().unwrap()["foo"], "bar");
+            assert_eq!(collected.to_bytes(), "hello");
+        }
+    }
+
+    #[tokio::test]
+    async fn fast_consumer_waits_for_slow_one() {
+        let mut fast = BroadcastBody::new(test_body(&["a", "b", "c"], None), 2);
+        let mut slow = fast.clone();
+
+        assert_eq!(
+            fast.frame().await.unwrap().unwrap().into_data().unwrap(),
+            "a"
+        );
+        assert_eq!(
+            fast.frame().await.unwrap().unwrap().into_data().unwrap(),
+            "b"
+        );
+
+        let waker = futures_util::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(Pin::new(&mut fast).poll_frame(&mut cx).is_pending());
+
+        assert_eq!(
+            slow.frame().await.unwrap().unwrap().into_data().unwrap(),
+            "a"
+        );
+        assert_eq!(
+            fast.frame().await.unwrap().unwrap().into_data().unwrap(),
+            "c"
+        );
+
+        let rest = slow.collect().await.unwrap().to_bytes();
+        assert_eq!(rest, "bc");
+        assert!(fast.frame().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn lossy_consumer_reports_lag() {
+        let mut fast = BroadcastBody::lossy(test_body(&["a", "b", "c"], None), 1);
+        let mut slow = fast.clone();
+
+        let collected = (&mut fast).collect().await.unwrap().to_bytes();
+        assert_eq!(collected, "abc");
+
+        match slow.frame().await.unwrap() {
+            Err(BroadcastError::Lagged(missed)) => assert_eq!(missed, 2),
+            other => panic!("expected lag, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn dropped_consumer_releases_buffer() {
+        let mut fast = BroadcastBody::new(test_body(&["a", "b", "c"], None), 1);
+        let slow = fast.clone();
+        drop(slow);
+
+        let collected = (&mut fast).collect().await.unwrap().to_bytes();
+        assert_eq!(collected, "abc");
+        assert!(fast.is_end_stream());
+    }
+
+    #[tokio::test]
+    async fn errors_are_shared() {
+        let body = StreamBody::new(stream::iter(vec![
+            Ok(Frame::data(Bytes::from("a"))),
+            Err("oh no"),
+        ]));
+        let mut a = BroadcastBody::new(body, 4);
+        let mut b = a.clone();
+
+        for body in [&mut a, &mut b] {
+            assert!(body.frame().await.unwrap().is_ok());
+            match body.frame().await.unwrap() {
+                Err(BroadcastError::Body(err)) => assert_eq!(*err, "oh no"),
+                other => panic!("expected body error, got {:?}", other.map(|_| ())),
+            }
+        }
+    }
+}
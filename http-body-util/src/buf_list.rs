@@ -0,0 +1,271 @@
+use std::collections::VecDeque;
+use std::io::IoSlice;
+use std::iter::FromIterator;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+/// A list of [`Buf`]s that is itself a [`Buf`].
+///
+/// Reading from the list reads the buffers in the order they were pushed, without copying them
+/// into one allocation. [`chunks_vectored`](Buf::chunks_vectored) returns a slice for every
+/// buffer, allowing vectored writes, and [`copy_to_bytes`](Buf::copy_to_bytes) avoids copying
+/// when the front buffer can satisfy it.
+///
+/// # Example
+///
+/// ```
+/// use bytes::{Buf, Bytes};
+/// use http_body_util::BufList;
+///
+/// let mut bufs = BufList::new();
+/// bufs.push(Bytes::from("hello "));
+/// bufs.push(Bytes::from("world"));
+///
+/// assert_eq!(bufs.remaining(), 11);
+/// assert_eq!(bufs.copy_to_bytes(11), "hello world");
+/// ```
+#[derive(Clone, Debug)]
+pub struct BufList<T> {
+    bufs: VecDeque<T>,
+}
+
+impl<T> BufList<T> {
+    /// Create a new, empty `BufList`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of buffers in the list.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bufs.len()
+    }
+
+    /// Returns whether the list contains no buffers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bufs.is_empty()
+    }
+
+    /// Remove the first buffer of the list.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.bufs.pop_front()
+    }
+
+    /// Returns an iterator over the buffers of the list.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.bufs.iter()
+    }
+}
+
+impl<T: Buf> BufList<T> {
+    /// Append a buffer to the end of the list.
+    ///
+    /// Empty buffers are ignored.
+    #[inline]
+    pub fn push(&mut self, buf: T) {
+        if buf.has_remaining() {
+            self.bufs.push_back(buf);
+        }
+    }
+}
+
+impl<T: Buf> Buf for BufList<T> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.bufs.iter().map(|buf| buf.remaining()).sum()
+    }
+
+    #[inline]
+    fn has_remaining(&self) -> bool {
+        self.bufs.iter().any(|buf| buf.has_remaining())
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        self.bufs.front().map(Buf::chunk).unwrap_or_default()
+    }
+
+    #[inline]
+    fn advance(&mut self, mut cnt: usize) {
+        while cnt > 0 {
+            {
+                let front = &mut self.bufs[0];
+                let rem = front.remaining();
+                if rem > cnt {
+                    front.advance(cnt);
+                    return;
+                } else {
+                    front.advance(rem);
+                    cnt -= rem;
+                }
+            }
+            self.bufs.pop_front();
+        }
+    }
+
+    #[inline]
+    fn chunks_vectored<'t>(&'t self, dst: &mut [IoSlice<'t>]) -> usize {
+        if dst.is_empty() {
+            return 0;
+        }
+        let mut vecs = 0;
+        for buf in &self.bufs {
+            vecs += buf.chunks_vectored(&mut dst[vecs..]);
+            if vecs == dst.len() {
+                break;
+            }
+        }
+        vecs
+    }
+
+    #[inline]
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        // Our inner buffer may have an optimized version of copy_to_bytes, and if the whole
+        // request can be fulfilled by the front buffer, we can take advantage.
+        match self.bufs.front_mut() {
+            Some(front) if front.remaining() == len => {
+                let b = front.copy_to_bytes(len);
+                self.bufs.pop_front();
+                b
+            }
+            Some(front) if front.remaining() > len => front.copy_to_bytes(len),
+            _ => {
+                let rem = self.remaining();
+                assert!(len <= rem, "`len` greater than remaining");
+                let mut bm = BytesMut::with_capacity(len);
+                if rem == len {
+                    // .take() costs a lot more, so skip it if we don't need it
+                    bm.put(self);
+                } else {
+                    bm.put(self.take(len));
+                }
+                bm.freeze()
+            }
+        }
+    }
+}
+
+impl<T> Default for BufList<T> {
+    fn default() -> Self {
+        BufList {
+            bufs: VecDeque::new(),
+        }
+    }
+}
+
+impl<T: Buf> Extend<T> for BufList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for buf in iter {
+            self.push(buf);
+        }
+    }
+}
+
+impl<T: Buf> FromIterator<T> for BufList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut bufs = BufList::new();
+        bufs.extend(iter);
+        bufs
+    }
+}
+
+impl<T> IntoIterator for BufList<T> {
+    type Item = T;
+    type IntoIter = std::collections::vec_deque::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.bufs.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use super::*;
+
+    fn hello_world_buf() -> BufList<Bytes> {
+        BufList {
+            bufs: vec![Bytes::from("Hello"), Bytes::from(" "), Bytes::from("World")].into(),
+        }
+    }
+
+    #[test]
+    fn to_bytes_shorter() {
+        let mut bufs = hello_world_buf();
+        let old_ptr = bufs.chunk().as_ptr();
+        let start = bufs.copy_to_bytes(4);
+        assert_eq!(start, "Hell");
+        assert!(ptr::eq(old_ptr, start.as_ptr()));
+        assert_eq!(bufs.chunk(), b"o");
+        assert!(ptr::eq(old_ptr.wrapping_add(4), bufs.chunk().as_ptr()));
+        assert_eq!(bufs.remaining(), 7);
+    }
+
+    #[test]
+    fn to_bytes_eq() {
+        let mut bufs = hello_world_buf();
+        let old_ptr = bufs.chunk().as_ptr();
+        let start = bufs.copy_to_bytes(5);
+        assert_eq!(start, "Hello");
+        assert!(ptr::eq(old_ptr, start.as_ptr()));
+        assert_eq!(bufs.chunk(), b" ");
+        assert_eq!(bufs.remaining(), 6);
+    }
+
+    #[test]
+    fn to_bytes_longer() {
+        let mut bufs = hello_world_buf();
+        let start = bufs.copy_to_bytes(7);
+        assert_eq!(start, "Hello W");
+        assert_eq!(bufs.remaining(), 4);
+    }
+
+    #[test]
+    fn one_long_buf_to_bytes() {
+        let mut buf = BufList::default();
+        buf.push(b"Hello World" as &[_]);
+        assert_eq!(buf.copy_to_bytes(5), "Hello");
+        assert_eq!(buf.chunk(), b" World");
+    }
+
+    #[test]
+    fn chunks_vectored_all_segments() {
+        let bufs = hello_world_buf();
+        let mut dst = [IoSlice::new(&[]); 4];
+        assert_eq!(bufs.chunks_vectored(&mut dst), 3);
+        assert_eq!(&*dst[0], b"Hello");
+        assert_eq!(&*dst[1], b" ");
+        assert_eq!(&*dst[2], b"World");
+    }
+
+    #[test]
+    fn chunks_vectored_partial() {
+        let mut bufs = hello_world_buf();
+        bufs.advance(2);
+
+        let mut dst = [IoSlice::new(&[]); 2];
+        assert_eq!(bufs.chunks_vectored(&mut dst), 2);
+        assert_eq!(&*dst[0], b"llo");
+        assert_eq!(&*dst[1], b" ");
+        assert_eq!(bufs.chunks_vectored(&mut []), 0);
+    }
+
+    #[test]
+    fn push_skips_empty() {
+        let mut bufs: BufList<Bytes> = vec![Bytes::new(), Bytes::from("a")].into_iter().collect();
+        bufs.push(Bytes::new());
+        assert_eq!(bufs.len(), 1);
+        assert_eq!(bufs.pop().unwrap(), "a");
+        assert!(bufs.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "`len` greater than remaining")]
+    fn buf_to_bytes_too_many() {
+        hello_world_buf().copy_to_bytes(42);
+    }
+}
@@ -2,22 +2,28 @@
 
 use std::{
     fmt::Display,
+    future::Future,
     pin::Pin,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
 };
 
 use bytes::Buf;
+use futures_sink::Sink;
 use http::HeaderMap;
-use http_body::{Body, Frame};
+use http_body::{Body, Frame, SizeHint};
 use pin_project_lite::pin_project;
 use tokio::sync::{mpsc, oneshot};
 
+use crate::BodySink;
+
 pin_project! {
     /// A body backed by a channel.
     pub struct Channel<D, E = std::convert::Infallible> {
         rx_frame: mpsc::Receiver<Frame<D>>,
         #[pin]
         rx_error: oneshot::Receiver<E>,
+        remaining: Option<u64>,
     }
 }
 
@@ -28,9 +34,33 @@ impl<D, E> Channel<D, E> {
     /// attempts to send new messages will wait until a message is received from the channel. The
     /// provided buffer capacity must be at least 1.
     pub fn new(buffer: usize) -> (Sender<D, E>, Self) {
+        Self::new_inner(buffer, None)
+    }
+
+    /// Create a new channel body that will carry `len` bytes of data.
+    ///
+    /// The size hint of the body is exact, decreasing as data is received, so a `Content-Length`
+    /// can be sent instead of using chunked encoding. The senders are expected to send exactly
+    /// `len` bytes; this is not enforced.
+    pub fn with_content_length(buffer: usize, len: u64) -> (Sender<D, E>, Self) {
+        Self::new_inner(buffer, Some(len))
+    }
+
+    fn new_inner(buffer: usize, remaining: Option<u64>) -> (Sender<D, E>, Self) {
         let (tx_frame, rx_frame) = mpsc::channel(buffer);
         let (tx_error, rx_error) = oneshot::channel();
-        (Sender { tx_frame, tx_error }, Self { rx_frame, rx_error })
+        let tx = Sender {
+            tx_frame,
+            tx_error: Arc::new(Mutex::new(Some(tx_error))),
+            permit: None,
+            reserve: Mutex::new(None),
+        };
+        let body = Self {
+            rx_frame,
+            rx_error,
+            remaining,
+        };
+        (tx, body)
     }
 }
 
@@ -48,19 +78,37 @@ where
         let this = self.project();
 
         match this.rx_frame.poll_recv(cx) {
-            Poll::Ready(frame @ Some(_)) => return Poll::Ready(frame.map(Ok)),
+            Poll::Ready(Some(frame)) => {
+                trace!("channel: received frame");
+                if let (Some(remaining), Some(data)) = (this.remaining, frame.data_ref()) {
+                    *remaining = remaining.saturating_sub(data.remaining() as u64);
+                }
+                return Poll::Ready(Some(Ok(frame)));
+            }
             Poll::Ready(None) | Poll::Pending => {}
         }
 
-        use core::future::Future;
         match this.rx_error.poll(cx) {
-            Poll::Ready(Ok(error)) => return Poll::Ready(Some(Err(error))),
-            Poll::Ready(Err(_)) => return Poll::Ready(None),
+            Poll::Ready(Ok(error)) => {
+                debug!("channel: aborted");
+                return Poll::Ready(Some(Err(error)));
+            }
+            Poll::Ready(Err(_)) => {
+                trace!("channel: all senders dropped");
+                return Poll::Ready(None);
+            }
             Poll::Pending => {}
         }
 
         Poll::Pending
     }
+
+    fn size_hint(&self) -> SizeHint {
+        match self.remaining {
+            Some(remaining) => SizeHint::with_exact(remaining),
+            None => SizeHint::default(),
+        }
+    }
 }
 
 impl<D, E: std::fmt::Debug> std::fmt::Debug for Channel<D, E> {
@@ -68,19 +116,48 @@ impl<D, E: std::fmt::Debug> std::fmt::Debug for Channel<D, E> {
         f.debug_struct("Channel")
             .field("rx_frame", &self.rx_frame)
             .field("rx_error", &self.rx_error)
+            .field("remaining", &self.remaining)
             .finish()
     }
 }
 
 /// A sender half created through [`Channel::new`].
+///
+/// Besides the `send` methods, the sender implements [`Sink`] and [`BodySink`], so a stream of
+/// frames can be forwarded into the body. The sink waits for capacity in the channel before
+/// accepting a frame. Closing the sink does not end the body; drop the sender to end it.
+///
+/// The sender can be cloned so several tasks can send frames to the same body. The body ends once
+/// all clones are dropped, and the first clone to [`abort`](Sender::abort) sets the error of the
+/// body.
 pub struct Sender<D, E = std::convert::Infallible> {
     tx_frame: mpsc::Sender<Frame<D>>,
-    tx_error: oneshot::Sender<E>,
+    /// Shared by all clones, the oneshot is dropped with the last one.
+    tx_error: Arc<Mutex<Option<oneshot::Sender<E>>>>,
+    /// Capacity reserved by `poll_ready` for the next frame.
+    permit: Option<mpsc::OwnedPermit<Frame<D>>>,
+    /// The pending reservation of `poll_ready`.
+    ///
+    /// The future is not `Sync`, so it is kept in a `Mutex` to keep the sender `Sync`. It is only
+    /// accessed through `&mut self`, so the lock is never contended.
+    reserve: Mutex<Option<ReserveFuture<D>>>,
 }
 
+type ReserveFuture<D> = Pin<
+    Box<
+        dyn Future<Output = Result<mpsc::OwnedPermit<Frame<D>>, mpsc::error::SendError<()>>> + Send,
+    >,
+>;
+
 impl<D, E> Sender<D, E> {
     /// Send a frame on the channel.
+    ///
+    /// If capacity was reserved with [`poll_ready`](Sender::poll_ready), this does not wait.
     pub async fn send(&mut self, frame: Frame<D>) -> Result<(), SendError> {
+        if let Some(permit) = self.permit.take() {
+            permit.send(frame);
+            return Ok(());
+        }
         self.tx_frame.send(frame).await.map_err(|_| SendError)
     }
 
@@ -94,9 +171,153 @@ impl<D, E> Sender<D, E> {
         self.send(Frame::trailers(trailers)).await
     }
 
+    /// Attempt to send a frame on the channel without waiting.
+    ///
+    /// This is meant for producers that cannot wait, such as synchronous callbacks. If the channel
+    /// is full or the body was dropped, the frame is returned in the error.
+    pub fn try_send(&mut self, frame: Frame<D>) -> Result<(), TrySendError<D>> {
+        if let Some(permit) = self.permit.take() {
+            permit.send(frame);
+            return Ok(());
+        }
+        self.tx_frame.try_send(frame).map_err(|err| match err {
+            mpsc::error::TrySendError::Full(frame) => TrySendError {
+                frame,
+                closed: false,
+            },
+            mpsc::error::TrySendError::Closed(frame) => TrySendError {
+                frame,
+                closed: true,
+            },
+        })
+    }
+
+    /// Wait for capacity in the channel, returning a [`Permit`] to send one frame.
+    ///
+    /// This allows waiting for capacity before producing an expensive frame, such as before
+    /// reading the next chunk from disk. Sending with the permit does not wait.
+    pub async fn reserve(&mut self) -> Result<Permit<'_, D>, SendError> {
+        // Release capacity reserved by `poll_ready`, the permit replaces it.
+        self.permit = None;
+        let permit = self.tx_frame.reserve().await.map_err(|_| SendError)?;
+        Ok(Permit { permit })
+    }
+
+    /// Returns whether the body was dropped.
+    ///
+    /// Once it is, sending fails, so producers can stop doing expensive work.
+    pub fn is_closed(&self) -> bool {
+        self.tx_frame.is_closed()
+    }
+
+    /// Wait until the body is dropped, for example because the client disconnected.
+    ///
+    /// This is useful to stop a long-lived producer that may not send for a while.
+    pub async fn closed(&self) {
+        self.tx_frame.closed().await
+    }
+
     /// Aborts the body in an abnormal fashion.
+    ///
+    /// If the sender was cloned, only the first abort sets the error of the body.
     pub fn abort(self, error: E) {
-        self.tx_error.send(error).ok();
+        if let Some(tx_error) = self.tx_error.lock().unwrap().take() {
+            tx_error.send(error).ok();
+        }
+    }
+}
+
+impl<D, E> Clone for Sender<D, E> {
+    /// Returns a new sender for the same body.
+    ///
+    /// Capacity reserved with `poll_ready` is not shared with the clone.
+    fn clone(&self) -> Self {
+        Self {
+            tx_frame: self.tx_frame.clone(),
+            tx_error: self.tx_error.clone(),
+            permit: None,
+            reserve: Mutex::new(None),
+        }
+    }
+}
+
+impl<D, E> Sender<D, E>
+where
+    D: Send + 'static,
+{
+    /// Poll for capacity in the channel.
+    ///
+    /// Once this returns `Poll::Ready(Ok(()))`, capacity for one frame is reserved and the next
+    /// [`send`](Sender::send) or [`try_send`](Sender::try_send) does not wait or fail because the
+    /// channel is full.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
+        if self.permit.is_some() {
+            return Poll::Ready(Ok(()));
+        }
+
+        let reserve = self.reserve.get_mut().unwrap();
+        let tx_frame = &self.tx_frame;
+        let fut = reserve.get_or_insert_with(|| Box::pin(tx_frame.clone().reserve_owned()));
+        let result = match fut.as_mut().poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        *reserve = None;
+
+        let permit = result.map_err(|_| SendError)?;
+        self.permit = Some(permit);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<D, E> Sink<Frame<D>> for Sender<D, E>
+where
+    D: Send + 'static,
+{
+    type Error = SendError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Sender::poll_ready(self.get_mut(), cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, frame: Frame<D>) -> Result<(), Self::Error> {
+        let permit = self.get_mut().permit.take().ok_or(SendError)?;
+        permit.send(frame);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        this.permit = None;
+        *this.reserve.get_mut().unwrap() = None;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<D, E> BodySink<D> for Sender<D, E>
+where
+    D: Send + 'static,
+{
+    type Error = SendError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Sink::poll_ready(self, cx)
+    }
+
+    fn send_frame(self: Pin<&mut Self>, frame: Frame<D>) -> Result<(), Self::Error> {
+        Sink::start_send(self, frame)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Sink::poll_flush(self, cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Sink::poll_close(self, cx)
     }
 }
 
@@ -109,6 +330,36 @@ impl<D, E: std::fmt::Debug> std::fmt::Debug for Sender<D, E> {
     }
 }
 
+/// Capacity for one frame reserved with [`Sender::reserve`].
+///
+/// Dropping the permit without sending releases the capacity.
+pub struct Permit<'a, D> {
+    permit: mpsc::Permit<'a, Frame<D>>,
+}
+
+impl<D> Permit<'_, D> {
+    /// Send a frame on the channel.
+    pub fn send(self, frame: Frame<D>) {
+        self.permit.send(frame);
+    }
+
+    /// Send data on data channel.
+    pub fn send_data(self, buf: D) {
+        self.send(Frame::data(buf));
+    }
+
+    /// Send trailers on trailers channel.
+    pub fn send_trailers(self, trailers: HeaderMap) {
+        self.send(Frame::trailers(trailers));
+    }
+}
+
+impl<D> std::fmt::Debug for Permit<'_, D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Permit").finish()
+    }
+}
+
 /// The error returned if [`Sender`] fails to send because the receiver is closed.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -122,9 +373,50 @@ impl Display for SendError {
 
 impl std::error::Error for SendError {}
 
+/// The error returned if [`Sender::try_send`] fails to send a frame.
+///
+/// The frame that could not be sent can be retrieved with [`into_frame`].
+///
+/// [`into_frame`]: TrySendError::into_frame
+#[derive(Debug)]
+pub struct TrySendError<D> {
+    frame: Frame<D>,
+    closed: bool,
+}
+
+impl<D> TrySendError<D> {
+    /// Returns whether the frame was not sent because the channel is full.
+    pub fn is_full(&self) -> bool {
+        !self.closed
+    }
+
+    /// Returns whether the frame was not sent because the receiver is closed.
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Consume the error, returning the frame that could not be sent.
+    pub fn into_frame(self) -> Frame<D> {
+        self.frame
+    }
+}
+
+impl<D> Display for TrySendError<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.closed {
+            write!(f, "failed to send frame: channel closed")
+        } else {
+            write!(f, "failed to send frame: channel full")
+        }
+    }
+}
+
+impl<D: std::fmt::Debug> std::error::Error for TrySendError<D> {}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
+    use futures_util::{stream, SinkExt};
     use http::{HeaderName, HeaderValue};
 
     use crate::BodyExt;
@@ -193,6 +485,143 @@ mod tests {
         assert_eq!(collected.to_bytes(), "Hello!");
     }
 
+    #[tokio::test]
+    async fn can_forward_stream() {
+        let (mut tx, body) = Channel::<Bytes>::new(1);
+
+        tokio::spawn(async move {
+            let frames = ["Hel", "lo", "!"].map(|chunk| Ok(Frame::data(Bytes::from(chunk))));
+            tx.send_all(&mut stream::iter(frames)).await.unwrap();
+        });
+
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.to_bytes(), "Hello!");
+    }
+
+    #[tokio::test]
+    async fn sink_fails_when_body_dropped() {
+        let (mut tx, body) = Channel::<Bytes>::new(1);
+        drop(body);
+
+        let err = tx.send(Frame::data(Bytes::from("hi"))).await;
+        assert!(err.is_err());
+        let err = SinkExt::send(&mut tx, Frame::data(Bytes::from("hi"))).await;
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn body_sink() {
+        let (mut tx, body) = Channel::<Bytes>::new(1);
+
+        tokio::spawn(async move {
+            for chunk in ["Hel", "lo", "!"] {
+                futures_util::future::poll_fn(|cx| BodySink::poll_ready(Pin::new(&mut tx), cx))
+                    .await
+                    .unwrap();
+                BodySink::send_frame(Pin::new(&mut tx), Frame::data(Bytes::from(chunk))).unwrap();
+            }
+        });
+
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.to_bytes(), "Hello!");
+    }
+
+    #[tokio::test]
+    async fn try_send_full_and_closed() {
+        let (mut tx, mut body) = Channel::<Bytes>::new(1);
+
+        tx.try_send(Frame::data(Bytes::from("Hel"))).unwrap();
+        let err = tx.try_send(Frame::data(Bytes::from("lo!"))).unwrap_err();
+        assert!(err.is_full());
+        let frame = err.into_frame();
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "Hel");
+        tx.try_send(frame).unwrap();
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "lo!");
+
+        drop(body);
+        let err = tx.try_send(Frame::data(Bytes::from("!"))).unwrap_err();
+        assert!(err.is_closed());
+    }
+
+    #[tokio::test]
+    async fn reserve_before_sending() {
+        let (mut tx, mut body) = Channel::<Bytes>::new(1);
+
+        let permit = tx.reserve().await.unwrap();
+        permit.send_data(Bytes::from("Hel"));
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "Hel");
+
+        drop(tx.reserve().await.unwrap());
+        tx.try_send(Frame::data(Bytes::from("lo!"))).unwrap();
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "lo!");
+    }
+
+    #[tokio::test]
+    async fn poll_ready_reserves_capacity() {
+        let (mut tx, mut body) = Channel::<Bytes>::new(1);
+        tx.try_send(Frame::data(Bytes::from("Hel"))).unwrap();
+
+        let mut cx = Context::from_waker(futures_util::task::noop_waker_ref());
+        assert!(tx.poll_ready(&mut cx).is_pending());
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "Hel");
+        assert!(matches!(tx.poll_ready(&mut cx), Poll::Ready(Ok(()))));
+        tx.try_send(Frame::data(Bytes::from("lo!"))).unwrap();
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "lo!");
+    }
+
+    #[tokio::test]
+    async fn closed_when_body_dropped() {
+        let (tx, body) = Channel::<Bytes>::new(1);
+        assert!(!tx.is_closed());
+
+        tokio::spawn(async move {
+            drop(body);
+        });
+        tx.closed().await;
+        assert!(tx.is_closed());
+    }
+
+    #[tokio::test]
+    async fn content_length_size_hint() {
+        let (mut tx, mut body) = Channel::<Bytes>::with_content_length(4, 6);
+        assert_eq!(body.size_hint().exact(), Some(6));
+
+        tx.send_data(Bytes::from("Hel")).await.unwrap();
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(body.size_hint().exact(), Some(3));
+
+        tx.send_data(Bytes::from("lo!")).await.unwrap();
+        drop(tx);
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "lo!");
+    }
+
+    #[tokio::test]
+    async fn cloned_senders() {
+        let (tx, body) = Channel::<Bytes>::new(4);
+
+        for chunk in ["a", "b", "c"] {
+            let mut tx = tx.clone();
+            tokio::spawn(async move {
+                tx.send_data(Bytes::from(chunk)).await.unwrap();
+            });
+        }
+        drop(tx);
+
+        let mut data = body.collect().await.unwrap().to_bytes().to_vec();
+        data.sort_unstable();
+        assert_eq!(data, b"abc");
+    }
+
     /// A stand-in for an error type, for unit tests.
     type Error = &'static str;
     /// An example error message.
@@ -212,6 +641,20 @@ mod tests {
         assert_eq!(err, MSG);
     }
 
+    #[tokio::test]
+    async fn clone_aborts() {
+        let (tx, body) = Channel::<Bytes, Error>::new(1024);
+        let tx2 = tx.clone();
+
+        tokio::spawn(async move {
+            tx2.abort(MSG);
+            tx.abort("ignored");
+        });
+
+        let err = body.collect().await.unwrap_err();
+        assert_eq!(err, MSG);
+    }
+
     #[tokio::test]
     async fn aborts_after_trailers() {
         let (mut tx, body) = Channel::<Bytes, Error>::new(1024);
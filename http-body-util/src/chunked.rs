@@ -0,0 +1,359 @@
+use std::{
+    error::Error,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use futures_core::ready;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+/// The longest chunk size line or trailer line that is accepted.
+const MAX_LINE_LEN: usize = 4096;
+/// The largest trailer section that is accepted.
+const MAX_TRAILERS_LEN: usize = 16 * 1024;
+
+pin_project! {
+    /// A body that decodes the HTTP/1.1 chunked transfer coding.
+    ///
+    /// The wrapped body yields the raw chunked bytes, such as captured HTTP/1 traffic. This body
+    /// yields the decoded data of each chunk, followed by a trailers frame if the message has a
+    /// trailer section. Chunk extensions are ignored.
+    ///
+    /// Malformed input, or input that ends before the last chunk, results in a
+    /// [`ChunkedDecodeError`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, ChunkedDecoder, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let raw = Full::new(Bytes::from("5\r\nhello\r\n0\r\nfoo: bar\r\n\r\n"));
+    /// let collected = ChunkedDecoder::new(raw).collect().await.unwrap();
+    ///
+    /// assert_eq!(collected.trailers().unwrap()["foo"], "bar");
+    /// assert_eq!(collected.to_bytes(), "hello");
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct ChunkedDecoder<B> {
+        #[pin]
+        inner: B,
+        decoder: Decoder,
+    }
+}
+
+impl<B> ChunkedDecoder<B> {
+    /// Create a new `ChunkedDecoder`.
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            decoder: Decoder {
+                buf: BytesMut::new(),
+                state: State::Size,
+                trailers: None,
+                trailers_len: 0,
+            },
+        }
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for ChunkedDecoder<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = Bytes;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+
+        loop {
+            if let Some(frame) = this.decoder.decode()? {
+                return Poll::Ready(Some(Ok(frame)));
+            }
+            if let State::Done = this.decoder.state {
+                return Poll::Ready(None);
+            }
+
+            match ready!(this.inner.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    if let Ok(mut data) = frame.into_data() {
+                        while data.has_remaining() {
+                            let chunk = data.chunk();
+                            this.decoder.buf.extend_from_slice(chunk);
+                            let len = chunk.len();
+                            data.advance(len);
+                        }
+                    }
+                }
+                Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+                None => {
+                    this.decoder.state = State::Done;
+                    let err = ChunkedDecodeError::new("unexpected end of chunked body");
+                    return Poll::Ready(Some(Err(err.into())));
+                }
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        matches!(self.decoder.state, State::Done) && self.decoder.trailers.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.is_end_stream() {
+            return SizeHint::with_exact(0);
+        }
+        SizeHint::default()
+    }
+}
+
+#[derive(Debug)]
+struct Decoder {
+    buf: BytesMut,
+    state: State,
+    trailers: Option<HeaderMap>,
+    trailers_len: usize,
+}
+
+#[derive(Debug)]
+enum State {
+    Size,
+    Data(u64),
+    DataEnd,
+    Trailers,
+    Done,
+}
+
+impl Decoder {
+    /// Decodes the next frame from the buffered input, if there is enough of it.
+    fn decode(&mut self) -> Result<Option<Frame<Bytes>>, ChunkedDecodeError> {
+        loop {
+            match self.state {
+                State::Size => {
+                    let line = match self.take_line()? {
+                        Some(line) => line,
+                        None => return Ok(None),
+                    };
+                    self.state = match parse_size(&line)? {
+                        0 => State::Trailers,
+                        size => State::Data(size),
+                    };
+                }
+                State::Data(remaining) => {
+                    if self.buf.is_empty() {
+                        return Ok(None);
+                    }
+                    let len = remaining.min(self.buf.len() as u64);
+                    let data = self.buf.split_to(len as usize).freeze();
+                    self.state = match remaining - len {
+                        0 => State::DataEnd,
+                        remaining => State::Data(remaining),
+                    };
+                    return Ok(Some(Frame::data(data)));
+                }
+                State::DataEnd => {
+                    if self.buf.len() < 2 {
+                        return Ok(None);
+                    }
+                    if &self.buf[..2] != b"\r\n" {
+                        return Err(ChunkedDecodeError::new("missing CRLF after chunk data"));
+                    }
+                    self.buf.advance(2);
+                    self.state = State::Size;
+                }
+                State::Trailers => {
+                    let line = match self.take_line()? {
+                        Some(line) => line,
+                        None => return Ok(None),
+                    };
+                    if line.is_empty() {
+                        self.state = State::Done;
+                        return Ok(self.trailers.take().map(Frame::trailers));
+                    }
+
+                    self.trailers_len += line.len();
+                    if self.trailers_len > MAX_TRAILERS_LEN {
+                        return Err(ChunkedDecodeError::new("trailer section too large"));
+                    }
+                    let (name, value) = parse_trailer(&line)?;
+                    self.trailers
+                        .get_or_insert_with(HeaderMap::new)
+                        .append(name, value);
+                }
+                State::Done => return Ok(None),
+            }
+        }
+    }
+
+    /// Takes a CRLF terminated line from the buffer, without the CRLF.
+    fn take_line(&mut self) -> Result<Option<BytesMut>, ChunkedDecodeError> {
+        match self.buf.windows(2).position(|window| window == b"\r\n") {
+            Some(pos) if pos <= MAX_LINE_LEN => {
+                let line = self.buf.split_to(pos);
+                self.buf.advance(2);
+                Ok(Some(line))
+            }
+            None if self.buf.len() <= MAX_LINE_LEN => Ok(None),
+            _ => Err(ChunkedDecodeError::new("line too long")),
+        }
+    }
+}
+
+fn parse_size(line: &[u8]) -> Result<u64, ChunkedDecodeError> {
+    let invalid = || ChunkedDecodeError::new("invalid chunk size");
+
+    let end = line.iter().position(|&b| b == b';').unwrap_or(line.len());
+    let digits = trim_end(&line[..end]);
+    if digits.is_empty() {
+        return Err(invalid());
+    }
+
+    digits.iter().try_fold(0u64, |size, &b| {
+        let digit = char::from(b).to_digit(16).ok_or_else(invalid)?;
+        size.checked_mul(16)
+            .and_then(|size| size.checked_add(u64::from(digit)))
+            .ok_or_else(invalid)
+    })
+}
+
+fn parse_trailer(line: &[u8]) -> Result<(HeaderName, HeaderValue), ChunkedDecodeError> {
+    let invalid = || ChunkedDecodeError::new("invalid trailer");
+
+    let colon = line.iter().position(|&b| b == b':').ok_or_else(invalid)?;
+    let name = HeaderName::from_bytes(&line[..colon]).map_err(|_| invalid())?;
+    let value = trim_start(trim_end(&line[colon + 1..]));
+    let value = HeaderValue::from_bytes(value).map_err(|_| invalid())?;
+    Ok((name, value))
+}
+
+fn trim_start(mut bytes: &[u8]) -> &[u8] {
+    while let [b' ' | b'\t', rest @ ..] = bytes {
+        bytes = rest;
+    }
+    bytes
+}
+
+fn trim_end(mut bytes: &[u8]) -> &[u8] {
+    while let [rest @ .., b' ' | b'\t'] = bytes {
+        bytes = rest;
+    }
+    bytes
+}
+
+/// An error returned when a [`ChunkedDecoder`] reads malformed input.
+#[derive(Debug)]
+pub struct ChunkedDecodeError {
+    reason: &'static str,
+}
+
+impl ChunkedDecodeError {
+    fn new(reason: &'static str) -> Self {
+        Self { reason }
+    }
+}
+
+impl fmt::Display for ChunkedDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid chunked encoding: {}", self.reason)
+    }
+}
+
+impl Error for ChunkedDecodeError {}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use futures_util::stream;
+
+    use super::*;
+    use crate::{BodyExt, Full, StreamBody};
+
+    const RAW: &[u8] = b"5;ext=1\r\nhello\r\n6 \r\n world\r\n0\r\nfoo: bar\r\nfoo:baz \r\n\r\n";
+
+    async fn decode(
+        raw: &'static [u8],
+        split: usize,
+    ) -> Result<crate::Collected<Bytes>, Box<dyn Error + Send + Sync>> {
+        let frames = raw
+            .chunks(split)
+            .map(|chunk| Ok::<_, Infallible>(Frame::data(Bytes::from_static(chunk))))
+            .collect::<Vec<_>>();
+        ChunkedDecoder::new(StreamBody::new(stream::iter(frames)))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn decodes_chunks_and_trailers() {
+        for split in [1, 2, 3, 7, RAW.len()] {
+            let collected = decode(RAW, split).await.unwrap();
+            let trailers = collected.trailers().unwrap();
+            let values = trailers.get_all("foo").iter().collect::<Vec<_>>();
+            assert_eq!(values, ["bar", "baz"]);
+            assert_eq!(collected.to_bytes(), "hello world");
+        }
+    }
+
+    #[tokio::test]
+    async fn no_trailers() {
+        let body = ChunkedDecoder::new(Full::new(Bytes::from_static(b"3\r\nabc\r\n0\r\n\r\n")));
+        let collected = body.collect().await.unwrap();
+        assert!(collected.trailers().is_none());
+        assert_eq!(collected.to_bytes(), "abc");
+    }
+
+    #[tokio::test]
+    async fn malformed_input() {
+        for raw in [
+            &b"x\r\nabc\r\n0\r\n\r\n"[..],
+            b"3\r\nabcd\r\n0\r\n\r\n",
+            b"ffffffffffffffffff\r\n",
+            b"0\r\nno colon\r\n\r\n",
+        ] {
+            let err = decode(raw, 4).await.unwrap_err();
+            assert!(err.downcast_ref::<ChunkedDecodeError>().is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn truncated_input() {
+        let err = decode(b"5\r\nhel", 4).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid chunked encoding: unexpected end of chunked body"
+        );
+    }
+}
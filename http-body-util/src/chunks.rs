@@ -0,0 +1,114 @@
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    iter::FromIterator,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use http_body::{Body, Frame, SizeHint};
+
+/// A body that consists of a list of in-memory chunks.
+///
+/// Each chunk is yielded as one data frame, and the size hint is exact. Empty chunks are skipped.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body::Body as _;
+/// use http_body_util::{BodyExt, Chunks};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let body = Chunks::new(vec![Bytes::from("hello "), Bytes::from("world")]);
+/// assert_eq!(body.size_hint().exact(), Some(11));
+/// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello world");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Chunks<D> {
+    chunks: VecDeque<D>,
+    remaining: u64,
+}
+
+impl<D: Buf> Chunks<D> {
+    /// Create a new `Chunks` from an iterator of chunks.
+    pub fn new<I>(chunks: I) -> Self
+    where
+        I: IntoIterator<Item = D>,
+    {
+        let chunks: VecDeque<D> = chunks
+            .into_iter()
+            .filter(|chunk| chunk.has_remaining())
+            .collect();
+        let remaining = chunks.iter().map(|chunk| chunk.remaining() as u64).sum();
+        Self { chunks, remaining }
+    }
+}
+
+impl<D: Buf> Body for Chunks<D> {
+    type Data = D;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let chunk = self.chunks.pop_front().map(|chunk| {
+            self.remaining -= chunk.remaining() as u64;
+            Ok(Frame::data(chunk))
+        });
+        Poll::Ready(chunk)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.remaining)
+    }
+}
+
+impl<D: Buf> Default for Chunks<D> {
+    /// Create an empty `Chunks`.
+    fn default() -> Self {
+        Self {
+            chunks: VecDeque::new(),
+            remaining: 0,
+        }
+    }
+}
+
+impl<D: Buf> FromIterator<D> for Chunks<D> {
+    fn from_iter<I: IntoIterator<Item = D>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl<D> Unpin for Chunks<D> {}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::BodyExt;
+
+    #[tokio::test]
+    async fn one_frame_per_chunk() {
+        let mut body: Chunks<Bytes> = vec!["hel", "", "lo"].into_iter().map(Bytes::from).collect();
+        assert_eq!(body.size_hint().exact(), Some(5));
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "hel");
+        assert_eq!(body.size_hint().exact(), Some(2));
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "lo");
+        assert!(body.is_end_stream());
+        assert!(body.frame().await.is_none());
+    }
+}
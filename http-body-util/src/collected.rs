@@ -8,7 +8,7 @@ use bytes::{Buf, Bytes};
 use http::HeaderMap;
 use http_body::{Body, Frame};
 
-use crate::util::BufList;
+use crate::BufList;
 
 /// A collected body produced by [`BodyExt::collect`] which collects all the DATA frames
 /// and trailers.
@@ -28,11 +28,49 @@ impl<B: Buf> Collected<B> {
         self.trailers.as_ref()
     }
 
+    /// Consume `self`, returning the trailers, if any.
+    pub fn into_trailers(self) -> Option<HeaderMap> {
+        self.trailers
+    }
+
     /// Aggregate this buffered into a [`Buf`].
-    pub fn aggregate(self) -> impl Buf {
+    pub fn aggregate(self) -> BufList<B> {
         self.bufs
     }
 
+    /// Convert this body into a `Vec<u8>`.
+    pub fn to_vec(mut self) -> Vec<u8> {
+        let mut vec = Vec::with_capacity(self.bufs.remaining());
+        while self.bufs.has_remaining() {
+            let chunk = self.bufs.chunk();
+            vec.extend_from_slice(chunk);
+            let len = chunk.len();
+            self.bufs.advance(len);
+        }
+        vec
+    }
+
+    /// Consume `self`, returning the data as [`Bytes`] and the trailers, if any.
+    pub fn into_parts(mut self) -> (Bytes, Option<HeaderMap>) {
+        let data = self.bufs.copy_to_bytes(self.bufs.remaining());
+        (data, self.trailers)
+    }
+
+    /// Returns an iterator over the buffered data segments, in the order they were received.
+    ///
+    /// Unlike [`to_bytes`](Collected::to_bytes), this does not copy the data, which allows vectored
+    /// writes after collection.
+    pub fn segments(&self) -> impl Iterator<Item = &B> + '_ {
+        self.bufs.iter()
+    }
+
+    /// Consume `self`, returning an iterator over the buffered data segments.
+    ///
+    /// This allows forwarding the data without copying it. The trailers are discarded.
+    pub fn into_segments(self) -> impl Iterator<Item = B> {
+        self.bufs.into_iter()
+    }
+
     /// Convert this body into a [`Bytes`].
     pub fn to_bytes(mut self) -> Bytes {
         self.bufs.copy_to_bytes(self.bufs.remaining())
@@ -41,11 +79,8 @@ impl<B: Buf> Collected<B> {
     pub(crate) fn push_frame(&mut self, frame: Frame<B>) {
         let frame = match frame.into_data() {
             Ok(data) => {
-                // Only push this frame if it has some data in it, to avoid crashing on
-                // `BufList::push`.
-                if data.has_remaining() {
-                    self.bufs.push(data);
-                }
+                // Empty frames are skipped by `BufList::push`.
+                self.bufs.push(data);
                 return;
             }
             Err(frame) => frame,
@@ -165,6 +200,61 @@ mod tests {
         assert_eq!(&buf.copy_to_bytes(buf.remaining())[..], b"helloworld!");
     }
 
+    #[tokio::test]
+    async fn segments() {
+        let bufs = [Bytes::from("hello"), Bytes::from("world"), Bytes::from("!")];
+        let body = StreamBody::new(stream::iter(
+            bufs.clone().map(Frame::data).map(Ok::<_, Infallible>),
+        ));
+
+        let buffered = body.collect().await.unwrap();
+        assert_eq!(
+            buffered.segments().collect::<Vec<_>>(),
+            bufs.iter().collect::<Vec<_>>()
+        );
+
+        let segments: Vec<_> = buffered.into_segments().collect();
+        assert_eq!(segments, bufs);
+        assert_eq!(segments[0].as_ptr(), bufs[0].as_ptr());
+    }
+
+    #[tokio::test]
+    async fn into_parts() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("this", "a trailer".try_into().unwrap());
+        let body = || {
+            let bufs = [
+                Frame::data(&b"hello"[..]),
+                Frame::data(&b"world!"[..]),
+                Frame::trailers(trailers.clone()),
+            ];
+            StreamBody::new(stream::iter(bufs.map(Ok::<_, Infallible>)))
+        };
+
+        let (data, parts_trailers) = body().collect().await.unwrap().into_parts();
+        assert_eq!(data, "helloworld!");
+        assert_eq!(parts_trailers.unwrap(), trailers);
+
+        assert_eq!(body().collect().await.unwrap().to_vec(), b"helloworld!");
+        assert_eq!(
+            body().collect().await.unwrap().into_trailers().unwrap(),
+            trailers
+        );
+    }
+
+    #[tokio::test]
+    async fn aggregate_chunks_vectored() {
+        let bufs = [&b"hello"[..], &b"world"[..], &b"!"[..]];
+        let body = StreamBody::new(stream::iter(bufs.map(Frame::data).map(Ok::<_, Infallible>)));
+
+        let buf = body.collect().await.unwrap().aggregate();
+        let mut dst = [std::io::IoSlice::new(&[]); 8];
+        assert_eq!(buf.chunks_vectored(&mut dst), 3);
+        assert_eq!(&*dst[0], b"hello");
+        assert_eq!(&*dst[1], b"world");
+        assert_eq!(&*dst[2], b"!");
+    }
+
     /// Test for issue [#88](https://github.com/hyperium/http-body/issues/88).
     #[tokio::test]
     async fn empty_frame() {
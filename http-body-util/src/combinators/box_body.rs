@@ -1,8 +1,9 @@
 use crate::BodyExt as _;
 
-use bytes::Buf;
+use bytes::{Buf, Bytes};
 use http_body::{Body, Frame, SizeHint};
 use std::{
+    any::Any,
     fmt,
     pin::Pin,
     task::{Context, Poll},
@@ -10,12 +11,111 @@ use std::{
 
 /// A boxed [`Body`] trait object.
 pub struct BoxBody<D, E> {
-    inner: Pin<Box<dyn Body<Data = D, Error = E> + Send + Sync + 'static>>,
+    inner: Pin<Box<dyn AnyBody<Data = D, Error = E> + Send + Sync + 'static>>,
 }
 
 /// A boxed [`Body`] trait object that is !Sync.
 pub struct UnsyncBoxBody<D, E> {
-    inner: Pin<Box<dyn Body<Data = D, Error = E> + Send + 'static>>,
+    inner: Pin<Box<dyn AnyBody<Data = D, Error = E> + Send + 'static>>,
+}
+
+/// A boxed [`Body`] trait object with its data converted to [`Bytes`].
+///
+/// Created with [`BodyExt::boxed_bytes`], this allows bodies with different data types to be
+/// returned as the same type.
+///
+/// [`BodyExt::boxed_bytes`]: crate::BodyExt::boxed_bytes
+pub type BytesBoxBody<E> = BoxBody<Bytes, E>;
+
+/// A boxed [`Body`] trait object that is neither `Send` nor `Sync`.
+///
+/// This is for single-threaded runtimes, such as a tokio `LocalSet` or wasm, where bodies may hold
+/// `Rc` state.
+pub struct LocalBoxBody<D, E> {
+    inner: Pin<Box<dyn AnyBody<Data = D, Error = E> + 'static>>,
+}
+
+/// A boxed [`Body`] trait object that implements [`Clone`].
+///
+/// This allows retry and mirroring layers to duplicate type-erased bodies. Cloning clones the
+/// boxed body in its current state.
+pub struct BoxCloneBody<D, E> {
+    inner: Pin<Box<dyn CloneBody<Data = D, Error = E> + Send + Sync + 'static>>,
+}
+
+/// A body that can be downcast to its concrete type.
+trait AnyBody: Body {
+    fn as_any(&self) -> &dyn Any;
+
+    /// Moves `self` into `slot`, which must be a `&mut Option<Pin<Box<Self>>>`.
+    fn take_into(self: Pin<Box<Self>>, slot: &mut dyn Any);
+}
+
+impl<B: Body + 'static> AnyBody for B {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn take_into(self: Pin<Box<Self>>, slot: &mut dyn Any) {
+        if let Some(slot) = slot.downcast_mut::<Option<Pin<Box<Self>>>>() {
+            *slot = Some(self);
+        }
+    }
+}
+
+/// A body that can be cloned into a new box.
+trait CloneBody: Body {
+    fn clone_box(
+        &self,
+    ) -> Pin<Box<dyn CloneBody<Data = Self::Data, Error = Self::Error> + Send + Sync + 'static>>;
+}
+
+impl<B> CloneBody for B
+where
+    B: Body + Clone + Send + Sync + 'static,
+{
+    fn clone_box(
+        &self,
+    ) -> Pin<Box<dyn CloneBody<Data = Self::Data, Error = Self::Error> + Send + Sync + 'static>>
+    {
+        Box::pin(self.clone())
+    }
+}
+
+/// Implements `downcast` and `downcast_ref` for the boxed bodies.
+macro_rules! downcast_methods {
+    () => {
+        /// Attempt to downcast the boxed body to a concrete type, returning it.
+        ///
+        /// This allows recovering a known body, for example to use a specialized fast path for
+        /// it. Returns `self` if the body is not a `B`. Only bodies that are [`Unpin`] can be taken
+        /// out of the box.
+        pub fn downcast<B>(self) -> Result<B, Self>
+        where
+            B: Body + Unpin + 'static,
+            D: Buf + 'static,
+            E: 'static,
+        {
+            // Deref explicitly, the `Pin<Box<_>>` is itself a body implementing `AnyBody`.
+            if !(*self.inner).as_any().is::<B>() {
+                return Err(self);
+            }
+
+            let mut slot: Option<Pin<Box<B>>> = None;
+            self.inner.take_into(&mut slot);
+            Ok(*Pin::into_inner(slot.expect("body type checked")))
+        }
+
+        /// Returns a reference to the boxed body if it is a `B`.
+        pub fn downcast_ref<B>(&self) -> Option<&B>
+        where
+            B: Body + 'static,
+            D: Buf + 'static,
+            E: 'static,
+        {
+            (*self.inner).as_any().downcast_ref()
+        }
+    };
 }
 
 impl<D, E> BoxBody<D, E> {
@@ -29,6 +129,8 @@ impl<D, E> BoxBody<D, E> {
             inner: Box::pin(body),
         }
     }
+
+    downcast_methods!();
 }
 
 impl<D, E> fmt::Debug for BoxBody<D, E> {
@@ -69,6 +171,123 @@ where
     }
 }
 
+// === LocalBoxBody ===
+impl<D, E> LocalBoxBody<D, E> {
+    /// Create a new `LocalBoxBody`.
+    pub fn new<B>(body: B) -> Self
+    where
+        B: Body<Data = D, Error = E> + 'static,
+        D: Buf,
+    {
+        Self {
+            inner: Box::pin(body),
+        }
+    }
+
+    downcast_methods!();
+}
+
+impl<D, E> fmt::Debug for LocalBoxBody<D, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalBoxBody").finish()
+    }
+}
+
+impl<D, E> Body for LocalBoxBody<D, E>
+where
+    D: Buf,
+{
+    type Data = D;
+    type Error = E;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.inner.as_mut().poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<D, E> Default for LocalBoxBody<D, E>
+where
+    D: Buf + 'static,
+{
+    fn default() -> Self {
+        LocalBoxBody::new(crate::Empty::new().map_err(|err| match err {}))
+    }
+}
+
+// === BoxCloneBody ===
+impl<D, E> BoxCloneBody<D, E> {
+    /// Create a new `BoxCloneBody`.
+    pub fn new<B>(body: B) -> Self
+    where
+        B: Body<Data = D, Error = E> + Clone + Send + Sync + 'static,
+        D: Buf,
+    {
+        Self {
+            inner: Box::pin(body),
+        }
+    }
+}
+
+impl<D, E> Clone for BoxCloneBody<D, E>
+where
+    D: Buf,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: (*self.inner).clone_box(),
+        }
+    }
+}
+
+impl<D, E> fmt::Debug for BoxCloneBody<D, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxCloneBody").finish()
+    }
+}
+
+impl<D, E> Body for BoxCloneBody<D, E>
+where
+    D: Buf,
+{
+    type Data = D;
+    type Error = E;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.inner.as_mut().poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<D, E> Default for BoxCloneBody<D, E>
+where
+    D: Buf + 'static,
+{
+    fn default() -> Self {
+        BoxCloneBody::new(crate::Empty::new().map_err(|err| match err {}))
+    }
+}
+
 // === UnsyncBoxBody ===
 impl<D, E> UnsyncBoxBody<D, E> {
     /// Create a new `UnsyncBoxBody`.
@@ -81,6 +300,8 @@ impl<D, E> UnsyncBoxBody<D, E> {
             inner: Box::pin(body),
         }
     }
+
+    downcast_methods!();
 }
 
 impl<D, E> fmt::Debug for UnsyncBoxBody<D, E> {
@@ -120,3 +341,64 @@ where
         UnsyncBoxBody::new(crate::Empty::new().map_err(|err| match err {}))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::*;
+    use crate::{BodyExt, Empty, Full};
+
+    #[test]
+    fn downcast() {
+        let body = BoxBody::new(Full::new(Bytes::from("hello")));
+        assert!(body.downcast_ref::<Empty<Bytes>>().is_none());
+        assert!(body.downcast_ref::<Full<Bytes>>().is_some());
+
+        let body = body.downcast::<Empty<Bytes>>().unwrap_err();
+        let full: Full<Bytes> = body.downcast().unwrap();
+        assert_eq!(full.size_hint().exact(), Some(5));
+    }
+
+    #[tokio::test]
+    async fn box_clone_body() {
+        let body = BoxCloneBody::new(Full::new(Bytes::from("hello")));
+        let retry = body.clone();
+
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+        assert_eq!(retry.collect().await.unwrap().to_bytes(), "hello");
+    }
+
+    #[tokio::test]
+    async fn local_box_body() {
+        let data = std::rc::Rc::new(Bytes::from("hello"));
+        let body = Full::new((*data).clone())
+            .map_frame(move |frame| {
+                let _ = &data;
+                frame
+            })
+            .boxed_local();
+
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    }
+
+    #[tokio::test]
+    async fn boxed_bytes() {
+        let bodies: Vec<BytesBoxBody<Infallible>> = vec![
+            Full::new(&b"hello"[..]).boxed_bytes(),
+            Full::new(Bytes::from(" world")).boxed_bytes(),
+        ];
+
+        let mut data = Vec::new();
+        for body in bodies {
+            data.extend_from_slice(&body.collect().await.unwrap().to_bytes());
+        }
+        assert_eq!(data, b"hello world");
+    }
+
+    #[test]
+    fn downcast_unsync() {
+        let body = UnsyncBoxBody::<Bytes, Infallible>::new(Empty::new());
+        assert!(body.downcast::<Empty<Bytes>>().is_ok());
+    }
+}
@@ -0,0 +1,283 @@
+use std::{
+    fmt,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+#[cfg(feature = "time")]
+use crate::timer::TokioTimer;
+use crate::timer::{Sleep, Timer};
+
+pin_project! {
+    /// Body returned by the `coalesce` combinator.
+    ///
+    /// The delay is measured by a [`Timer`], given to [`Coalesce::with_timer`]. With the `time`
+    /// feature, `BodyExt::coalesce` uses a `TokioTimer`.
+    pub struct Coalesce<B: Body> {
+        buf: BytesMut,
+        threshold: usize,
+        max_delay: Duration,
+        timer: Arc<dyn Timer + Send + Sync>,
+        sleep: Option<Pin<Box<dyn Sleep>>>,
+        armed: bool,
+        // A frame or error received while data was buffered, yielded after the buffer.
+        pending: Option<Result<Frame<Bytes>, B::Error>>,
+        done: bool,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B: Body> Coalesce<B> {
+    #[cfg(feature = "time")]
+    pub(crate) fn new(inner: B, threshold: usize, max_delay: Duration) -> Self {
+        Self::with_timer(inner, threshold, max_delay, TokioTimer)
+    }
+
+    /// Create a new `Coalesce` merging data frames smaller than `threshold` bytes, flushing
+    /// `max_delay` after the first buffered frame as measured by `timer`.
+    ///
+    /// See `BodyExt::coalesce` for more details.
+    pub fn with_timer<T>(inner: B, threshold: usize, max_delay: Duration, timer: T) -> Self
+    where
+        T: Timer + Send + Sync + 'static,
+    {
+        Self {
+            buf: BytesMut::new(),
+            threshold,
+            max_delay,
+            timer: Arc::new(timer),
+            sleep: None,
+            armed: false,
+            pending: None,
+            done: false,
+            inner,
+        }
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    ///
+    /// Any buffered data is lost.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: Body> Body for Coalesce<B> {
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+
+        if let Some(next) = this.pending.take() {
+            return Poll::Ready(Some(next));
+        }
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let next = loop {
+            match this.inner.as_mut().poll_frame(cx) {
+                Poll::Ready(Some(Ok(frame))) => match frame.into_data() {
+                    Ok(mut data) => {
+                        let len = data.remaining();
+                        if len >= *this.threshold {
+                            break Ok(Frame::data(data.copy_to_bytes(len)));
+                        }
+
+                        this.buf.put(data);
+                        if this.buf.len() >= *this.threshold {
+                            trace!(bytes = this.buf.len(), "coalesce: threshold reached");
+                            return Poll::Ready(Some(Ok(flush(this.buf, this.armed))));
+                        }
+                        if !*this.armed {
+                            let deadline = this.timer.now() + *this.max_delay;
+                            match this.sleep {
+                                Some(sleep) => this.timer.reset(sleep, deadline),
+                                None => *this.sleep = Some(this.timer.sleep_until(deadline)),
+                            }
+                            *this.armed = true;
+                        }
+                    }
+                    Err(frame) => {
+                        break Ok(frame.map_data(|mut data| data.copy_to_bytes(data.remaining())))
+                    }
+                },
+                Poll::Ready(Some(Err(err))) => break Err(err),
+                Poll::Ready(None) => {
+                    *this.done = true;
+                    if this.buf.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    return Poll::Ready(Some(Ok(flush(this.buf, this.armed))));
+                }
+                Poll::Pending => {
+                    if !*this.armed {
+                        return Poll::Pending;
+                    }
+                    let sleep = this.sleep.as_mut().expect("armed without a timer");
+                    if sleep.as_mut().poll(cx).is_pending() {
+                        return Poll::Pending;
+                    }
+                    trace!(bytes = this.buf.len(), "coalesce: delay elapsed");
+                    return Poll::Ready(Some(Ok(flush(this.buf, this.armed))));
+                }
+            }
+        };
+
+        if this.buf.is_empty() {
+            return Poll::Ready(Some(next));
+        }
+        *this.pending = Some(next);
+        Poll::Ready(Some(Ok(flush(this.buf, this.armed))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.pending.is_none() && self.buf.is_empty() && (self.done || self.inner.is_end_stream())
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let pending = match &self.pending {
+            Some(Ok(frame)) => frame.data_ref().map_or(0, Bytes::len),
+            _ => 0,
+        };
+        let buffered = SizeHint::with_exact((self.buf.len() + pending) as u64);
+        if self.done {
+            return buffered;
+        }
+        self.inner.size_hint().saturating_add(&buffered)
+    }
+}
+
+impl<B> fmt::Debug for Coalesce<B>
+where
+    B: Body + fmt::Debug,
+    B::Error: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Coalesce")
+            .field("buf", &self.buf)
+            .field("threshold", &self.threshold)
+            .field("max_delay", &self.max_delay)
+            .field("armed", &self.armed)
+            .field("pending", &self.pending)
+            .field("done", &self.done)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+fn flush(buf: &mut BytesMut, armed: &mut bool) -> Frame<Bytes> {
+    *armed = false;
+    Frame::data(buf.split().freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::Infallible, time::Duration};
+
+    use bytes::Bytes;
+    #[cfg(feature = "time")]
+    use http::HeaderMap;
+    use http_body::Frame;
+
+    use super::Coalesce;
+    use crate::test_support::ManualTimer;
+    use crate::{BodyExt, StreamBody};
+
+    fn data(chunk: &'static str) -> Result<Frame<Bytes>, Infallible> {
+        Ok(Frame::data(Bytes::from(chunk)))
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test]
+    async fn merges_small_frames() {
+        let frames = vec![
+            data("a"),
+            data("b"),
+            data("c"),
+            data("large"),
+            data("d"),
+            Ok(Frame::trailers(HeaderMap::new())),
+        ];
+        let body =
+            StreamBody::new(futures_util::stream::iter(frames)).coalesce(4, Duration::from_secs(1));
+
+        let frames = body.collect_frames().await.unwrap();
+        let data: Vec<_> = frames.iter().filter_map(Frame::data_ref).collect();
+        assert_eq!(data, ["abc", "large", "d"]);
+        assert!(frames.last().unwrap().is_trailers());
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test]
+    async fn flushes_at_threshold() {
+        let frames = vec![data("ab"), data("cd"), data("e")];
+        let body =
+            StreamBody::new(futures_util::stream::iter(frames)).coalesce(4, Duration::from_secs(1));
+
+        let frames = body.collect_frames().await.unwrap();
+        let data: Vec<_> = frames.iter().filter_map(Frame::data_ref).collect();
+        assert_eq!(data, ["abcd", "e"]);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn flushes_after_delay() {
+        use futures_util::StreamExt;
+
+        let frames =
+            futures_util::stream::iter(vec![data("hello")]).chain(futures_util::stream::pending());
+        let mut body = StreamBody::new(frames).coalesce(1024, Duration::from_millis(10));
+
+        let start = tokio::time::Instant::now();
+        let frame = body.frame().await.unwrap().unwrap();
+        assert_eq!(frame.into_data().unwrap(), "hello");
+        assert_eq!(start.elapsed(), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn custom_timer() {
+        use futures_util::{FutureExt, StreamExt};
+
+        let timer = ManualTimer::new();
+        let frames =
+            futures_util::stream::iter(vec![data("hello")]).chain(futures_util::stream::pending());
+        let mut body = Coalesce::with_timer(
+            StreamBody::new(frames),
+            1024,
+            Duration::from_millis(10),
+            timer.clone(),
+        );
+
+        assert!(body.frame().now_or_never().is_none());
+        timer.advance(Duration::from_millis(10));
+        let frame = body.frame().now_or_never().unwrap().unwrap().unwrap();
+        assert_eq!(frame.into_data().unwrap(), "hello");
+    }
+}
@@ -32,12 +32,23 @@ impl<T: Body + ?Sized> Future for Collect<T> {
         loop {
             let frame = ready!(me.body.as_mut().poll_frame(cx));
 
-            let frame = if let Some(frame) = frame {
-                frame?
-            } else {
-                return Poll::Ready(Ok(me.collected.take().expect("polled after complete")));
+            let frame = match frame {
+                Some(Ok(frame)) => frame,
+                Some(Err(err)) => {
+                    debug!("collect: body error");
+                    return Poll::Ready(Err(err));
+                }
+                None => {
+                    trace!("collect: end of stream");
+                    return Poll::Ready(Ok(me.collected.take().expect("polled after complete")));
+                }
             };
 
+            trace!(
+                bytes = frame.data_ref().map_or(0, bytes::Buf::remaining),
+                trailers = frame.is_trailers(),
+                "collect: frame"
+            );
             me.collected.as_mut().unwrap().push_frame(frame);
         }
     }
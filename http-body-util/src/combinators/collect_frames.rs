@@ -0,0 +1,81 @@
+use std::{
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::ready;
+use http_body::{Body, Frame};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future returned by [`BodyExt::collect_frames`].
+    ///
+    /// [`BodyExt::collect_frames`]: crate::BodyExt::collect_frames
+    #[must_use = "futures don't do anything unless polled"]
+    pub struct CollectFrames<B>
+    where
+        B: Body,
+        B: ?Sized,
+    {
+        frames: Vec<Frame<B::Data>>,
+        #[pin]
+        body: B,
+    }
+}
+
+impl<B: Body> CollectFrames<B> {
+    pub(crate) fn new(body: B) -> Self {
+        Self {
+            frames: Vec::new(),
+            body,
+        }
+    }
+}
+
+impl<B: Body + ?Sized> Future for CollectFrames<B> {
+    type Output = Result<Vec<Frame<B::Data>>, B::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            match ready!(this.body.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => this.frames.push(frame),
+                Some(Err(err)) => return Poll::Ready(Err(err)),
+                None => return Poll::Ready(Ok(mem::take(this.frames))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use futures_util::stream;
+    use http::HeaderMap;
+
+    use crate::{BodyExt, StreamBody};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn keeps_frame_boundaries() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("foo", "bar".parse().unwrap());
+        let body = StreamBody::new(stream::iter(vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hel"))),
+            Ok(Frame::data(Bytes::from("lo"))),
+            Ok(Frame::trailers(trailers)),
+        ]));
+
+        let frames = body.collect_frames().await.unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].data_ref().unwrap(), "hel");
+        assert_eq!(frames[1].data_ref().unwrap(), "lo");
+        assert_eq!(frames[2].trailers_ref().unwrap()["foo"], "bar");
+    }
+}
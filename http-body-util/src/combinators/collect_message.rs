@@ -0,0 +1,94 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_core::ready;
+use http::{request, response, Request, Response};
+use http_body::Body;
+use pin_project_lite::pin_project;
+
+use super::{Collect, CollectLimitError, CollectWithLimit};
+use crate::Collected;
+
+pin_project! {
+    /// Future returned by [`RequestExt::into_collected`] and [`ResponseExt::into_collected`].
+    ///
+    /// [`RequestExt::into_collected`]: crate::RequestExt::into_collected
+    /// [`ResponseExt::into_collected`]: crate::ResponseExt::into_collected
+    #[must_use = "futures don't do anything unless polled"]
+    pub struct IntoCollected<P, B>
+    where
+        B: Body,
+    {
+        parts: Option<P>,
+        #[pin]
+        collect: Collect<B>,
+    }
+}
+
+impl<P, B: Body> IntoCollected<P, B> {
+    pub(crate) fn new(parts: P, collect: Collect<B>) -> Self {
+        Self {
+            parts: Some(parts),
+            collect,
+        }
+    }
+}
+
+pin_project! {
+    /// Future returned by [`RequestExt::into_bytes_with_limit`] and
+    /// [`ResponseExt::into_bytes_with_limit`].
+    ///
+    /// [`RequestExt::into_bytes_with_limit`]: crate::RequestExt::into_bytes_with_limit
+    /// [`ResponseExt::into_bytes_with_limit`]: crate::ResponseExt::into_bytes_with_limit
+    #[must_use = "futures don't do anything unless polled"]
+    pub struct IntoBytesWithLimit<P, B>
+    where
+        B: Body,
+    {
+        parts: Option<P>,
+        #[pin]
+        collect: CollectWithLimit<B>,
+    }
+}
+
+impl<P, B: Body> IntoBytesWithLimit<P, B> {
+    pub(crate) fn new(parts: P, collect: CollectWithLimit<B>) -> Self {
+        Self {
+            parts: Some(parts),
+            collect,
+        }
+    }
+}
+
+macro_rules! message_futures {
+    ($parts:ty, $message:ident) => {
+        impl<B: Body> Future for IntoCollected<$parts, B> {
+            type Output = Result<$message<Collected<B::Data>>, B::Error>;
+
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = self.project();
+                let collected = ready!(this.collect.poll(cx))?;
+                let parts = this.parts.take().expect("polled after complete");
+                Poll::Ready(Ok($message::from_parts(parts, collected)))
+            }
+        }
+
+        impl<B: Body> Future for IntoBytesWithLimit<$parts, B> {
+            type Output = Result<$message<Bytes>, CollectLimitError<B::Data, B::Error>>;
+
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = self.project();
+                let collected = ready!(this.collect.poll(cx))?;
+                let parts = this.parts.take().expect("polled after complete");
+                Poll::Ready(Ok($message::from_parts(parts, collected.to_bytes())))
+            }
+        }
+    };
+}
+
+message_futures!(request::Parts, Request);
+message_futures!(response::Parts, Response);
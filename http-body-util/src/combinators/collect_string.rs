@@ -0,0 +1,217 @@
+use std::{
+    error::Error,
+    fmt,
+    future::Future,
+    pin::Pin,
+    string::FromUtf8Error,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http_body::Body;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future returned by [`BodyExt::collect_string`].
+    ///
+    /// [`BodyExt::collect_string`]: crate::BodyExt::collect_string
+    #[must_use = "futures don't do anything unless polled"]
+    pub struct CollectString<B>
+    where
+        B: ?Sized,
+    {
+        buf: Vec<u8>,
+        limit: Option<usize>,
+        #[pin]
+        body: B,
+    }
+}
+
+impl<B> CollectString<B> {
+    pub(crate) fn new(body: B) -> Self {
+        Self {
+            buf: Vec::new(),
+            limit: None,
+            body,
+        }
+    }
+
+    /// Fail with a length limit error if the body contains more than `limit` bytes.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl<B: Body + ?Sized> Future for CollectString<B> {
+    type Output = Result<String, CollectStringError<B::Error>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            let frame = match ready!(this.body.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => frame,
+                Some(Err(err)) => {
+                    return Poll::Ready(Err(CollectStringError::new(Kind::Body(err))))
+                }
+                None => {
+                    let buf = std::mem::take(this.buf);
+                    let res = String::from_utf8(buf)
+                        .map_err(|err| CollectStringError::new(Kind::Utf8(err)));
+                    return Poll::Ready(res);
+                }
+            };
+
+            let mut data = match frame.into_data() {
+                Ok(data) => data,
+                Err(_) => continue,
+            };
+
+            if let Some(limit) = *this.limit {
+                if this.buf.len().saturating_add(data.remaining()) > limit {
+                    debug!(limit, "collect_string: length limit exceeded");
+                    return Poll::Ready(Err(CollectStringError::new(Kind::LengthLimit)));
+                }
+            }
+
+            this.buf.reserve(data.remaining());
+            while data.has_remaining() {
+                let chunk = data.chunk();
+                this.buf.extend_from_slice(chunk);
+                let len = chunk.len();
+                data.advance(len);
+            }
+        }
+    }
+}
+
+/// The error returned by [`BodyExt::collect_string`].
+///
+/// [`BodyExt::collect_string`]: crate::BodyExt::collect_string
+#[derive(Debug)]
+pub struct CollectStringError<E> {
+    kind: Kind<E>,
+}
+
+#[derive(Debug)]
+enum Kind<E> {
+    Body(E),
+    Utf8(FromUtf8Error),
+    LengthLimit,
+}
+
+impl<E> CollectStringError<E> {
+    fn new(kind: Kind<E>) -> Self {
+        Self { kind }
+    }
+
+    /// Returns whether the body returned an error.
+    pub fn is_body(&self) -> bool {
+        matches!(self.kind, Kind::Body(_))
+    }
+
+    /// Returns whether the body is not valid UTF-8.
+    pub fn is_utf8(&self) -> bool {
+        matches!(self.kind, Kind::Utf8(_))
+    }
+
+    /// Returns whether the body contained more data than the limit.
+    pub fn is_length_limit(&self) -> bool {
+        matches!(self.kind, Kind::LengthLimit)
+    }
+
+    /// Consume the error, returning the error of the body, if it returned one.
+    pub fn into_body_error(self) -> Option<E> {
+        match self.kind {
+            Kind::Body(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Consume the error, returning the collected bytes if the body is not valid UTF-8.
+    pub fn into_bytes(self) -> Option<Vec<u8>> {
+        match self.kind {
+            Kind::Utf8(err) => Some(err.into_bytes()),
+            _ => None,
+        }
+    }
+}
+
+impl<E> fmt::Display for CollectStringError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Kind::Body(_) => f.write_str("error reading body"),
+            Kind::Utf8(_) => f.write_str("body is not valid UTF-8"),
+            Kind::LengthLimit => f.write_str("length limit exceeded"),
+        }
+    }
+}
+
+impl<E> Error for CollectStringError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            Kind::Body(err) => Some(err),
+            Kind::Utf8(err) => Some(err),
+            Kind::LengthLimit => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::Infallible, io};
+
+    use bytes::Bytes;
+    use http::HeaderMap;
+    use http_body::Frame;
+
+    use crate::{BodyExt, Full, StreamBody};
+
+    #[tokio::test]
+    async fn collects_text() {
+        let frames = futures_util::stream::iter(vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from_static(&[0xc3]))),
+            Ok(Frame::data(Bytes::from_static(&[0xa9, b'!']))),
+            Ok(Frame::trailers(HeaderMap::new())),
+        ]);
+        let text = StreamBody::new(frames).collect_string().await.unwrap();
+        assert_eq!(text, "é!");
+    }
+
+    #[tokio::test]
+    async fn invalid_utf8() {
+        let body = Full::new(Bytes::from_static(&[b'a', 0xff]));
+        let err = body.collect_string().await.unwrap_err();
+        assert!(err.is_utf8());
+        assert_eq!(err.to_string(), "body is not valid UTF-8");
+        assert_eq!(err.into_bytes().unwrap(), [b'a', 0xff]);
+    }
+
+    #[tokio::test]
+    async fn limit() {
+        let body = Full::new(Bytes::from("hello"));
+        assert_eq!(body.collect_string().limit(5).await.unwrap(), "hello");
+
+        let body = Full::new(Bytes::from("hello"));
+        let err = body.collect_string().limit(4).await.unwrap_err();
+        assert!(err.is_length_limit());
+    }
+
+    #[tokio::test]
+    async fn body_error() {
+        let frames = futures_util::stream::iter(vec![Err::<Frame<Bytes>, _>(io::Error::new(
+            io::ErrorKind::ConnectionReset,
+            "reset",
+        ))]);
+        let err = StreamBody::new(frames).collect_string().await.unwrap_err();
+        assert!(err.is_body());
+        assert!(!err.is_utf8());
+        let err = err.into_body_error().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+    }
+}
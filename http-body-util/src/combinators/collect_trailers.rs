@@ -0,0 +1,222 @@
+use std::{
+    error::Error,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http::HeaderMap;
+use http_body::Body;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future returned by [`BodyExt::collect_trailers`].
+    ///
+    /// [`BodyExt::collect_trailers`]: crate::BodyExt::collect_trailers
+    #[must_use = "futures don't do anything unless polled"]
+    pub struct CollectTrailers<B>
+    where
+        B: ?Sized,
+    {
+        trailers: Option<HeaderMap>,
+        #[pin]
+        body: B,
+    }
+}
+
+impl<B> CollectTrailers<B> {
+    pub(crate) fn new(body: B) -> Self {
+        Self {
+            trailers: None,
+            body,
+        }
+    }
+
+    /// Fail with a [`CollectTrailersError`] if the body has any data left, instead of discarding
+    /// it.
+    ///
+    /// This is useful for responses such as gRPC trailers-only responses, where data means the
+    /// peer misbehaved.
+    pub fn deny_data(self) -> CollectTrailersStrict<B> {
+        CollectTrailersStrict {
+            trailers: self.trailers,
+            body: self.body,
+        }
+    }
+}
+
+impl<B: Body + ?Sized> Future for CollectTrailers<B> {
+    type Output = Result<Option<HeaderMap>, B::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            let frame = match ready!(this.body.as_mut().poll_frame(cx)) {
+                Some(frame) => frame?,
+                None => return Poll::Ready(Ok(this.trailers.take())),
+            };
+
+            if let Ok(trailers) = frame.into_trailers() {
+                match this.trailers {
+                    Some(current) => current.extend(trailers),
+                    None => *this.trailers = Some(trailers),
+                }
+            }
+        }
+    }
+}
+
+pin_project! {
+    /// Future returned by [`CollectTrailers::deny_data`].
+    #[must_use = "futures don't do anything unless polled"]
+    pub struct CollectTrailersStrict<B> {
+        trailers: Option<HeaderMap>,
+        #[pin]
+        body: B,
+    }
+}
+
+impl<B: Body> Future for CollectTrailersStrict<B> {
+    type Output = Result<Option<HeaderMap>, CollectTrailersError<B::Error>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            let frame = match ready!(this.body.as_mut().poll_frame(cx)) {
+                Some(frame) => frame.map_err(CollectTrailersError::body)?,
+                None => return Poll::Ready(Ok(this.trailers.take())),
+            };
+
+            match frame.into_data() {
+                Ok(data) if data.has_remaining() => {
+                    return Poll::Ready(Err(CollectTrailersError::data()));
+                }
+                Ok(_) => {}
+                Err(frame) => {
+                    if let Ok(trailers) = frame.into_trailers() {
+                        match this.trailers {
+                            Some(current) => current.extend(trailers),
+                            None => *this.trailers = Some(trailers),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The error returned by [`CollectTrailers::deny_data`].
+#[derive(Debug)]
+pub struct CollectTrailersError<E> {
+    kind: Kind<E>,
+}
+
+#[derive(Debug)]
+enum Kind<E> {
+    Body(E),
+    Data,
+}
+
+impl<E> CollectTrailersError<E> {
+    fn body(err: E) -> Self {
+        Self {
+            kind: Kind::Body(err),
+        }
+    }
+
+    fn data() -> Self {
+        Self { kind: Kind::Data }
+    }
+
+    /// Returns whether the body returned an error.
+    pub fn is_body(&self) -> bool {
+        matches!(self.kind, Kind::Body(_))
+    }
+
+    /// Returns whether the body had data left.
+    pub fn is_data(&self) -> bool {
+        matches!(self.kind, Kind::Data)
+    }
+
+    /// Consume the error, returning the error of the body, if it returned one.
+    pub fn into_body_error(self) -> Option<E> {
+        match self.kind {
+            Kind::Body(err) => Some(err),
+            Kind::Data => None,
+        }
+    }
+}
+
+impl<E> fmt::Display for CollectTrailersError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Kind::Body(_) => f.write_str("error reading body"),
+            Kind::Data => f.write_str("body has data left"),
+        }
+    }
+}
+
+impl<E> Error for CollectTrailersError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            Kind::Body(err) => Some(err),
+            Kind::Data => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use http::HeaderMap;
+    use http_body::Frame;
+
+    use crate::{BodyExt, Full, StreamBody};
+
+    #[tokio::test]
+    async fn discards_data() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-checksum", "abc".parse().unwrap());
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hello"))),
+            Ok(Frame::trailers(trailers.clone())),
+        ];
+        let body = StreamBody::new(futures_util::stream::iter(frames));
+
+        assert_eq!(body.collect_trailers().await.unwrap(), Some(trailers));
+    }
+
+    #[tokio::test]
+    async fn no_trailers() {
+        let body = Full::new(Bytes::from("hello"));
+        assert_eq!(body.collect_trailers().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn deny_data() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", "0".parse().unwrap());
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::new())),
+            Ok(Frame::trailers(trailers.clone())),
+        ];
+        let body = StreamBody::new(futures_util::stream::iter(frames));
+        let collected = body.collect_trailers().deny_data().await.unwrap();
+        assert_eq!(collected, Some(trailers));
+
+        let body = Full::new(Bytes::from("hello"));
+        let err = body.collect_trailers().deny_data().await.unwrap_err();
+        assert!(err.is_data());
+        assert!(!err.is_body());
+    }
+}
@@ -0,0 +1,185 @@
+use std::{
+    error::Error,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http_body::Body;
+use pin_project_lite::pin_project;
+
+use crate::Collected;
+
+pin_project! {
+    /// Future returned by [`BodyExt::collect_with_limit`].
+    ///
+    /// [`BodyExt::collect_with_limit`]: crate::BodyExt::collect_with_limit
+    #[must_use = "futures don't do anything unless polled"]
+    pub struct CollectWithLimit<B>
+    where
+        B: Body,
+        B: ?Sized,
+    {
+        collected: Option<Collected<B::Data>>,
+        received: usize,
+        limit: usize,
+        #[pin]
+        body: B,
+    }
+}
+
+impl<B: Body> CollectWithLimit<B> {
+    pub(crate) fn new(body: B, limit: usize) -> Self {
+        Self {
+            collected: Some(Collected::default()),
+            received: 0,
+            limit,
+            body,
+        }
+    }
+}
+
+impl<B: Body + ?Sized> Future for CollectWithLimit<B> {
+    type Output = Result<Collected<B::Data>, CollectLimitError<B::Data, B::Error>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            let frame = match ready!(this.body.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => frame,
+                Some(Err(err)) => {
+                    let collected = this.collected.take().expect("polled after complete");
+                    return Poll::Ready(Err(CollectLimitError {
+                        collected,
+                        error: Some(err),
+                    }));
+                }
+                None => {
+                    let collected = this.collected.take().expect("polled after complete");
+                    return Poll::Ready(Ok(collected));
+                }
+            };
+
+            *this.received += frame.data_ref().map_or(0, Buf::remaining);
+            let collected = this.collected.as_mut().expect("polled after complete");
+            collected.push_frame(frame);
+
+            if *this.received > *this.limit {
+                debug!(limit = *this.limit, "collect: length limit exceeded");
+                return Poll::Ready(Err(CollectLimitError {
+                    collected: this.collected.take().unwrap(),
+                    error: None,
+                }));
+            }
+        }
+    }
+}
+
+/// The error returned by [`BodyExt::collect_with_limit`].
+///
+/// It contains the frames collected before the error, so the partial data is not lost.
+///
+/// [`BodyExt::collect_with_limit`]: crate::BodyExt::collect_with_limit
+#[derive(Debug)]
+pub struct CollectLimitError<D, E> {
+    collected: Collected<D>,
+    /// The body error, or `None` if the length limit was exceeded.
+    error: Option<E>,
+}
+
+impl<D, E> CollectLimitError<D, E> {
+    /// Returns whether the body contained more data than the limit.
+    pub fn is_length_limit(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns whether the body returned an error.
+    pub fn is_body(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// Returns the data and trailers collected before the error.
+    ///
+    /// If the limit was exceeded, this includes the frame that exceeded it.
+    pub fn collected(&self) -> &Collected<D> {
+        &self.collected
+    }
+
+    /// Consume the error, returning the data and trailers collected before it.
+    pub fn into_collected(self) -> Collected<D> {
+        self.collected
+    }
+
+    /// Consume the error, returning the error of the body, if it returned one.
+    pub fn into_body_error(self) -> Option<E> {
+        self.error
+    }
+}
+
+impl<D, E> fmt::Display for CollectLimitError<D, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error {
+            Some(_) => f.write_str("error reading body"),
+            None => f.write_str("length limit exceeded"),
+        }
+    }
+}
+
+impl<D, E> Error for CollectLimitError<D, E>
+where
+    D: fmt::Debug,
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.as_ref().map(|err| err as &(dyn Error + 'static))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use bytes::Bytes;
+    use futures_util::stream;
+    use http_body::Frame;
+
+    use crate::{BodyExt, Full, StreamBody};
+
+    #[tokio::test]
+    async fn under_limit() {
+        let body = Full::new(Bytes::from("hello"));
+        let collected = body.collect_with_limit(5).await.unwrap();
+        assert_eq!(collected.to_bytes(), "hello");
+    }
+
+    #[tokio::test]
+    async fn over_limit_keeps_partial_data() {
+        let body = StreamBody::new(stream::iter(vec![
+            Ok::<_, io::Error>(Frame::data(Bytes::from("hello "))),
+            Ok(Frame::data(Bytes::from("world"))),
+            Ok(Frame::data(Bytes::from("!"))),
+        ]));
+
+        let err = body.collect_with_limit(8).await.unwrap_err();
+        assert!(err.is_length_limit());
+        assert_eq!(err.to_string(), "length limit exceeded");
+        assert_eq!(err.into_collected().to_bytes(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn body_error_keeps_partial_data() {
+        let body = StreamBody::new(stream::iter(vec![
+            Ok(Frame::data(Bytes::from("hello"))),
+            Err(io::Error::new(io::ErrorKind::Other, "reset")),
+        ]));
+
+        let err = body.collect_with_limit(64).await.unwrap_err();
+        assert!(err.is_body());
+        assert_eq!(err.collected().segments().count(), 1);
+        assert_eq!(err.into_body_error().unwrap().to_string(), "reset");
+    }
+}
@@ -0,0 +1,275 @@
+use std::{
+    error::Error,
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http_body::Body;
+use pin_project_lite::pin_project;
+use serde::de::DeserializeOwned;
+
+use crate::Collected;
+
+pin_project! {
+    /// Future that collects a body and deserializes it.
+    ///
+    /// See [`BodyExt::deserialize`] for more details.
+    ///
+    /// [`BodyExt::deserialize`]: crate::BodyExt::deserialize
+    #[must_use = "futures don't do anything unless polled"]
+    pub struct Deserialize<B, T>
+    where
+        B: Body,
+        B: ?Sized,
+    {
+        collected: Option<Collected<B::Data>>,
+        received: usize,
+        limit: Option<usize>,
+        format: Format,
+        _marker: PhantomData<fn() -> T>,
+        #[pin]
+        body: B,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Format {
+    Json,
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl<B: Body, T> Deserialize<B, T> {
+    pub(crate) fn new(body: B) -> Self {
+        Self {
+            collected: Some(Collected::default()),
+            received: 0,
+            limit: None,
+            format: Format::Json,
+            _marker: PhantomData,
+            body,
+        }
+    }
+}
+
+impl<B: Body + ?Sized, T> Deserialize<B, T> {
+    /// Fail with an error if the body contains more than `limit` bytes of data.
+    pub fn limit(mut self, limit: usize) -> Self
+    where
+        B: Sized,
+    {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Deserialize the body as CBOR instead of JSON.
+    #[cfg(feature = "cbor")]
+    pub fn cbor(mut self) -> Self
+    where
+        B: Sized,
+    {
+        self.format = Format::Cbor;
+        self
+    }
+}
+
+impl<B, T> Future for Deserialize<B, T>
+where
+    B: Body + ?Sized,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+    T: DeserializeOwned,
+{
+    type Output = Result<T, DeserializeError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut me = self.project();
+
+        loop {
+            let frame = match ready!(me.body.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => frame,
+                Some(Err(err)) => return Poll::Ready(Err(DeserializeError::body(err.into()))),
+                None => break,
+            };
+
+            if let Some(data) = frame.data_ref() {
+                *me.received += data.remaining();
+                if me.limit.map_or(false, |limit| *me.received > limit) {
+                    return Poll::Ready(Err(DeserializeError {
+                        kind: Kind::LengthLimit,
+                    }));
+                }
+            }
+            me.collected.as_mut().unwrap().push_frame(frame);
+        }
+
+        let collected = me.collected.take().expect("polled after complete");
+        let result = match me.format {
+            Format::Json => {
+                serde_json::from_slice(&collected.to_bytes()).map_err(|err| DeserializeError {
+                    kind: Kind::Json(err),
+                })
+            }
+            #[cfg(feature = "cbor")]
+            Format::Cbor => {
+                ciborium::de::from_reader(collected.aggregate().reader()).map_err(|err| {
+                    DeserializeError {
+                        kind: Kind::Cbor(err),
+                    }
+                })
+            }
+        };
+        Poll::Ready(result)
+    }
+}
+
+impl<B, T> fmt::Debug for Deserialize<B, T>
+where
+    B: Body + fmt::Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Deserialize")
+            .field("received", &self.received)
+            .field("limit", &self.limit)
+            .field("format", &self.format)
+            .field("body", &&self.body)
+            .finish()
+    }
+}
+
+/// An error returned by [`Deserialize`].
+pub struct DeserializeError {
+    kind: Kind,
+}
+
+enum Kind {
+    Body(Box<dyn Error + Send + Sync>),
+    LengthLimit,
+    Json(serde_json::Error),
+    #[cfg(feature = "cbor")]
+    Cbor(ciborium::de::Error<std::io::Error>),
+}
+
+impl DeserializeError {
+    fn body(err: Box<dyn Error + Send + Sync>) -> Self {
+        Self {
+            kind: Kind::Body(err),
+        }
+    }
+
+    /// Returns whether the body itself returned an error.
+    pub fn is_body(&self) -> bool {
+        matches!(self.kind, Kind::Body(_))
+    }
+
+    /// Returns whether the body exceeded the configured limit.
+    pub fn is_length_limit(&self) -> bool {
+        matches!(self.kind, Kind::LengthLimit)
+    }
+
+    /// Returns whether the collected data could not be deserialized.
+    pub fn is_data(&self) -> bool {
+        !self.is_body() && !self.is_length_limit()
+    }
+}
+
+impl fmt::Debug for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_tuple("DeserializeError");
+        match &self.kind {
+            Kind::Body(err) => f.field(err),
+            Kind::LengthLimit => f.field(&"LengthLimit"),
+            Kind::Json(err) => f.field(err),
+            #[cfg(feature = "cbor")]
+            Kind::Cbor(err) => f.field(err),
+        };
+        f.finish()
+    }
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            Kind::Body(_) => f.write_str("error reading body"),
+            Kind::LengthLimit => f.write_str("length limit exceeded"),
+            Kind::Json(_) => f.write_str("invalid JSON body"),
+            #[cfg(feature = "cbor")]
+            Kind::Cbor(_) => f.write_str("invalid CBOR body"),
+        }
+    }
+}
+
+impl Error for DeserializeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            Kind::Body(err) => Some(&**err),
+            Kind::LengthLimit => None,
+            Kind::Json(err) => Some(err),
+            #[cfg(feature = "cbor")]
+            Kind::Cbor(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use futures_util::stream;
+    use http_body::Frame;
+    use serde::Deserialize;
+
+    use crate::{BodyExt, Full, StreamBody};
+
+    #[derive(Debug, Deserialize, PartialEq, serde::Serialize)]
+    struct User {
+        id: u32,
+        name: String,
+    }
+
+    fn user() -> User {
+        User {
+            id: 1,
+            name: "ferris".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn json_across_frames() {
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from(r#"{"id":1,"#))),
+            Ok(Frame::data(Bytes::from(r#""name":"ferris"}"#))),
+        ];
+        let body = StreamBody::new(stream::iter(frames));
+        assert_eq!(body.deserialize::<User>().await.unwrap(), user());
+    }
+
+    #[tokio::test]
+    async fn limit() {
+        let body = Full::new(Bytes::from(r#"{"id":1,"name":"ferris"}"#));
+        let err = body.deserialize::<User>().limit(8).await.unwrap_err();
+        assert!(err.is_length_limit());
+    }
+
+    #[tokio::test]
+    async fn invalid_data() {
+        let body = Full::new(Bytes::from(r#"{"id":1}"#));
+        let err = body.deserialize::<User>().await.unwrap_err();
+        assert!(err.is_data());
+    }
+
+    #[cfg(feature = "cbor")]
+    #[tokio::test]
+    async fn cbor() {
+        let mut buf = Vec::new();
+        ciborium::ser::into_writer(&user(), &mut buf).unwrap();
+
+        let body = Full::new(Bytes::from(buf));
+        assert_eq!(body.deserialize::<User>().cbor().await.unwrap(), user());
+    }
+}
@@ -0,0 +1,518 @@
+use std::{
+    any::type_name,
+    error::Error,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Body returned by the [`digest`] combinator.
+    ///
+    /// [`digest`]: crate::BodyExt::digest
+    pub struct Digest<B, H> {
+        #[pin]
+        inner: B,
+        hasher: Option<H>,
+        name: HeaderName,
+        encoding: DigestEncoding,
+    }
+}
+
+impl<B, H> Digest<B, H> {
+    #[inline]
+    pub(crate) fn new(body: B, hasher: H, name: HeaderName) -> Self {
+        Self {
+            inner: body,
+            hasher: Some(hasher),
+            name,
+            encoding: DigestEncoding::Hex,
+        }
+    }
+
+    /// Set how the digest is encoded in the trailer.
+    ///
+    /// Defaults to [`DigestEncoding::Hex`].
+    pub fn encoding(mut self, encoding: DigestEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B, H> Body for Digest<B, H>
+where
+    B: Body,
+    B::Data: Clone,
+    H: digest::Digest,
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+
+        let hasher = match this.hasher.as_mut() {
+            Some(hasher) => hasher,
+            None => return Poll::Ready(None),
+        };
+
+        if !this.inner.is_end_stream() {
+            match ready!(this.inner.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_trailers() {
+                    Ok(mut trailers) => {
+                        let hasher = this.hasher.take().unwrap();
+                        trailers.insert(this.name.clone(), finish(hasher, *this.encoding));
+                        return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+                    }
+                    Err(frame) => {
+                        if let Some(data) = frame.data_ref() {
+                            update(hasher, data);
+                        }
+                        return Poll::Ready(Some(Ok(frame)));
+                    }
+                },
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => {}
+            }
+        }
+
+        let hasher = this.hasher.take().unwrap();
+        let mut trailers = HeaderMap::new();
+        trailers.insert(this.name.clone(), finish(hasher, *this.encoding));
+        Poll::Ready(Some(Ok(Frame::trailers(trailers))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.hasher.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.hasher.is_none() {
+            return SizeHint::with_exact(0);
+        }
+        self.inner.size_hint()
+    }
+}
+
+/// Encodes the final digest as a header value.
+fn finish<H: digest::Digest>(hasher: H, encoding: DigestEncoding) -> HeaderValue {
+    let encoded = encode(&hasher.finalize(), encoding);
+    HeaderValue::from_str(&encoded).expect("hex and base64 are valid header values")
+}
+
+fn update<H: digest::Digest, D: Buf + Clone>(hasher: &mut H, data: &D) {
+    let mut data = data.clone();
+    while data.has_remaining() {
+        let chunk = data.chunk();
+        hasher.update(chunk);
+        let len = chunk.len();
+        data.advance(len);
+    }
+}
+
+fn encode(digest: &[u8], encoding: DigestEncoding) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::new();
+    match encoding {
+        DigestEncoding::Hex => {
+            for byte in digest {
+                out.push(char::from(HEX[usize::from(byte >> 4)]));
+                out.push(char::from(HEX[usize::from(byte & 0xf)]));
+            }
+        }
+        DigestEncoding::Base64 => {
+            for chunk in digest.chunks(3) {
+                let n = chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+                for i in 0..4 {
+                    if i <= chunk.len() {
+                        out.push(char::from(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize]));
+                    } else {
+                        out.push('=');
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+impl<B, H> fmt::Debug for Digest<B, H>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Digest")
+            .field("inner", &self.inner)
+            .field("hasher", &type_name::<H>())
+            .field("name", &self.name)
+            .field("encoding", &self.encoding)
+            .finish()
+    }
+}
+
+pin_project! {
+    /// Body returned by the [`verify_digest`] combinator.
+    ///
+    /// [`verify_digest`]: crate::BodyExt::verify_digest
+    pub struct VerifyDigest<B, H> {
+        #[pin]
+        inner: B,
+        hasher: Option<H>,
+        expected: ExpectedDigest,
+    }
+}
+
+impl<B, H> VerifyDigest<B, H> {
+    #[inline]
+    pub(crate) fn new(body: B, hasher: H, expected: ExpectedDigest) -> Self {
+        Self {
+            inner: body,
+            hasher: Some(hasher),
+            expected,
+        }
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B, H> Body for VerifyDigest<B, H>
+where
+    B: Body,
+    B::Data: Clone,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+    H: digest::Digest,
+{
+    type Data = B::Data;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+
+        let hasher = match this.hasher.as_mut() {
+            Some(hasher) => hasher,
+            None => return Poll::Ready(None),
+        };
+
+        if !this.inner.is_end_stream() {
+            match ready!(this.inner.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    if let Some(data) = frame.data_ref() {
+                        update(hasher, data);
+                        return Poll::Ready(Some(Ok(frame)));
+                    }
+                    let result = match frame.trailers_ref() {
+                        Some(trailers) => this
+                            .expected
+                            .verify(this.hasher.take().unwrap(), Some(trailers)),
+                        None => Ok(()),
+                    };
+                    return Poll::Ready(Some(result.map(|()| frame).map_err(Into::into)));
+                }
+                Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+                None => {}
+            }
+        }
+
+        let hasher = this.hasher.take().unwrap();
+        match this.expected.verify(hasher, None) {
+            Ok(()) => Poll::Ready(None),
+            Err(err) => Poll::Ready(Some(Err(err.into()))),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.hasher.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.hasher.is_none() {
+            return SizeHint::with_exact(0);
+        }
+        self.inner.size_hint()
+    }
+}
+
+impl<B, H> fmt::Debug for VerifyDigest<B, H>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VerifyDigest")
+            .field("inner", &self.inner)
+            .field("hasher", &type_name::<H>())
+            .field("expected", &self.expected)
+            .finish()
+    }
+}
+
+/// How a digest is encoded in a header value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DigestEncoding {
+    /// Lowercase hexadecimal, the default for [`BodyExt::digest`](crate::BodyExt::digest).
+    Hex,
+    /// Standard base64 with padding, as used by `Content-MD5`.
+    Base64,
+}
+
+/// The digest a [`VerifyDigest`] body is checked against.
+#[derive(Clone, Debug)]
+pub struct ExpectedDigest {
+    source: Source,
+    encoding: DigestEncoding,
+}
+
+#[derive(Clone, Debug)]
+enum Source {
+    Value(HeaderValue),
+    Trailer(HeaderName),
+}
+
+impl ExpectedDigest {
+    /// Expect the digest from the `Content-MD5` header in `headers`, if present.
+    pub fn content_md5(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get("content-md5")
+            .map(|value| Self::value(value.clone(), DigestEncoding::Base64))
+    }
+
+    /// Expect a digest that is already known, such as from a response header.
+    pub fn value(value: HeaderValue, encoding: DigestEncoding) -> Self {
+        Self {
+            source: Source::Value(value),
+            encoding,
+        }
+    }
+
+    /// Expect the digest in the trailer named `name`.
+    ///
+    /// Verification fails if the body ends without this trailer.
+    pub fn trailer(name: HeaderName, encoding: DigestEncoding) -> Self {
+        Self {
+            source: Source::Trailer(name),
+            encoding,
+        }
+    }
+
+    fn verify<H: digest::Digest>(
+        &self,
+        hasher: H,
+        trailers: Option<&HeaderMap>,
+    ) -> Result<(), DigestError> {
+        let expected = match &self.source {
+            Source::Value(value) => value,
+            Source::Trailer(name) => trailers
+                .and_then(|trailers| trailers.get(name))
+                .ok_or(DigestError { missing: true })?,
+        };
+        let actual = encode(&hasher.finalize(), self.encoding);
+        let expected = expected.as_bytes();
+        let matches = match self.encoding {
+            DigestEncoding::Hex => actual.as_bytes().eq_ignore_ascii_case(expected),
+            DigestEncoding::Base64 => actual.as_bytes() == expected,
+        };
+        if matches {
+            Ok(())
+        } else {
+            Err(DigestError { missing: false })
+        }
+    }
+}
+
+/// An error returned by [`VerifyDigest`] when the body does not match the expected digest.
+#[derive(Debug)]
+pub struct DigestError {
+    missing: bool,
+}
+
+impl DigestError {
+    /// Returns whether the expected digest trailer was missing.
+    pub fn is_missing(&self) -> bool {
+        self.missing
+    }
+}
+
+impl fmt::Display for DigestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.missing {
+            f.write_str("digest trailer missing")
+        } else {
+            f.write_str("digest mismatch")
+        }
+    }
+}
+
+impl Error for DigestError {}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use futures_util::stream;
+    use sha2::{Digest as _, Sha256};
+
+    use super::*;
+    use crate::{BodyExt, Full, StreamBody};
+
+    const HELLO_WORLD: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+    #[tokio::test]
+    async fn appends_trailer() {
+        let name = HeaderName::from_static("x-checksum");
+        let body = Full::new(Bytes::from("hello world")).digest(Sha256::new(), name);
+        assert_eq!(body.size_hint().exact(), Some(11));
+
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.trailers().unwrap()["x-checksum"], HELLO_WORLD);
+        assert_eq!(collected.to_bytes(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn merges_with_existing_trailers() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("foo", HeaderValue::from_static("bar"));
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hello "))),
+            Ok(Frame::data(Bytes::from("world"))),
+            Ok(Frame::trailers(trailers)),
+        ];
+        let name = HeaderName::from_static("x-checksum");
+        let mut body = StreamBody::new(stream::iter(frames)).digest(Sha256::new(), name);
+
+        let mut collected = Vec::new();
+        while let Some(frame) = body.frame().await {
+            collected.push(frame.unwrap());
+        }
+        assert!(body.is_end_stream());
+
+        let trailers = collected.pop().unwrap().into_trailers().unwrap();
+        assert_eq!(trailers["foo"], "bar");
+        assert_eq!(trailers["x-checksum"], HELLO_WORLD);
+        assert_eq!(collected.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn appends_base64_trailer() {
+        let name = HeaderName::from_static("content-md5");
+        let body = Full::new(Bytes::from("hello world"))
+            .digest(md5::Md5::new(), name)
+            .encoding(DigestEncoding::Base64);
+
+        let collected = body.collect().await.unwrap();
+        assert_eq!(
+            collected.trailers().unwrap()["content-md5"],
+            "XrY7u+Ae7tCTyyK7j1rNww=="
+        );
+    }
+
+    #[test]
+    fn encodes_base64() {
+        assert_eq!(encode(b"", DigestEncoding::Base64), "");
+        assert_eq!(encode(b"f", DigestEncoding::Base64), "Zg==");
+        assert_eq!(encode(b"fo", DigestEncoding::Base64), "Zm8=");
+        assert_eq!(encode(b"foo", DigestEncoding::Base64), "Zm9v");
+        assert_eq!(encode(b"foob", DigestEncoding::Base64), "Zm9vYg==");
+    }
+
+    #[tokio::test]
+    async fn verifies_content_md5() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "content-md5",
+            HeaderValue::from_static("XrY7u+Ae7tCTyyK7j1rNww=="),
+        );
+        let expected = ExpectedDigest::content_md5(&headers).unwrap();
+
+        let body = Full::new(Bytes::from("hello world")).verify_digest(md5::Md5::new(), expected);
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.to_bytes(), "hello world");
+
+        let expected = ExpectedDigest::content_md5(&headers).unwrap();
+        let body = Full::new(Bytes::from("hello")).verify_digest(md5::Md5::new(), expected);
+        let err = body.collect().await.unwrap_err();
+        let err = err.downcast_ref::<DigestError>().unwrap();
+        assert!(!err.is_missing());
+    }
+
+    #[tokio::test]
+    async fn verifies_trailer() {
+        let name = HeaderName::from_static("x-checksum");
+        let expected = ExpectedDigest::trailer(name.clone(), DigestEncoding::Hex);
+        let body = Full::new(Bytes::from("hello world"))
+            .digest(Sha256::new(), name.clone())
+            .verify_digest(Sha256::new(), expected);
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.trailers().unwrap()["x-checksum"], HELLO_WORLD);
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert(name.clone(), HeaderValue::from_static("00"));
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hello world"))),
+            Ok(Frame::trailers(trailers)),
+        ];
+        let expected = ExpectedDigest::trailer(name.clone(), DigestEncoding::Hex);
+        let body = StreamBody::new(stream::iter(frames)).verify_digest(Sha256::new(), expected);
+        let err = body.collect().await.unwrap_err();
+        assert!(!err.downcast_ref::<DigestError>().unwrap().is_missing());
+
+        let expected = ExpectedDigest::trailer(name, DigestEncoding::Hex);
+        let body = Full::new(Bytes::from("hello world")).verify_digest(Sha256::new(), expected);
+        let err = body.collect().await.unwrap_err();
+        assert!(err.downcast_ref::<DigestError>().unwrap().is_missing());
+    }
+}
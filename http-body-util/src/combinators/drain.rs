@@ -0,0 +1,83 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http_body::Body;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future returned by [`BodyExt::drain`].
+    ///
+    /// [`BodyExt::drain`]: crate::BodyExt::drain
+    #[must_use = "futures don't do anything unless polled"]
+    pub struct Drain<B>
+    where
+        B: ?Sized,
+    {
+        drained: u64,
+        #[pin]
+        body: B,
+    }
+}
+
+impl<B> Drain<B> {
+    pub(crate) fn new(body: B) -> Self {
+        Self { drained: 0, body }
+    }
+}
+
+impl<B: Body + ?Sized> Future for Drain<B> {
+    type Output = Result<u64, B::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            let frame = match ready!(this.body.as_mut().poll_frame(cx)) {
+                Some(frame) => frame?,
+                None => return Poll::Ready(Ok(*this.drained)),
+            };
+
+            if let Some(data) = frame.data_ref() {
+                *this.drained = this.drained.saturating_add(data.remaining() as u64);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use http::HeaderMap;
+    use http_body::Frame;
+
+    use crate::{BodyExt, StreamBody};
+
+    #[tokio::test]
+    async fn counts_discarded_bytes() {
+        let frames = futures_util::stream::iter(vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hello"))),
+            Ok(Frame::data(Bytes::from(" world"))),
+            Ok(Frame::trailers(HeaderMap::new())),
+        ]);
+        let drained = StreamBody::new(frames).drain().await.unwrap();
+        assert_eq!(drained, 11);
+    }
+
+    #[tokio::test]
+    async fn stops_at_error() {
+        let frames = futures_util::stream::iter(vec![
+            Ok(Frame::data(Bytes::from("hello"))),
+            Err("boom"),
+            Ok(Frame::data(Bytes::from("unreachable"))),
+        ]);
+        let err = StreamBody::new(frames).drain().await.unwrap_err();
+        assert_eq!(err, "boom");
+    }
+}
@@ -0,0 +1,152 @@
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pin_project! {
+    /// Body returned by the [`fuse`] combinator.
+    ///
+    /// [`fuse`]: crate::BodyExt::fuse
+    #[derive(Clone, Copy, Debug)]
+    pub struct Fuse<B> {
+        done: bool,
+        fuse_on_error: bool,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> Fuse<B> {
+    #[inline]
+    pub(crate) fn new(body: B) -> Self {
+        Self {
+            done: false,
+            fuse_on_error: false,
+            inner: body,
+        }
+    }
+
+    /// Also end the body after the wrapped body yields an error.
+    ///
+    /// By default only the end of the wrapped body fuses it, and polling after an error polls the
+    /// wrapped body again.
+    pub fn fuse_on_error(mut self, enabled: bool) -> Self {
+        self.fuse_on_error = enabled;
+        self
+    }
+
+    /// Returns `true` if the wrapped body has ended, and will not be polled again.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: Body> Body for Fuse<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let frame = futures_core::ready!(this.inner.poll_frame(cx));
+        match &frame {
+            None => *this.done = true,
+            Some(Err(_)) if *this.fuse_on_error => *this.done = true,
+            _ => {}
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.done || self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.done {
+            return SizeHint::with_exact(0);
+        }
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use bytes::Bytes;
+    use http_body::{Body, Frame};
+
+    use crate::BodyExt;
+
+    /// A body that yields an error, and panics if polled again after ending.
+    #[derive(Default)]
+    struct Misbehaving {
+        polls: usize,
+    }
+
+    impl Body for Misbehaving {
+        type Data = Bytes;
+        type Error = &'static str;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+            self.polls += 1;
+            match self.polls {
+                1 => Poll::Ready(Some(Err("oops"))),
+                2 => Poll::Ready(None),
+                _ => panic!("polled after end"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn fuses_after_end() {
+        let mut body = Misbehaving::default().fuse();
+        assert!(body.frame().await.unwrap().is_err());
+        assert!(body.frame().await.is_none());
+        assert!(body.is_done());
+        assert!(body.is_end_stream());
+        assert!(body.frame().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn fuse_on_error() {
+        let mut body = Misbehaving::default().fuse().fuse_on_error(true);
+        assert!(body.frame().await.unwrap().is_err());
+        assert!(body.frame().await.is_none());
+        assert_eq!(body.get_ref().polls, 1);
+    }
+}
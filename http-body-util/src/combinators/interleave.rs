@@ -0,0 +1,259 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::ready;
+use http::HeaderMap;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Body returned by the [`interleave`] combinator.
+    ///
+    /// [`interleave`]: crate::BodyExt::interleave
+    #[derive(Debug)]
+    pub struct Interleave<A, B> {
+        #[pin]
+        first: A,
+        #[pin]
+        second: B,
+        first_done: bool,
+        second_done: bool,
+        second_next: bool,
+        on_end: InterleaveEnd,
+        trailers: Option<HeaderMap>,
+        done: bool,
+    }
+}
+
+impl<A, B> Interleave<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            first_done: false,
+            second_done: false,
+            second_next: false,
+            on_end: InterleaveEnd::Continue,
+            trailers: None,
+            done: false,
+        }
+    }
+
+    /// Set what happens when one of the bodies ends before the other.
+    pub fn on_end(mut self, on_end: InterleaveEnd) -> Self {
+        self.on_end = on_end;
+        self
+    }
+
+    /// Get a reference to the inner bodies
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Get a mutable reference to the inner bodies
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Get a pinned mutable reference to the inner bodies
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> (Pin<&mut A>, Pin<&mut B>) {
+        let this = self.project();
+        (this.first, this.second)
+    }
+
+    /// Consume `self`, returning the inner bodies
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+/// What an [`Interleave`] body does when one of its bodies ends before the other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InterleaveEnd {
+    /// Keep yielding the frames of the other body until it ends too.
+    ///
+    /// This is the default.
+    Continue,
+    /// End as soon as either body ends.
+    Stop,
+}
+
+impl Default for InterleaveEnd {
+    fn default() -> Self {
+        InterleaveEnd::Continue
+    }
+}
+
+impl<A, B> Body for Interleave<A, B>
+where
+    A: Body,
+    B: Body<Data = A::Data, Error = A::Error>,
+{
+    type Data = A::Data;
+    type Error = A::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+
+        loop {
+            if *this.done || (*this.first_done && *this.second_done) {
+                *this.done = true;
+                return Poll::Ready(
+                    this.trailers
+                        .take()
+                        .map(|trailers| Ok(Frame::trailers(trailers))),
+                );
+            }
+
+            let second = *this.first_done || (*this.second_next && !*this.second_done);
+            let frame = if second {
+                ready!(this.second.as_mut().poll_frame(cx))
+            } else {
+                ready!(this.first.as_mut().poll_frame(cx))
+            };
+
+            match frame {
+                Some(Ok(frame)) => match frame.into_trailers() {
+                    // Trailers can only come last, so they are merged and yielded at the end.
+                    Ok(trailers) => this
+                        .trailers
+                        .get_or_insert_with(HeaderMap::new)
+                        .extend(trailers),
+                    Err(frame) => {
+                        *this.second_next = !second;
+                        return Poll::Ready(Some(Ok(frame)));
+                    }
+                },
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => {
+                    if second {
+                        *this.second_done = true;
+                    } else {
+                        *this.first_done = true;
+                    }
+                    if *this.on_end == InterleaveEnd::Stop {
+                        *this.done = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.done
+            || (self.trailers.is_none()
+                && (self.first_done || self.first.is_end_stream())
+                && (self.second_done || self.second.is_end_stream()))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.done {
+            return SizeHint::with_exact(0);
+        }
+
+        let first = if self.first_done {
+            SizeHint::with_exact(0)
+        } else {
+            self.first.size_hint()
+        };
+        let second = if self.second_done {
+            SizeHint::with_exact(0)
+        } else {
+            self.second.size_hint()
+        };
+
+        let mut hint = SizeHint::new();
+        if let (Some(first), Some(second)) = (first.upper(), second.upper()) {
+            if let Some(upper) = first.checked_add(second) {
+                hint.set_upper(upper);
+            }
+        }
+        if self.on_end == InterleaveEnd::Continue {
+            hint.set_lower(first.lower().saturating_add(second.lower()));
+        }
+        hint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::test_support::{test_body, trailer};
+    use crate::{BodyExt, Full};
+
+    async fn frames<B>(mut body: B) -> (Vec<Bytes>, Option<HeaderMap>)
+    where
+        B: Body<Data = Bytes, Error = Infallible> + Unpin,
+    {
+        let mut data = Vec::new();
+        let mut trailers = None;
+        while let Some(frame) = body.frame().await {
+            match frame.unwrap().into_data() {
+                Ok(chunk) => data.push(chunk),
+                Err(frame) => trailers = frame.into_trailers().ok(),
+            }
+        }
+        (data, trailers)
+    }
+
+    #[tokio::test]
+    async fn alternates() {
+        let body = test_body(&["a1", "a2"], None).interleave(test_body(&["b1", "b2"], None));
+        let (data, trailers) = frames(body).await;
+        assert_eq!(data, ["a1", "b1", "a2", "b2"]);
+        assert!(trailers.is_none());
+    }
+
+    #[tokio::test]
+    async fn continues_after_early_end() {
+        let body = test_body(&["a1"], None).interleave(test_body(&["b1", "b2", "b3"], None));
+        let (data, _) = frames(body).await;
+        assert_eq!(data, ["a1", "b1", "b2", "b3"]);
+
+        let body = test_body(&["a1", "a2", "a3"], None).interleave(test_body::<&str>(&[], None));
+        let (data, _) = frames(body).await;
+        assert_eq!(data, ["a1", "a2", "a3"]);
+    }
+
+    #[tokio::test]
+    async fn stops_after_early_end() {
+        let body = test_body(&["a1", "a2"], Some(trailer("x-a")))
+            .interleave(test_body(&["b1"], Some(trailer("x-b"))))
+            .on_end(InterleaveEnd::Stop);
+        let (data, trailers) = frames(body).await;
+        assert_eq!(data, ["a1", "b1", "a2"]);
+        // The second body ended after its trailers, which are kept.
+        assert_eq!(trailers.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn merges_trailers() {
+        let body = test_body(&["a1"], Some(trailer("x-a")))
+            .interleave(test_body(&["b1"], Some(trailer("x-b"))));
+        let (data, trailers) = frames(body).await;
+        assert_eq!(data, ["a1", "b1"]);
+        let trailers = trailers.unwrap();
+        assert!(trailers.contains_key("x-a"));
+        assert!(trailers.contains_key("x-b"));
+    }
+
+    #[test]
+    fn size_hint() {
+        let body = Full::new(Bytes::from("abc")).interleave(Full::new(Bytes::from("de")));
+        assert_eq!(body.size_hint().exact(), Some(5));
+
+        let body = body.on_end(InterleaveEnd::Stop);
+        assert_eq!(body.size_hint().lower(), 0);
+        assert_eq!(body.size_hint().upper(), Some(5));
+    }
+}
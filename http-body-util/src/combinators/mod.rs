@@ -1,17 +1,60 @@
 //! Combinators for the `Body` trait.
 
 mod box_body;
+mod coalesce;
 mod collect;
+mod collect_frames;
+mod collect_message;
+mod collect_string;
+mod collect_trailers;
+mod collect_with_limit;
+#[cfg(feature = "serde")]
+mod deserialize;
+#[cfg(feature = "digest")]
+mod digest;
+mod drain;
+mod err_into;
 mod frame;
+mod fuse;
+mod interleave;
 mod map_err;
 mod map_frame;
+mod on_eos;
+mod on_incomplete;
+mod peekable;
+mod preview;
+mod progress;
 mod with_trailers;
+#[cfg(feature = "io")]
+mod write_to;
 
 pub use self::{
-    box_body::{BoxBody, UnsyncBoxBody},
+    box_body::{BoxBody, BoxCloneBody, BytesBoxBody, LocalBoxBody, UnsyncBoxBody},
+    coalesce::Coalesce,
     collect::Collect,
+    collect_frames::CollectFrames,
+    collect_message::{IntoBytesWithLimit, IntoCollected},
+    collect_string::{CollectString, CollectStringError},
+    collect_trailers::{CollectTrailers, CollectTrailersError, CollectTrailersStrict},
+    collect_with_limit::{CollectLimitError, CollectWithLimit},
+    drain::Drain,
+    err_into::ErrInto,
     frame::Frame,
+    fuse::Fuse,
+    interleave::{Interleave, InterleaveEnd},
     map_err::MapErr,
     map_frame::MapFrame,
-    with_trailers::WithTrailers,
+    on_eos::{EndOfStream, OnEos},
+    on_incomplete::OnIncomplete,
+    peekable::{Peek, Peekable},
+    preview::Preview,
+    progress::Progress,
+    with_trailers::{RejectConflicts, TrailersConflictError, TrailersMerge, WithTrailers},
 };
+
+#[cfg(feature = "serde")]
+pub use self::deserialize::{Deserialize, DeserializeError};
+#[cfg(feature = "digest")]
+pub use self::digest::{Digest, DigestEncoding, DigestError, ExpectedDigest, VerifyDigest};
+#[cfg(feature = "io")]
+pub use self::write_to::WriteTo;
@@ -0,0 +1,197 @@
+use bytes::Buf;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::{
+    any::type_name,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pin_project! {
+    /// Body returned by the [`on_eos`] combinator.
+    ///
+    /// [`on_eos`]: crate::BodyExt::on_eos
+    pub struct OnEos<B, F> {
+        #[pin]
+        inner: B,
+        f: Option<F>,
+        stats: EndOfStream,
+    }
+}
+
+impl<B, F> OnEos<B, F> {
+    #[inline]
+    pub(crate) fn new(body: B, f: F) -> Self {
+        Self {
+            inner: body,
+            f: Some(f),
+            stats: EndOfStream::default(),
+        }
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B, F> Body for OnEos<B, F>
+where
+    B: Body,
+    F: FnOnce(EndOfStream),
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        let frame = this.inner.as_mut().poll_frame(cx);
+
+        let ended = match &frame {
+            Poll::Ready(Some(Ok(frame))) => {
+                this.stats.frames += 1;
+                if let Some(data) = frame.data_ref() {
+                    this.stats.bytes += data.remaining() as u64;
+                } else if frame.is_trailers() {
+                    this.stats.trailers = true;
+                }
+                // The stats are final once the body reports its end, and waiting for `None`
+                // would skip the callback for callers that stop polling here.
+                this.inner.is_end_stream()
+            }
+            Poll::Ready(None) => true,
+            Poll::Ready(Some(Err(_))) | Poll::Pending => false,
+        };
+
+        if ended {
+            if let Some(f) = this.f.take() {
+                f(*this.stats);
+            }
+        }
+
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B, F> fmt::Debug for OnEos<B, F>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OnEos")
+            .field("inner", &self.inner)
+            .field("f", &type_name::<F>())
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+/// Statistics passed to the [`on_eos`] callback once a body has successfully ended.
+///
+/// [`on_eos`]: crate::BodyExt::on_eos
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EndOfStream {
+    bytes: u64,
+    frames: u64,
+    trailers: bool,
+}
+
+impl EndOfStream {
+    /// Returns the total number of data bytes yielded by the body.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Returns the total number of frames yielded by the body, including trailers.
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Returns whether the body yielded trailers.
+    pub fn has_trailers(&self) -> bool {
+        self.trailers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::sync::{Arc, Mutex};
+
+    use bytes::Bytes;
+    use futures_util::stream;
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::{BodyExt, Full, StreamBody};
+
+    #[tokio::test]
+    async fn fires_once_with_stats() {
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hel"))),
+            Ok(Frame::data(Bytes::from("lo"))),
+            Ok(Frame::trailers(HeaderMap::new())),
+        ];
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let calls2 = calls.clone();
+        let mut body = StreamBody::new(stream::iter(frames))
+            .on_eos(move |stats| calls2.lock().unwrap().push(stats));
+
+        while body.frame().await.is_some() {}
+        assert!(body.frame().await.is_none());
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].bytes(), 5);
+        assert_eq!(calls[0].frames(), 3);
+        assert!(calls[0].has_trailers());
+    }
+
+    #[tokio::test]
+    async fn fires_when_inner_reports_end_stream() {
+        let mut fired = false;
+        let mut body = Full::new(Bytes::from("hello")).on_eos(|_| fired = true);
+
+        body.frame().await.unwrap().unwrap();
+        drop(body);
+        assert!(fired);
+    }
+
+    #[tokio::test]
+    async fn does_not_fire_on_error() {
+        let mut fired = false;
+        let mut body = StreamBody::new(stream::iter(vec![Err::<Frame<Bytes>, _>("oh no")]))
+            .on_eos(|_| fired = true);
+
+        assert!(body.frame().await.unwrap().is_err());
+        drop(body);
+        assert!(!fired);
+    }
+}
@@ -0,0 +1,165 @@
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::{
+    any::type_name,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pin_project! {
+    /// Body returned by the [`on_incomplete`] combinator.
+    ///
+    /// [`on_incomplete`]: crate::BodyExt::on_incomplete
+    pub struct OnIncomplete<B, F>
+    where
+        F: FnOnce(),
+    {
+        #[pin]
+        inner: B,
+        guard: Guard<F>,
+    }
+}
+
+/// Calls the callback when dropped, unless it was disarmed first.
+struct Guard<F: FnOnce()> {
+    f: Option<F>,
+}
+
+impl<F: FnOnce()> Drop for Guard<F> {
+    fn drop(&mut self) {
+        if let Some(f) = self.f.take() {
+            f();
+        }
+    }
+}
+
+impl<B, F: FnOnce()> OnIncomplete<B, F> {
+    #[inline]
+    pub(crate) fn new(body: B, f: F) -> Self {
+        Self {
+            inner: body,
+            guard: Guard { f: Some(f) },
+        }
+    }
+
+    /// Returns whether the body has reached its end, disarming the callback.
+    pub fn is_complete(&self) -> bool {
+        self.guard.f.is_none()
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+}
+
+impl<B, F> Body for OnIncomplete<B, F>
+where
+    B: Body,
+    F: FnOnce(),
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        let frame = this.inner.as_mut().poll_frame(cx);
+
+        let complete = match &frame {
+            // A body that reports its end was read completely, so dropping it before polling for
+            // `None` must not count as an incomplete read.
+            Poll::Ready(Some(Ok(_))) => this.inner.is_end_stream(),
+            Poll::Ready(None) => true,
+            Poll::Ready(Some(Err(_))) | Poll::Pending => false,
+        };
+        if complete {
+            this.guard.f = None;
+        }
+
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B, F> fmt::Debug for OnIncomplete<B, F>
+where
+    B: fmt::Debug,
+    F: FnOnce(),
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OnIncomplete")
+            .field("inner", &self.inner)
+            .field("f", &type_name::<F>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use futures_util::stream;
+
+    use super::*;
+    use crate::{BodyExt, Full, StreamBody};
+
+    #[tokio::test]
+    async fn fires_when_dropped_early() {
+        let fired = Arc::new(AtomicBool::new(false));
+        let fired2 = fired.clone();
+        let frames = vec![
+            Ok::<_, &str>(Frame::data(Bytes::from("a"))),
+            Ok(Frame::data(Bytes::from("b"))),
+        ];
+        let mut body = StreamBody::new(stream::iter(frames))
+            .on_incomplete(move || fired2.store(true, Ordering::SeqCst));
+
+        body.frame().await.unwrap().unwrap();
+        assert!(!body.is_complete());
+        drop(body);
+        assert!(fired.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn does_not_fire_when_complete() {
+        let mut fired = false;
+        let body = Full::new(Bytes::from("hello")).on_incomplete(|| fired = true);
+
+        body.collect().await.unwrap();
+        assert!(!fired);
+    }
+
+    #[tokio::test]
+    async fn fires_after_error() {
+        let mut fired = false;
+        let mut body = StreamBody::new(stream::iter(vec![Err::<Frame<Bytes>, _>("oh no")]))
+            .on_incomplete(|| fired = true);
+
+        assert!(body.frame().await.unwrap().is_err());
+        drop(body);
+        assert!(fired);
+    }
+}
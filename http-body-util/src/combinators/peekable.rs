@@ -0,0 +1,185 @@
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+type PeekedFrame<'a, B> = Option<Result<&'a Frame<<B as Body>::Data>, &'a <B as Body>::Error>>;
+
+pin_project! {
+    /// A body that can peek at the next frame without consuming it.
+    ///
+    /// Returned by [`BodyExt::peekable`].
+    ///
+    /// [`BodyExt::peekable`]: crate::BodyExt::peekable
+    #[derive(Debug)]
+    pub struct Peekable<B: Body> {
+        // `Some(None)` once the inner body has ended.
+        peeked: Option<Option<Result<Frame<B::Data>, B::Error>>>,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B: Body> Peekable<B> {
+    pub(crate) fn new(inner: B) -> Self {
+        Self {
+            peeked: None,
+            inner,
+        }
+    }
+
+    /// Returns a future that resolves to a reference to the next frame, without consuming it.
+    ///
+    /// The frame is yielded by the next call to `poll_frame`. Resolves to `None` if the body has
+    /// ended.
+    pub fn peek(&mut self) -> Peek<'_, B>
+    where
+        B: Unpin,
+    {
+        Peek(Some(self))
+    }
+
+    /// Attempt to peek at the next frame, without consuming it.
+    pub fn poll_peek(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<PeekedFrame<'_, B>> {
+        let this = self.project();
+
+        if this.peeked.is_none() {
+            *this.peeked = Some(ready!(this.inner.poll_frame(cx)));
+        }
+
+        Poll::Ready(this.peeked.as_ref().unwrap().as_ref().map(Result::as_ref))
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    ///
+    /// A frame that has been peeked at is lost.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: Body> Body for Peekable<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+
+        match this.peeked.take() {
+            Some(frame) => Poll::Ready(frame),
+            None => this.inner.poll_frame(cx),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.peeked {
+            Some(Some(_)) => false,
+            Some(None) => true,
+            None => self.inner.is_end_stream(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.peeked {
+            Some(Some(Ok(frame))) => {
+                let peeked = frame.data_ref().map_or(0, |data| data.remaining() as u64);
+                self.inner
+                    .size_hint()
+                    .saturating_add(&SizeHint::with_exact(peeked))
+            }
+            Some(_) => SizeHint::with_exact(0),
+            None => self.inner.size_hint(),
+        }
+    }
+}
+
+/// Future returned by [`Peekable::peek`].
+#[must_use = "futures don't do anything unless polled"]
+pub struct Peek<'a, B: Body>(Option<&'a mut Peekable<B>>);
+
+impl<B: Body> fmt::Debug for Peek<'_, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Peek").finish()
+    }
+}
+
+impl<'a, B: Body + Unpin> Future for Peek<'a, B> {
+    type Output = PeekedFrame<'a, B>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let peekable = self.0.as_mut().expect("polled after complete");
+        ready!(Pin::new(&mut **peekable).poll_peek(cx));
+
+        // Peeking again is ready immediately, and returns a reference with the full lifetime.
+        let peekable = self.0.take().unwrap();
+        Pin::new(peekable).poll_peek(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use http_body::Body;
+
+    use crate::{BodyExt, Empty, Full, StreamBody};
+
+    #[tokio::test]
+    async fn peek_then_poll() {
+        let frames = vec![
+            Ok::<_, Infallible>(http_body::Frame::data(Bytes::from("hello"))),
+            Ok(http_body::Frame::data(Bytes::from(" world"))),
+        ];
+        let mut body = StreamBody::new(futures_util::stream::iter(frames)).peekable();
+
+        let frame = body.peek().await.unwrap().unwrap();
+        assert_eq!(frame.data_ref().unwrap(), "hello");
+        // Peeking twice returns the same frame.
+        let frame = body.peek().await.unwrap().unwrap();
+        assert_eq!(frame.data_ref().unwrap(), "hello");
+
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn peek_keeps_size_hint() {
+        let mut body = Full::new(Bytes::from("hello")).peekable();
+        assert!(body.peek().await.is_some());
+        assert!(!body.is_end_stream());
+        assert_eq!(body.size_hint().exact(), Some(5));
+    }
+
+    #[tokio::test]
+    async fn peek_empty() {
+        let mut body = Empty::<Bytes>::new().peekable();
+        assert!(body.peek().await.is_none());
+        assert!(body.is_end_stream());
+        assert!(body.frame().await.is_none());
+    }
+}
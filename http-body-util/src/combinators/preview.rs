@@ -0,0 +1,187 @@
+use bytes::Buf;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::HexPreview;
+
+pin_project! {
+    /// Body returned by the [`preview`] combinator.
+    ///
+    /// [`preview`]: crate::BodyExt::preview
+    #[derive(Clone)]
+    pub struct Preview<B> {
+        #[pin]
+        inner: B,
+        head: Vec<u8>,
+        limit: usize,
+        bytes: u64,
+    }
+}
+
+impl<B> Preview<B> {
+    #[inline]
+    pub(crate) fn new(body: B, limit: usize) -> Self {
+        Self {
+            inner: body,
+            head: Vec::new(),
+            limit,
+            bytes: 0,
+        }
+    }
+
+    /// Returns a preview of the data yielded so far.
+    ///
+    /// The preview shows up to the first `limit` bytes, and counts the rest of the bytes yielded
+    /// so far as omitted.
+    pub fn hex(&self) -> HexPreview<'_> {
+        HexPreview::with_total(&self.head, self.bytes)
+    }
+
+    /// Returns the number of data bytes yielded so far.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for Preview<B>
+where
+    B: Body,
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        let frame = this.inner.as_mut().poll_frame(cx);
+
+        if let Poll::Ready(Some(Ok(frame))) = &frame {
+            if let Some(data) = frame.data_ref() {
+                *this.bytes += data.remaining() as u64;
+
+                // Copy from the chunks without advancing the data, which is yielded as is.
+                let mut chunks = [std::io::IoSlice::new(&[]); 16];
+                let n = data.chunks_vectored(&mut chunks);
+                let mut copied = 0;
+                for chunk in &chunks[..n] {
+                    let take = (*this.limit - this.head.len()).min(chunk.len());
+                    if take == 0 {
+                        break;
+                    }
+                    this.head.extend_from_slice(&chunk[..take]);
+                    copied += take;
+                }
+
+                // The chunks may not cover all the data. The head must stay contiguous, so it
+                // stops where they end.
+                if copied < data.remaining() && this.head.len() < *this.limit {
+                    *this.limit = this.head.len();
+                }
+            }
+        }
+
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B> fmt::Debug for Preview<B>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Preview")
+            .field("inner", &self.inner)
+            .field("head", &self.hex())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use futures_util::stream;
+
+    use super::*;
+    use crate::{BodyExt, BufList, StreamBody};
+
+    #[tokio::test]
+    async fn keeps_first_bytes() {
+        let chunks = vec!["he", "llo", " world"]
+            .into_iter()
+            .map(|chunk| Ok::<_, Infallible>(Frame::data(Bytes::from(chunk))));
+        let mut body = StreamBody::new(stream::iter(chunks)).preview(4);
+
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(body.hex().data(), b"he");
+        assert_eq!(body.hex().omitted(), 0);
+
+        while body.frame().await.is_some() {}
+        assert_eq!(body.hex().data(), b"hell");
+        assert_eq!(body.hex().omitted(), 7);
+        assert_eq!(body.bytes(), 11);
+    }
+
+    #[tokio::test]
+    async fn copies_across_chunks() {
+        let data: BufList<Bytes> = vec![Bytes::from("ab"), Bytes::from("cd")]
+            .into_iter()
+            .collect();
+        let chunks = vec![Ok::<_, Infallible>(Frame::data(data))];
+        let mut body = StreamBody::new(stream::iter(chunks)).preview(3);
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data.remaining(), 4);
+        assert_eq!(body.hex().data(), b"abc");
+    }
+
+    #[tokio::test]
+    async fn stops_at_uncovered_data() {
+        let data: BufList<Bytes> = (b'a'..=b'z').map(|b| Bytes::from(vec![b])).collect();
+        let chunks = vec![
+            Ok::<_, Infallible>(Frame::data(data)),
+            Ok(Frame::data(vec![Bytes::from("!")].into_iter().collect())),
+        ];
+        let mut body = StreamBody::new(stream::iter(chunks)).preview(32);
+
+        while body.frame().await.is_some() {}
+        assert_eq!(body.hex().data(), b"abcdefghijklmnop");
+        assert_eq!(body.hex().omitted(), 11);
+    }
+}
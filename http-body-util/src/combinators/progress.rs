@@ -0,0 +1,149 @@
+use bytes::Buf;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::{
+    any::type_name,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pin_project! {
+    /// Body returned by the [`progress`] combinator.
+    ///
+    /// [`progress`]: crate::BodyExt::progress
+    #[derive(Clone, Copy)]
+    pub struct Progress<B, F> {
+        #[pin]
+        inner: B,
+        f: F,
+        bytes: u64,
+    }
+}
+
+impl<B, F> Progress<B, F> {
+    #[inline]
+    pub(crate) fn new(body: B, f: F) -> Self {
+        Self {
+            inner: body,
+            f,
+            bytes: 0,
+        }
+    }
+
+    /// Returns the number of data bytes yielded so far.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B, F> Body for Progress<B, F>
+where
+    B: Body,
+    F: FnMut(u64, &SizeHint),
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        let frame = this.inner.as_mut().poll_frame(cx);
+
+        if let Poll::Ready(Some(Ok(frame))) = &frame {
+            if let Some(data) = frame.data_ref() {
+                *this.bytes += data.remaining() as u64;
+
+                let remaining = this.inner.size_hint();
+                let mut total = SizeHint::new();
+                if let Some(upper) = remaining.upper() {
+                    total.set_upper(upper + *this.bytes);
+                }
+                total.set_lower(remaining.lower() + *this.bytes);
+
+                (this.f)(*this.bytes, &total);
+            }
+        }
+
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B, F> fmt::Debug for Progress<B, F>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("inner", &self.inner)
+            .field("f", &type_name::<F>())
+            .field("bytes", &self.bytes)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use futures_util::stream;
+
+    use super::*;
+    use crate::{BodyExt, Full, StreamBody};
+
+    #[tokio::test]
+    async fn reports_bytes_and_total() {
+        let mut calls = Vec::new();
+        let body = Full::new(Bytes::from("hello")).progress(|bytes, total| {
+            calls.push((bytes, total.exact()));
+        });
+
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.to_bytes(), "hello");
+        assert_eq!(calls, [(5, Some(5))]);
+    }
+
+    #[tokio::test]
+    async fn reports_after_each_data_frame() {
+        let chunks =
+            ["he", "llo", "!"].map(|chunk| Ok::<_, Infallible>(Frame::data(Bytes::from(chunk))));
+        let mut seen = Vec::new();
+        let mut body = StreamBody::new(stream::iter(chunks)).progress(|bytes, _| seen.push(bytes));
+
+        while body.frame().await.is_some() {}
+        assert_eq!(body.bytes(), 6);
+        drop(body);
+        assert_eq!(seen, [2, 5, 6]);
+    }
+}
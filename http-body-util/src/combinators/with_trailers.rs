@@ -1,21 +1,33 @@
 use std::{
+    error::Error,
+    fmt,
     future::Future,
     pin::Pin,
     task::{Context, Poll},
 };
 
 use futures_core::ready;
-use http::HeaderMap;
+use http::{HeaderMap, HeaderName};
 use http_body::{Body, Frame};
 use pin_project_lite::pin_project;
 
+type PolledFrame<T> = Option<Result<Frame<<T as Body>::Data>, <T as Body>::Error>>;
+
 pin_project! {
     /// Adds trailers to a body.
     ///
     /// See [`BodyExt::with_trailers`] for more details.
-    pub struct WithTrailers<T, F> {
+    ///
+    /// If both the body and the future yield trailers, they are merged into a single trailers
+    /// frame. By default, fields from the future replace the fields of the body with the same
+    /// name. Use [`merge`](WithTrailers::merge) or
+    /// [`reject_conflicts`](WithTrailers::reject_conflicts) to change this.
+    ///
+    /// [`BodyExt::with_trailers`]: crate::BodyExt::with_trailers
+    pub struct WithTrailers<T, F, M = TrailersMerge> {
         #[pin]
         state: State<T, F>,
+        merge: M,
     }
 }
 
@@ -26,10 +38,123 @@ impl<T, F> WithTrailers<T, F> {
                 body,
                 trailers: Some(trailers),
             },
+            merge: TrailersMerge::Replace,
         }
     }
+
+    /// Set how trailers from the future are merged with trailers of the body.
+    pub fn merge(mut self, policy: TrailersMerge) -> Self {
+        self.merge = policy;
+        self
+    }
+
+    /// Return a [`TrailersConflictError`] if the body and the future both yield a trailer field
+    /// with the same name.
+    ///
+    /// This avoids silently sending conflicting values for fields such as `grpc-status`.
+    pub fn reject_conflicts(self) -> WithTrailers<T, F, RejectConflicts>
+    where
+        T: Body,
+        T::Error: From<TrailersConflictError>,
+    {
+        WithTrailers {
+            state: self.state,
+            merge: RejectConflicts,
+        }
+    }
+}
+
+/// How [`WithTrailers`] merges trailers from its future with trailers of the body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TrailersMerge {
+    /// Fields from the future replace all values of the fields of the body with the same name.
+    ///
+    /// This is the default.
+    Replace,
+    /// Values from the future are appended to the values of the body.
+    Append,
 }
 
+impl Default for TrailersMerge {
+    fn default() -> Self {
+        TrailersMerge::Replace
+    }
+}
+
+impl TrailersMerge {
+    fn apply(self, mut trailers: HeaderMap, new_trailers: HeaderMap) -> HeaderMap {
+        match self {
+            TrailersMerge::Replace => trailers.extend(new_trailers),
+            TrailersMerge::Append => {
+                let mut last = None;
+                for (name, value) in new_trailers {
+                    if name.is_some() {
+                        last = name;
+                    }
+                    if let Some(name) = &last {
+                        trailers.append(name, value);
+                    }
+                }
+            }
+        }
+        trailers
+    }
+}
+
+/// The merge mode of a [`WithTrailers`] that rejects conflicting trailers.
+///
+/// See [`WithTrailers::reject_conflicts`].
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct RejectConflicts;
+
+impl RejectConflicts {
+    fn apply(
+        self,
+        mut trailers: HeaderMap,
+        new_trailers: HeaderMap,
+    ) -> Result<HeaderMap, TrailersConflictError> {
+        if let Some(name) = new_trailers
+            .keys()
+            .find(|name| trailers.contains_key(*name))
+        {
+            let name = name.clone();
+            return Err(TrailersConflictError { name });
+        }
+        trailers.extend(new_trailers);
+        Ok(trailers)
+    }
+}
+
+/// An error returned by [`WithTrailers`] when the body and the future both yield a trailer field
+/// with the same name.
+///
+/// See [`WithTrailers::reject_conflicts`].
+#[derive(Debug)]
+pub struct TrailersConflictError {
+    name: HeaderName,
+}
+
+impl TrailersConflictError {
+    /// Returns the name of the conflicting trailer field.
+    pub fn name(&self) -> &HeaderName {
+        &self.name
+    }
+}
+
+impl fmt::Display for TrailersConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "trailer field `{}` set by both the body and the trailers future",
+            self.name
+        )
+    }
+}
+
+impl Error for TrailersConflictError {}
+
 pin_project! {
     #[project = StateProj]
     enum State<T, F> {
@@ -47,18 +172,20 @@ pin_project! {
     }
 }
 
-impl<T, F> Body for WithTrailers<T, F>
+impl<T, F, M> WithTrailers<T, F, M>
 where
     T: Body,
     F: Future<Output = Option<Result<HeaderMap, T::Error>>>,
+    M: Copy,
 {
-    type Data = T::Data;
-    type Error = T::Error;
-
-    fn poll_frame(
+    fn poll_merged<G>(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
-    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        merge: G,
+    ) -> Poll<PolledFrame<T>>
+    where
+        G: FnOnce(M, HeaderMap, HeaderMap) -> Result<HeaderMap, T::Error>,
+    {
         loop {
             let mut this = self.as_mut().project();
 
@@ -95,10 +222,10 @@ where
                             this.state.set(State::Done);
                             return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
                         }
-                        (Some(new_trailers), Some(mut prev_trailers)) => {
-                            prev_trailers.extend(new_trailers);
+                        (Some(new_trailers), Some(prev_trailers)) => {
                             this.state.set(State::Done);
-                            return Poll::Ready(Some(Ok(Frame::trailers(prev_trailers))));
+                            let trailers = merge(*this.merge, prev_trailers, new_trailers);
+                            return Poll::Ready(Some(trailers.map(Frame::trailers)));
                         }
                     }
                 }
@@ -109,8 +236,7 @@ where
         }
     }
 
-    #[inline]
-    fn size_hint(&self) -> http_body::SizeHint {
+    fn body_size_hint(&self) -> http_body::SizeHint {
         match &self.state {
             State::PollBody { body, .. } => body.size_hint(),
             State::PollTrailers { .. } | State::Done => Default::default(),
@@ -118,6 +244,53 @@ where
     }
 }
 
+impl<T, F> Body for WithTrailers<T, F>
+where
+    T: Body,
+    F: Future<Output = Option<Result<HeaderMap, T::Error>>>,
+{
+    type Data = T::Data;
+    type Error = T::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.poll_merged(cx, |policy, trailers, new_trailers| {
+            Ok(policy.apply(trailers, new_trailers))
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.body_size_hint()
+    }
+}
+
+impl<T, F> Body for WithTrailers<T, F, RejectConflicts>
+where
+    T: Body,
+    T::Error: From<TrailersConflictError>,
+    F: Future<Output = Option<Result<HeaderMap, T::Error>>>,
+{
+    type Data = T::Data;
+    type Error = T::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.poll_merged(cx, |reject, trailers, new_trailers| {
+            reject.apply(trailers, new_trailers).map_err(From::from)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.body_size_hint()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::Infallible;
@@ -204,6 +377,69 @@ mod tests {
         assert!(unwrap_ready(body.as_mut().poll_frame(&mut cx)).is_none());
     }
 
+    fn conflicting() -> impl Body<Data = Bytes, Error = Box<dyn Error + Send + Sync>> {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("2"));
+        trailers.insert("x-body", HeaderValue::from_static("a"));
+        Empty::<Bytes>::new()
+            .with_trailers(std::future::ready(Some(Ok(trailers))))
+            .map_err(|err: Infallible| match err {})
+    }
+
+    fn future_trailers(
+    ) -> impl Future<Output = Option<Result<HeaderMap, Box<dyn Error + Send + Sync>>>> {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+        std::future::ready(Some(Ok(trailers)))
+    }
+
+    #[tokio::test]
+    async fn merge_policies() {
+        let status = |trailers: &HeaderMap| {
+            trailers
+                .get_all("grpc-status")
+                .iter()
+                .map(|value| value.to_str().unwrap().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        let body = conflicting().with_trailers(future_trailers());
+        let trailers = body.collect_trailers().await.unwrap().unwrap();
+        assert_eq!(status(&trailers), ["0"]);
+        assert_eq!(trailers["x-body"], "a");
+
+        let body = conflicting()
+            .with_trailers(future_trailers())
+            .merge(TrailersMerge::Append);
+        let trailers = body.collect_trailers().await.unwrap().unwrap();
+        assert_eq!(status(&trailers), ["2", "0"]);
+        assert_eq!(trailers["x-body"], "a");
+    }
+
+    #[tokio::test]
+    async fn reject_conflicts() {
+        let body = conflicting()
+            .with_trailers(future_trailers())
+            .reject_conflicts();
+        let err = body.collect_trailers().await.unwrap_err();
+        let err = err.downcast_ref::<TrailersConflictError>().unwrap();
+        assert_eq!(err.name(), "grpc-status");
+
+        let body = Empty::<Bytes>::new()
+            .map_err(|err: Infallible| -> Box<dyn Error + Send + Sync> { match err {} })
+            .with_trailers(future_trailers())
+            .reject_conflicts();
+        let trailers = body.collect_trailers().await.unwrap().unwrap();
+        assert_eq!(trailers["grpc-status"], "0");
+    }
+
+    #[test]
+    fn no_body_bound() {
+        // The type can be named for any body, so downstream bounds don't need `T: Body`.
+        fn assert_type<T, F>(_: Option<WithTrailers<T, F>>) {}
+        assert_type::<(), ()>(None);
+    }
+
     fn unwrap_ready<T>(poll: Poll<T>) -> T {
         match poll {
             Poll::Ready(t) => t,
@@ -0,0 +1,158 @@
+use std::{
+    error::Error,
+    fmt,
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http::HeaderMap;
+use http_body::Body;
+use pin_project_lite::pin_project;
+use tokio::io::AsyncWrite;
+
+pin_project! {
+    /// Future that writes the data of a body into an [`AsyncWrite`].
+    ///
+    /// See [`BodyExt::write_to`] for more details.
+    ///
+    /// [`BodyExt::write_to`]: crate::BodyExt::write_to
+    #[must_use = "futures don't do anything unless polled"]
+    pub struct WriteTo<B, W>
+    where
+        B: Body,
+    {
+        writer: W,
+        chunk: Option<B::Data>,
+        written: u64,
+        trailers: Option<HeaderMap>,
+        ended: bool,
+        #[pin]
+        body: B,
+    }
+}
+
+impl<B: Body, W> WriteTo<B, W> {
+    pub(crate) fn new(body: B, writer: W) -> Self {
+        Self {
+            writer,
+            chunk: None,
+            written: 0,
+            trailers: None,
+            ended: false,
+            body,
+        }
+    }
+}
+
+impl<B, W> Future for WriteTo<B, W>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+    W: AsyncWrite + Unpin,
+{
+    type Output = Result<(u64, Option<HeaderMap>), Box<dyn Error + Send + Sync>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut me = self.project();
+
+        loop {
+            if let Some(chunk) = me.chunk {
+                while chunk.has_remaining() {
+                    let n = ready!(Pin::new(&mut *me.writer).poll_write(cx, chunk.chunk()))?;
+                    if n == 0 {
+                        return Poll::Ready(Err(io::Error::from(io::ErrorKind::WriteZero).into()));
+                    }
+                    chunk.advance(n);
+                    *me.written += n as u64;
+                }
+                *me.chunk = None;
+            }
+
+            if *me.ended {
+                ready!(Pin::new(&mut *me.writer).poll_flush(cx))?;
+                return Poll::Ready(Ok((*me.written, me.trailers.take())));
+            }
+
+            match ready!(me.body.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => *me.chunk = Some(data),
+                    Err(frame) => {
+                        if let Ok(trailers) = frame.into_trailers() {
+                            *me.trailers = Some(trailers);
+                        }
+                    }
+                },
+                Some(Err(err)) => return Poll::Ready(Err(err.into())),
+                None => *me.ended = true,
+            }
+        }
+    }
+}
+
+impl<B, W> fmt::Debug for WriteTo<B, W>
+where
+    B: Body + fmt::Debug,
+    W: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteTo")
+            .field("writer", &self.writer)
+            .field("written", &self.written)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use futures_util::stream;
+    use http::HeaderMap;
+    use http_body::Frame;
+
+    use crate::{BodyExt, StreamBody};
+
+    #[tokio::test]
+    async fn writes_data_and_returns_trailers() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("foo", "bar".parse().unwrap());
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hello "))),
+            Ok(Frame::data(Bytes::from("world"))),
+            Ok(Frame::trailers(trailers)),
+        ];
+        let body = StreamBody::new(stream::iter(frames));
+
+        let mut out = Vec::new();
+        let (written, trailers) = body.write_to(&mut out).await.unwrap();
+        assert_eq!(written, 11);
+        assert_eq!(trailers.unwrap()["foo"], "bar");
+        assert_eq!(out, b"hello world");
+    }
+
+    #[tokio::test]
+    async fn partial_writes() {
+        let (mut client, mut server) = tokio::io::duplex(3);
+        let body = crate::Full::new(Bytes::from("hello world"));
+
+        let reader = tokio::spawn(async move {
+            let mut out = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(&mut server, &mut out)
+                .await
+                .unwrap();
+            out
+        });
+        let (written, trailers) = body.write_to(&mut client).await.unwrap();
+        drop(client);
+
+        assert_eq!(written, 11);
+        assert!(trailers.is_none());
+        assert_eq!(reader.await.unwrap(), b"hello world");
+    }
+}
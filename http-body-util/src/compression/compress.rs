@@ -0,0 +1,297 @@
+use std::{
+    error::Error,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, Bytes};
+use http::HeaderMap;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+#[cfg(feature = "zstd")]
+use std::io;
+
+use super::{ContentEncoding, Encoder};
+
+pin_project! {
+    /// A body that compresses the data of another body.
+    ///
+    /// Data frames are compressed as they arrive. Whenever the wrapped body has no frame ready,
+    /// the data compressed so far is flushed so that a slow body is not held back. Trailers are
+    /// passed through after the compressed data.
+    ///
+    /// The size of the compressed body is not known in advance, so the size hint is unknown.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::compression::{Compress, ContentEncoding};
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// # #[cfg(feature = "gzip")]
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Compress::new(Full::new(Bytes::from("hello")), ContentEncoding::Gzip);
+    /// let compressed = body.collect().await.unwrap().to_bytes();
+    /// assert_eq!(&compressed[..2], b"\x1f\x8b");
+    /// # }
+    /// # #[cfg(not(feature = "gzip"))]
+    /// # fn main() {}
+    /// ```
+    pub struct Compress<B> {
+        #[pin]
+        inner: B,
+        encoding: ContentEncoding,
+        encoder: Option<Encoder>,
+        dirty: bool,
+        trailers: Option<HeaderMap>,
+    }
+}
+
+impl<B> Compress<B> {
+    /// Create a new `Compress` encoding `inner` with `encoding`.
+    pub fn new(inner: B, encoding: ContentEncoding) -> Self {
+        // Without a dictionary, creating an encoder can only fail to allocate.
+        let encoder = Encoder::new(encoding, &[]).expect("failed to create encoder");
+        Self::with_encoder(inner, encoding, encoder)
+    }
+
+    /// Create a new `Compress` encoding `inner` with zstd and a preset dictionary.
+    ///
+    /// Dictionaries improve the compression of small bodies. The same dictionary must be used to
+    /// decompress the body.
+    #[cfg(feature = "zstd")]
+    pub fn with_zstd_dictionary(inner: B, dictionary: &[u8]) -> io::Result<Self> {
+        let encoder = Encoder::new(ContentEncoding::Zstd, dictionary)?;
+        Ok(Self::with_encoder(inner, ContentEncoding::Zstd, encoder))
+    }
+
+    fn with_encoder(inner: B, encoding: ContentEncoding, encoder: Encoder) -> Self {
+        Self {
+            inner,
+            encoding,
+            encoder: Some(encoder),
+            dirty: false,
+            trailers: None,
+        }
+    }
+
+    /// Returns the encoding used by this body.
+    pub fn encoding(&self) -> ContentEncoding {
+        self.encoding
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for Compress<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = Bytes;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+
+        loop {
+            let encoder = match this.encoder.as_mut() {
+                Some(encoder) => encoder,
+                None => return Poll::Ready(this.trailers.take().map(|t| Ok(Frame::trailers(t)))),
+            };
+
+            let frame = if this.inner.is_end_stream() {
+                None
+            } else {
+                match this.inner.as_mut().poll_frame(cx) {
+                    Poll::Ready(Some(Ok(frame))) => Some(frame),
+                    Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err.into()))),
+                    Poll::Ready(None) => None,
+                    Poll::Pending => {
+                        if !*this.dirty {
+                            return Poll::Pending;
+                        }
+                        *this.dirty = false;
+                        encoder.flush()?;
+                        let output = encoder.take();
+                        if output.is_empty() {
+                            return Poll::Pending;
+                        }
+                        return Poll::Ready(Some(Ok(Frame::data(output))));
+                    }
+                }
+            };
+
+            match frame.map(Frame::into_data) {
+                Some(Ok(mut data)) => {
+                    while data.has_remaining() {
+                        let chunk = data.chunk();
+                        encoder.write(chunk)?;
+                        let len = chunk.len();
+                        data.advance(len);
+                    }
+                    *this.dirty = true;
+                    let output = encoder.take();
+                    if !output.is_empty() {
+                        return Poll::Ready(Some(Ok(Frame::data(output))));
+                    }
+                }
+                Some(Err(frame)) => {
+                    if let Ok(trailers) = frame.into_trailers() {
+                        *this.trailers = Some(trailers);
+                        let output = this.encoder.take().unwrap().finish()?;
+                        if !output.is_empty() {
+                            return Poll::Ready(Some(Ok(Frame::data(output))));
+                        }
+                    }
+                }
+                None => {
+                    let output = this.encoder.take().unwrap().finish()?;
+                    if !output.is_empty() {
+                        return Poll::Ready(Some(Ok(Frame::data(output))));
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.encoder.is_none() && self.trailers.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.is_end_stream() {
+            return SizeHint::with_exact(0);
+        }
+        SizeHint::default()
+    }
+}
+
+impl<B> fmt::Debug for Compress<B>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Compress")
+            .field("inner", &self.inner)
+            .field("encoding", &self.encoding)
+            .finish()
+    }
+}
+
+#[cfg(all(test, any(feature = "gzip", feature = "deflate")))]
+mod tests {
+    #[cfg(feature = "gzip")]
+    use std::convert::Infallible;
+    use std::io::Read;
+
+    #[cfg(feature = "gzip")]
+    use futures_util::{stream, StreamExt};
+    #[cfg(feature = "gzip")]
+    use http::HeaderValue;
+
+    use super::*;
+    #[cfg(feature = "gzip")]
+    use crate::StreamBody;
+    use crate::{BodyExt, Full};
+
+    #[cfg(feature = "gzip")]
+    fn gunzip(data: &[u8]) -> String {
+        let mut out = String::new();
+        flate2::read::GzDecoder::new(data)
+            .read_to_string(&mut out)
+            .unwrap();
+        out
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn gzip_roundtrip() {
+        let body = Compress::new(Full::new(Bytes::from("hello world")), ContentEncoding::Gzip);
+        assert_eq!(body.size_hint().exact(), None);
+
+        let compressed = body.collect().await.unwrap().to_bytes();
+        assert_eq!(gunzip(&compressed), "hello world");
+    }
+
+    #[cfg(feature = "deflate")]
+    #[tokio::test]
+    async fn deflate_roundtrip() {
+        let body = Compress::new(
+            Full::new(Bytes::from("hello world")),
+            ContentEncoding::Deflate,
+        );
+        let compressed = body.collect().await.unwrap().to_bytes();
+
+        let mut out = String::new();
+        flate2::read::ZlibDecoder::new(&compressed[..])
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out, "hello world");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn preserves_trailers() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("foo", HeaderValue::from_static("bar"));
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hello "))),
+            Ok(Frame::data(Bytes::from("world"))),
+            Ok(Frame::trailers(trailers)),
+        ];
+        let body = Compress::new(StreamBody::new(stream::iter(frames)), ContentEncoding::Gzip);
+
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.trailers().unwrap()["foo"], "bar");
+        assert_eq!(gunzip(&collected.to_bytes()), "hello world");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn flushes_when_pending() {
+        use std::io::Write;
+
+        let data = stream::iter(vec![Ok::<_, Infallible>(Frame::data(Bytes::from("hello")))]);
+        let body = Compress::new(
+            StreamBody::new(data.chain(stream::pending())),
+            ContentEncoding::Gzip,
+        );
+        futures_util::pin_mut!(body);
+        let waker = futures_util::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut decoder = flate2::write::GzDecoder::new(Vec::new());
+        while let Poll::Ready(frame) = body.as_mut().poll_frame(&mut cx) {
+            let data = frame.unwrap().unwrap().into_data().unwrap();
+            decoder.write_all(&data).unwrap();
+        }
+        decoder.flush().unwrap();
+        assert_eq!(decoder.get_ref(), b"hello");
+    }
+}
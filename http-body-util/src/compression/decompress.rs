@@ -0,0 +1,468 @@
+use std::{
+    error::Error,
+    fmt, io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use futures_core::ready;
+use http::{HeaderMap, HeaderValue};
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+use super::{ContentEncoding, Decoder};
+
+/// The largest data frame yielded by [`Decompress`].
+const MAX_FRAME_SIZE: usize = 64 * 1024;
+
+pin_project! {
+    /// A body that decompresses the data of another body.
+    ///
+    /// The decoders are chosen from a `Content-Encoding` header value. Data frames are decoded as
+    /// they arrive and trailers are passed through after the decoded data. A body with the
+    /// `identity` encoding, or an empty header value, is passed through unchanged.
+    ///
+    /// Decoded data is yielded in frames of at most 64 KiB, so a small frame that decompresses to
+    /// a lot of data is spread over many frames instead of being decoded at once. A stream that
+    /// ends before it is complete yields an [`io::ErrorKind::UnexpectedEof`] error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http::HeaderValue;
+    /// use http_body_util::compression::{Compress, ContentEncoding, Decompress};
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// # #[cfg(feature = "gzip")]
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Compress::new(Full::new(Bytes::from("hello")), ContentEncoding::Gzip);
+    ///
+    /// let encoding = HeaderValue::from_static("gzip");
+    /// let body = Decompress::new(body, &encoding).unwrap();
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    /// # }
+    /// # #[cfg(not(feature = "gzip"))]
+    /// # fn main() {}
+    /// ```
+    pub struct Decompress<B> {
+        #[pin]
+        inner: B,
+        // In the order they are applied, which is the reverse of the header.
+        stages: Vec<Stage>,
+        // Whether the inner body has no more data.
+        eof: bool,
+        finished: bool,
+        trailers: Option<HeaderMap>,
+    }
+}
+
+impl<B> Decompress<B> {
+    /// Create a new `Decompress` decoding `inner` according to a `Content-Encoding` value.
+    ///
+    /// Returns an error if the value names an encoding that is not supported, which includes
+    /// encodings whose feature is not enabled.
+    pub fn new(inner: B, content_encoding: &HeaderValue) -> Result<Self, UnsupportedEncodingError> {
+        let encodings = parse(content_encoding)?;
+        // Without a dictionary, creating a decoder can only fail to allocate.
+        let decoders = encodings
+            .into_iter()
+            .map(|encoding| Decoder::new(encoding, &[]).expect("failed to create decoder"))
+            .collect();
+        Ok(Self::with_decoders(inner, decoders))
+    }
+
+    /// Create a new `Decompress` that decodes zstd with a preset dictionary.
+    ///
+    /// The dictionary must be the one the data was compressed with. An unsupported
+    /// `Content-Encoding` is returned as an [`io::ErrorKind::InvalidInput`] error wrapping an
+    /// [`UnsupportedEncodingError`].
+    #[cfg(feature = "zstd")]
+    pub fn with_zstd_dictionary(
+        inner: B,
+        content_encoding: &HeaderValue,
+        dictionary: &[u8],
+    ) -> io::Result<Self> {
+        let encodings = parse(content_encoding)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        let decoders = encodings
+            .into_iter()
+            .map(|encoding| Decoder::new(encoding, dictionary))
+            .collect::<io::Result<_>>()?;
+        Ok(Self::with_decoders(inner, decoders))
+    }
+
+    fn with_decoders(inner: B, decoders: Vec<Decoder>) -> Self {
+        Self {
+            inner,
+            stages: decoders
+                .into_iter()
+                .map(|decoder| Stage {
+                    decoder,
+                    buf: BytesMut::new(),
+                })
+                .collect(),
+            eof: false,
+            finished: false,
+            trailers: None,
+        }
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for Decompress<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = Bytes;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+
+        loop {
+            if *this.finished {
+                return Poll::Ready(this.trailers.take().map(|t| Ok(Frame::trailers(t))));
+            }
+
+            if !this.stages.is_empty() {
+                match pull(this.stages) {
+                    Ok(data) if !data.is_empty() => {
+                        return Poll::Ready(Some(Ok(Frame::data(data))))
+                    }
+                    Ok(_) if *this.eof => {
+                        *this.finished = true;
+                        continue;
+                    }
+                    // The body may still have trailers, or data the decoders will reject.
+                    Ok(_) => {}
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
+                    Err(err) => return Poll::Ready(Some(Err(err.into()))),
+                }
+            }
+
+            let frame = if this.inner.is_end_stream() {
+                None
+            } else {
+                match ready!(this.inner.as_mut().poll_frame(cx)) {
+                    Some(Ok(frame)) => Some(frame),
+                    Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+                    None => None,
+                }
+            };
+
+            match frame.map(Frame::into_data) {
+                Some(Ok(mut data)) => {
+                    let data = data.copy_to_bytes(data.remaining());
+                    match this.stages.first_mut() {
+                        Some(stage) => stage.decoder.input().push(data),
+                        None => return Poll::Ready(Some(Ok(Frame::data(data)))),
+                    }
+                }
+                Some(Err(frame)) => {
+                    if let Ok(trailers) = frame.into_trailers() {
+                        *this.trailers = Some(trailers);
+                        end(this.stages, this.eof, this.finished);
+                    }
+                }
+                None => end(this.stages, this.eof, this.finished),
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.finished && self.trailers.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.is_end_stream() {
+            SizeHint::with_exact(0)
+        } else if self.stages.is_empty() {
+            self.inner.size_hint()
+        } else {
+            SizeHint::default()
+        }
+    }
+}
+
+/// Parses a `Content-Encoding` value into encodings in the order they must be decoded.
+fn parse(content_encoding: &HeaderValue) -> Result<Vec<ContentEncoding>, UnsupportedEncodingError> {
+    let value = content_encoding
+        .to_str()
+        .map_err(|_| UnsupportedEncodingError)?;
+
+    value
+        .split(',')
+        .map(str::trim)
+        .rev()
+        .filter(|token| !token.is_empty() && !token.eq_ignore_ascii_case("identity"))
+        .map(|token| ContentEncoding::from_token(token).ok_or(UnsupportedEncodingError))
+        .collect()
+}
+
+/// A decoder and the buffer its output is read into.
+struct Stage {
+    decoder: Decoder,
+    buf: BytesMut,
+}
+
+/// Reads up to [`MAX_FRAME_SIZE`] bytes from the last stage, feeding each stage from the one
+/// before it.
+///
+/// Fails with [`io::ErrorKind::WouldBlock`] if the first stage needs more data from the body, and
+/// returns empty data once every stage has ended.
+fn pull(stages: &mut [Stage]) -> io::Result<Bytes> {
+    let (stage, rest) = stages.split_last_mut().expect("at least one stage");
+    loop {
+        if stage.buf.is_empty() {
+            stage.buf = BytesMut::zeroed(MAX_FRAME_SIZE);
+        }
+        match stage.decoder.read(&mut stage.buf) {
+            Ok(read) => return Ok(stage.buf.split_to(read).freeze()),
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock && !rest.is_empty() => {
+                let data = pull(rest)?;
+                let input = stage.decoder.input();
+                if data.is_empty() {
+                    input.eof = true;
+                } else {
+                    input.push(data);
+                }
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Marks the end of the body's data.
+fn end(stages: &mut [Stage], eof: &mut bool, finished: &mut bool) {
+    *eof = true;
+    match stages.first_mut() {
+        Some(stage) => stage.decoder.input().eof = true,
+        None => *finished = true,
+    }
+}
+
+impl<B> fmt::Debug for Decompress<B>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Decompress")
+            .field("inner", &self.inner)
+            .field("decoders", &self.stages.len())
+            .finish()
+    }
+}
+
+/// An error returned by [`Decompress::new`] when the `Content-Encoding` is not supported.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct UnsupportedEncodingError;
+
+impl fmt::Display for UnsupportedEncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unsupported content encoding")
+    }
+}
+
+impl Error for UnsupportedEncodingError {}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "gzip")]
+    use std::convert::Infallible;
+    #[cfg(feature = "gzip")]
+    use std::io::Write;
+
+    #[cfg(feature = "gzip")]
+    use futures_util::stream;
+
+    use super::*;
+    use crate::compression::Compress;
+    #[cfg(feature = "gzip")]
+    use crate::StreamBody;
+    use crate::{BodyExt, Full};
+
+    #[cfg(feature = "gzip")]
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), Default::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn decodes_split_frames() {
+        let compressed = gzip(b"hello world");
+        let frames = compressed
+            .chunks(3)
+            .map(|chunk| Ok::<_, Infallible>(Frame::data(Bytes::copy_from_slice(chunk))))
+            .collect::<Vec<_>>();
+        let body = StreamBody::new(stream::iter(frames));
+
+        let body = Decompress::new(body, &HeaderValue::from_static("GZIP")).unwrap();
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello world");
+    }
+
+    #[cfg(all(feature = "gzip", feature = "deflate"))]
+    #[tokio::test]
+    async fn decodes_multiple_encodings() {
+        let body = Full::new(Bytes::from("hello world"));
+        let body = Compress::new(body, ContentEncoding::Deflate);
+        let body = Compress::new(body, ContentEncoding::Gzip);
+
+        let body = Decompress::new(body, &HeaderValue::from_static("deflate, gzip")).unwrap();
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn identity_passes_through() {
+        let body = Full::new(Bytes::from("hello"));
+        let body = Decompress::new(body, &HeaderValue::from_static("identity")).unwrap();
+        assert_eq!(body.size_hint().exact(), Some(5));
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn invalid_data_errors() {
+        let body = Full::new(Bytes::from("not gzip"));
+        let body = Decompress::new(body, &HeaderValue::from_static("gzip")).unwrap();
+        assert!(body.collect().await.is_err());
+    }
+
+    async fn roundtrip(encoding: ContentEncoding) {
+        let body = Compress::new(Full::new(Bytes::from("hello world")), encoding);
+        let value = HeaderValue::from_static(encoding.as_str());
+        let body = Decompress::new(body, &value).unwrap();
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello world");
+    }
+
+    async fn compressed(data: &'static [u8], encoding: ContentEncoding) -> Bytes {
+        let body = Compress::new(Full::new(Bytes::from_static(data)), encoding);
+        body.collect().await.unwrap().to_bytes()
+    }
+
+    async fn bounded_frames(encoding: ContentEncoding) {
+        static ZEROS: [u8; 1 << 20] = [0; 1 << 20];
+        let compressed = compressed(&ZEROS, encoding).await;
+        assert!(compressed.len() < MAX_FRAME_SIZE);
+
+        let value = HeaderValue::from_static(encoding.as_str());
+        let mut body = Decompress::new(Full::new(compressed), &value).unwrap();
+        let mut len = 0;
+        while let Some(frame) = body.frame().await {
+            let data = frame.unwrap().into_data().unwrap();
+            assert!(data.len() <= MAX_FRAME_SIZE);
+            assert!(data.iter().all(|&b| b == 0));
+            len += data.len();
+        }
+        assert_eq!(len, ZEROS.len());
+    }
+
+    async fn truncated(encoding: ContentEncoding) {
+        let compressed = compressed(b"hello world, hello world", encoding).await;
+        let value = HeaderValue::from_static(encoding.as_str());
+        for len in [0, compressed.len() / 2, compressed.len() - 1] {
+            let body = Full::new(compressed.slice(..len));
+            let body = Decompress::new(body, &value).unwrap();
+            let err = body.collect().await.unwrap_err();
+            let err = err.downcast_ref::<io::Error>().unwrap();
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof, "{len}: {err}");
+        }
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn decodes_gzip() {
+        roundtrip(ContentEncoding::Gzip).await;
+        bounded_frames(ContentEncoding::Gzip).await;
+        truncated(ContentEncoding::Gzip).await;
+    }
+
+    #[cfg(feature = "deflate")]
+    #[tokio::test]
+    async fn decodes_deflate() {
+        roundtrip(ContentEncoding::Deflate).await;
+        bounded_frames(ContentEncoding::Deflate).await;
+        truncated(ContentEncoding::Deflate).await;
+    }
+
+    #[cfg(feature = "brotli")]
+    #[tokio::test]
+    async fn decodes_brotli() {
+        roundtrip(ContentEncoding::Brotli).await;
+        bounded_frames(ContentEncoding::Brotli).await;
+        truncated(ContentEncoding::Brotli).await;
+    }
+
+    #[cfg(feature = "zstd")]
+    #[tokio::test]
+    async fn decodes_zstd() {
+        roundtrip(ContentEncoding::Zstd).await;
+        bounded_frames(ContentEncoding::Zstd).await;
+        truncated(ContentEncoding::Zstd).await;
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn rejects_data_after_end() {
+        let mut data = gzip(b"hello").to_vec();
+        data.extend_from_slice(b"trailing");
+        let body = Decompress::new(
+            Full::new(Bytes::from(data)),
+            &HeaderValue::from_static("gzip"),
+        );
+        let err = body.unwrap().collect().await.unwrap_err();
+        let err = err.downcast_ref::<io::Error>().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[tokio::test]
+    async fn zstd_dictionary() {
+        let dictionary = b"hello world, this is a preset dictionary";
+        let body = Full::new(Bytes::from("hello world"));
+        let body = Compress::with_zstd_dictionary(body, dictionary).unwrap();
+        let compressed = body.collect().await.unwrap().to_bytes();
+
+        let value = HeaderValue::from_static("zstd");
+        let body = Full::new(compressed.clone());
+        let body = Decompress::with_zstd_dictionary(body, &value, dictionary).unwrap();
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello world");
+
+        let body = Decompress::new(Full::new(compressed), &value).unwrap();
+        assert!(body.collect().await.is_err());
+    }
+
+    #[test]
+    fn unsupported_encoding() {
+        let body = Full::new(Bytes::from("hello"));
+        assert!(Decompress::new(body, &HeaderValue::from_static("compress")).is_err());
+    }
+}
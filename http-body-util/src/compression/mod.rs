@@ -0,0 +1,298 @@
+//! Bodies that compress or decompress their data.
+//!
+//! Each encoding is enabled by the feature of the same name: `gzip`, `deflate`, `brotli` and
+//! `zstd`.
+
+use std::io::{self, BufRead, Read, Write};
+
+use bytes::{Buf, Bytes, BytesMut};
+
+mod compress;
+mod decompress;
+
+pub use self::compress::Compress;
+pub use self::decompress::{Decompress, UnsupportedEncodingError};
+
+/// A content coding supported by [`Compress`] and [`Decompress`].
+///
+/// The variants match the tokens used in the `Content-Encoding` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ContentEncoding {
+    /// The gzip format.
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// The zlib format, which HTTP calls `deflate`.
+    #[cfg(feature = "deflate")]
+    Deflate,
+    /// The brotli format.
+    #[cfg(feature = "brotli")]
+    Brotli,
+    /// The zstd format.
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl ContentEncoding {
+    /// Returns the `Content-Encoding` token of this encoding.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "gzip")]
+            ContentEncoding::Gzip => "gzip",
+            #[cfg(feature = "deflate")]
+            ContentEncoding::Deflate => "deflate",
+            #[cfg(feature = "brotli")]
+            ContentEncoding::Brotli => "br",
+            #[cfg(feature = "zstd")]
+            ContentEncoding::Zstd => "zstd",
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            #[cfg(feature = "gzip")]
+            _ if token.eq_ignore_ascii_case("gzip") || token.eq_ignore_ascii_case("x-gzip") => {
+                Some(ContentEncoding::Gzip)
+            }
+            #[cfg(feature = "deflate")]
+            _ if token.eq_ignore_ascii_case("deflate") => Some(ContentEncoding::Deflate),
+            #[cfg(feature = "brotli")]
+            _ if token.eq_ignore_ascii_case("br") => Some(ContentEncoding::Brotli),
+            #[cfg(feature = "zstd")]
+            _ if token.eq_ignore_ascii_case("zstd") => Some(ContentEncoding::Zstd),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "brotli")]
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+/// An encoder writing its compressed output into a buffer.
+enum Encoder {
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<Vec<u8>>),
+    #[cfg(feature = "deflate")]
+    Deflate(flate2::write::ZlibEncoder<Vec<u8>>),
+    #[cfg(feature = "brotli")]
+    Brotli(Box<brotli::CompressorWriter<Vec<u8>>>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+}
+
+impl Encoder {
+    /// Creates an encoder, with a preset `dictionary` if the encoding supports one.
+    #[cfg_attr(not(feature = "zstd"), allow(unused_variables))]
+    fn new(encoding: ContentEncoding, dictionary: &[u8]) -> io::Result<Self> {
+        Ok(match encoding {
+            #[cfg(feature = "gzip")]
+            ContentEncoding::Gzip => Encoder::Gzip(flate2::write::GzEncoder::new(
+                Vec::new(),
+                Default::default(),
+            )),
+            #[cfg(feature = "deflate")]
+            ContentEncoding::Deflate => Encoder::Deflate(flate2::write::ZlibEncoder::new(
+                Vec::new(),
+                Default::default(),
+            )),
+            #[cfg(feature = "brotli")]
+            ContentEncoding::Brotli => Encoder::Brotli(Box::new(brotli::CompressorWriter::new(
+                Vec::new(),
+                BROTLI_BUFFER_SIZE,
+                5,
+                22,
+            ))),
+            #[cfg(feature = "zstd")]
+            ContentEncoding::Zstd => Encoder::Zstd(zstd::stream::write::Encoder::with_dictionary(
+                Vec::new(),
+                0,
+                dictionary,
+            )?),
+        })
+    }
+
+    fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            #[cfg(feature = "gzip")]
+            Encoder::Gzip(encoder) => encoder,
+            #[cfg(feature = "deflate")]
+            Encoder::Deflate(encoder) => encoder,
+            #[cfg(feature = "brotli")]
+            Encoder::Brotli(encoder) => encoder,
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(encoder) => encoder,
+        }
+    }
+
+    fn write(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.writer().write_all(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer().flush()
+    }
+
+    /// Takes the output written so far.
+    fn take(&mut self) -> Bytes {
+        let output = match self {
+            #[cfg(feature = "gzip")]
+            Encoder::Gzip(encoder) => encoder.get_mut(),
+            #[cfg(feature = "deflate")]
+            Encoder::Deflate(encoder) => encoder.get_mut(),
+            #[cfg(feature = "brotli")]
+            Encoder::Brotli(encoder) => encoder.get_mut(),
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(encoder) => encoder.get_mut(),
+        };
+        Bytes::from(std::mem::take(output))
+    }
+
+    /// Finishes the stream, returning the remaining output.
+    fn finish(self) -> io::Result<Bytes> {
+        let output = match self {
+            #[cfg(feature = "gzip")]
+            Encoder::Gzip(encoder) => encoder.finish()?,
+            #[cfg(feature = "deflate")]
+            Encoder::Deflate(encoder) => encoder.finish()?,
+            #[cfg(feature = "brotli")]
+            Encoder::Brotli(encoder) => encoder.into_inner(),
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(encoder) => encoder.finish()?,
+        };
+        Ok(Bytes::from(output))
+    }
+}
+
+/// Input for a [`Decoder`], provided as it arrives.
+///
+/// Reading from an empty `Input` fails with [`io::ErrorKind::WouldBlock`] until `eof` is set, so
+/// a decoder stops where the data runs out and resumes once more is pushed.
+#[derive(Default)]
+struct Input {
+    data: Bytes,
+    eof: bool,
+    // Whether a read saw the end of the input, to tell truncated brotli streams from corrupt ones.
+    read_eof: bool,
+}
+
+impl Input {
+    fn push(&mut self, data: Bytes) {
+        if self.data.is_empty() {
+            self.data = data;
+        } else {
+            let mut buf = BytesMut::from(&self.data[..]);
+            buf.extend_from_slice(&data);
+            self.data = buf.freeze();
+        }
+    }
+}
+
+impl Read for Input {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.fill_buf()?.len().min(buf.len());
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl BufRead for Input {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.data.is_empty() {
+            if !self.eof {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            self.read_eof = true;
+        }
+        Ok(&self.data)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.data.advance(amt);
+    }
+}
+
+/// A decoder reading its compressed data from an [`Input`].
+enum Decoder {
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::bufread::GzDecoder<Input>),
+    #[cfg(feature = "deflate")]
+    Deflate(flate2::bufread::ZlibDecoder<Input>),
+    #[cfg(feature = "brotli")]
+    Brotli(Box<brotli::Decompressor<Input>>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::read::Decoder<'static, Input>),
+}
+
+impl Decoder {
+    /// Creates a decoder, with a preset `dictionary` if the encoding supports one.
+    #[cfg_attr(not(feature = "zstd"), allow(unused_variables))]
+    fn new(encoding: ContentEncoding, dictionary: &[u8]) -> io::Result<Self> {
+        Ok(match encoding {
+            #[cfg(feature = "gzip")]
+            ContentEncoding::Gzip => {
+                Decoder::Gzip(flate2::bufread::GzDecoder::new(Input::default()))
+            }
+            #[cfg(feature = "deflate")]
+            ContentEncoding::Deflate => {
+                Decoder::Deflate(flate2::bufread::ZlibDecoder::new(Input::default()))
+            }
+            #[cfg(feature = "brotli")]
+            ContentEncoding::Brotli => Decoder::Brotli(Box::new(brotli::Decompressor::new(
+                Input::default(),
+                BROTLI_BUFFER_SIZE,
+            ))),
+            #[cfg(feature = "zstd")]
+            ContentEncoding::Zstd => Decoder::Zstd(zstd::stream::read::Decoder::with_dictionary(
+                Input::default(),
+                dictionary,
+            )?),
+        })
+    }
+
+    fn input(&mut self) -> &mut Input {
+        match self {
+            #[cfg(feature = "gzip")]
+            Decoder::Gzip(decoder) => decoder.get_mut(),
+            #[cfg(feature = "deflate")]
+            Decoder::Deflate(decoder) => decoder.get_mut(),
+            #[cfg(feature = "brotli")]
+            Decoder::Brotli(decoder) => decoder.get_mut(),
+            #[cfg(feature = "zstd")]
+            Decoder::Zstd(decoder) => decoder.get_mut(),
+        }
+    }
+
+    /// Reads decoded data into `buf`.
+    ///
+    /// Fails with [`io::ErrorKind::WouldBlock`] if more input is needed, and returns `Ok(0)` at
+    /// the end of the stream. A stream cut short fails with [`io::ErrorKind::UnexpectedEof`].
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = match self {
+            #[cfg(feature = "gzip")]
+            Decoder::Gzip(decoder) => decoder.read(buf),
+            #[cfg(feature = "deflate")]
+            Decoder::Deflate(decoder) => decoder.read(buf),
+            #[cfg(feature = "brotli")]
+            Decoder::Brotli(decoder) => {
+                decoder.get_mut().read_eof = false;
+                decoder.read(buf).map_err(|err| {
+                    if decoder.get_ref().read_eof {
+                        io::Error::new(io::ErrorKind::UnexpectedEof, "brotli stream is incomplete")
+                    } else {
+                        err
+                    }
+                })
+            }
+            #[cfg(feature = "zstd")]
+            Decoder::Zstd(decoder) => decoder.read(buf),
+        }?;
+        if read == 0 && !buf.is_empty() && !self.input().data.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unexpected data after the end of the stream",
+            ));
+        }
+        Ok(read)
+    }
+}
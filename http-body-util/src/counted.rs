@@ -0,0 +1,180 @@
+use bytes::Buf;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+pin_project! {
+    /// A body that counts the bytes and frames it yields.
+    ///
+    /// The counts are kept in a [`Counter`] which can be cloned and read after the body has been
+    /// moved elsewhere, such as into a server or client connection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Counted, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Counted::new(Full::new(Bytes::from("hello")));
+    /// let counter = body.counter();
+    ///
+    /// body.collect().await.unwrap();
+    /// assert_eq!(counter.bytes(), 5);
+    /// assert_eq!(counter.frames(), 1);
+    /// # }
+    /// ```
+    #[derive(Clone, Debug)]
+    pub struct Counted<B> {
+        counter: Counter,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> Counted<B> {
+    /// Create a new `Counted` with a fresh [`Counter`].
+    pub fn new(inner: B) -> Self {
+        Self::with_counter(inner, Counter::new())
+    }
+
+    /// Create a new `Counted` that adds to an existing [`Counter`].
+    ///
+    /// This allows several bodies, for example all bodies of one connection, to share a counter.
+    pub fn with_counter(inner: B, counter: Counter) -> Self {
+        Self { counter, inner }
+    }
+
+    /// Returns a handle to the counter of this body.
+    pub fn counter(&self) -> Counter {
+        self.counter.clone()
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for Counted<B>
+where
+    B: Body,
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let frame = this.inner.poll_frame(cx);
+
+        if let Poll::Ready(Some(Ok(frame))) = &frame {
+            this.counter
+                .record(frame.data_ref().map_or(0, Buf::remaining));
+        }
+
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// A shared handle to the counts of a [`Counted`] body.
+#[derive(Clone, Debug, Default)]
+pub struct Counter {
+    inner: Arc<Counts>,
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    bytes: AtomicU64,
+    frames: AtomicU64,
+}
+
+impl Counter {
+    /// Create a new `Counter` starting at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&self, bytes: usize) {
+        self.inner.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.inner.frames.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the number of data bytes yielded so far.
+    pub fn bytes(&self) -> u64 {
+        self.inner.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of frames yielded so far, including trailers.
+    pub fn frames(&self) -> u64 {
+        self.inner.frames.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use futures_util::stream;
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::{BodyExt, Full, StreamBody};
+
+    #[tokio::test]
+    async fn counts_data_and_trailers() {
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hel"))),
+            Ok(Frame::data(Bytes::from("lo"))),
+            Ok(Frame::trailers(HeaderMap::new())),
+        ];
+        let body = Counted::new(StreamBody::new(stream::iter(frames)));
+        let counter = body.counter();
+
+        body.collect().await.unwrap();
+        assert_eq!(counter.bytes(), 5);
+        assert_eq!(counter.frames(), 3);
+    }
+
+    #[tokio::test]
+    async fn shared_counter() {
+        let counter = Counter::new();
+        let a = Counted::with_counter(Full::new(Bytes::from("hello")), counter.clone());
+        let b = Counted::with_counter(Full::new(Bytes::from("world!")), counter.clone());
+
+        a.collect().await.unwrap();
+        b.collect().await.unwrap();
+        assert_eq!(counter.bytes(), 11);
+        assert_eq!(counter.frames(), 2);
+    }
+}
@@ -10,8 +10,34 @@ use proj::EitherProj;
 /// Sum type with two cases: [`Left`] and [`Right`], used if a body can be one of
 /// two distinct types.
 ///
+/// Both cases must have the same data type, which the body yields directly, so data does not
+/// need to be unwrapped from another `Either`. Bodies with different data types can be converted
+/// with [`BodyExt::map_frame`] first. Errors of both cases are boxed.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body_util::{BodyExt, Either, Empty, Full};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// fn body(empty: bool) -> Either<Empty<Bytes>, Full<Bytes>> {
+///     if empty {
+///         Either::Left(Empty::new())
+///     } else {
+///         Either::Right(Full::new(Bytes::from("hello")))
+///     }
+/// }
+///
+/// let data: Bytes = body(false).collect().await.unwrap().to_bytes();
+/// assert_eq!(data, "hello");
+/// # }
+/// ```
+///
 /// [`Left`]: Either::Left
 /// [`Right`]: Either::Right
+/// [`BodyExt::map_frame`]: crate::BodyExt::map_frame
 #[derive(Debug, Clone, Copy)]
 pub enum Either<L, R> {
     /// A value of type `L`
@@ -24,7 +50,7 @@ impl<L, R> Either<L, R> {
     /// This function is part of the generated code from `pin-project-lite`,
     /// for a more in depth explanation and the rest of the generated code refer
     /// to the [`proj`] module.
-    pub(crate) fn project(self: Pin<&mut Self>) -> EitherProj<L, R> {
+    pub(crate) fn project(self: Pin<&mut Self>) -> EitherProj<'_, L, R> {
         unsafe {
             match self.get_unchecked_mut() {
                 Self::Left(left) => EitherProj::Left(Pin::new_unchecked(left)),
@@ -85,6 +111,68 @@ where
     }
 }
 
+macro_rules! either_n {
+    ($(#[$attr:meta])* $name:ident, $count:literal, $($variant:ident),+) => {
+        $(#[$attr])*
+        #[doc = concat!("Sum type with ", $count, " cases, used if a body can be one of ", $count, " distinct types.")]
+        ///
+        /// Like [`Either`], all cases must have the same data type and their errors are boxed.
+        #[derive(Debug, Clone, Copy)]
+        pub enum $name<$($variant),+> {
+            $(
+                #[doc = concat!("A value of type `", stringify!($variant), "`")]
+                $variant($variant),
+            )+
+        }
+
+        impl<$($variant),+, Data> Body for $name<$($variant),+>
+        where
+            $(
+                $variant: Body<Data = Data>,
+                $variant::Error: Into<Box<dyn Error + Send + Sync>>,
+            )+
+            Data: Buf,
+        {
+            type Data = Data;
+            type Error = Box<dyn Error + Send + Sync>;
+
+            fn poll_frame(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+                // SAFETY: This is a structural pin projection. The enum does not implement `Drop`
+                // and is only `Unpin` if all its cases are, and the inner value is never moved.
+                match unsafe { self.get_unchecked_mut() } {
+                    $(
+                        Self::$variant(inner) => unsafe { Pin::new_unchecked(inner) }
+                            .poll_frame(cx)
+                            .map(|poll| poll.map(|opt| opt.map_err(Into::into))),
+                    )+
+                }
+            }
+
+            fn is_end_stream(&self) -> bool {
+                match self {
+                    $(Self::$variant(inner) => inner.is_end_stream(),)+
+                }
+            }
+
+            fn size_hint(&self) -> SizeHint {
+                match self {
+                    $(Self::$variant(inner) => inner.size_hint(),)+
+                }
+            }
+        }
+    };
+}
+
+either_n!(Either3, "three", A, B, C);
+either_n!(Either4, "four", A, B, C, D);
+either_n!(Either5, "five", A, B, C, D, E);
+either_n!(Either6, "six", A, B, C, D, E, F);
+either_n!(Either7, "seven", A, B, C, D, E, F, G);
+either_n!(Either8, "eight", A, B, C, D, E, F, G, H);
+
 pub(crate) mod proj {
     //! This code is the (cleaned output) generated by [pin-project-lite], as it
     //! does not support tuple variants.
@@ -138,6 +226,7 @@ pub(crate) mod proj {
         }
         impl<'__pin, L, R> Unpin for Either<L, R> where __Origin<'__pin, L, R>: Unpin {}
 
+        #[allow(dead_code)]
         trait MustNotImplDrop {}
         #[allow(drop_bounds)]
         impl<T: Drop> MustNotImplDrop for T {}
@@ -148,7 +237,7 @@ pub(crate) mod proj {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{BodyExt, Empty, Full};
+    use crate::{BodyExt, Empty, Full, Limited};
 
     #[tokio::test]
     async fn data_left() {
@@ -178,6 +267,37 @@ mod tests {
         assert!(value.frame().await.is_none());
     }
 
+    #[tokio::test]
+    async fn either3() {
+        type Body3 =
+            Either3<Full<&'static [u8]>, Empty<&'static [u8]>, Limited<Full<&'static [u8]>>>;
+        let a: Body3 = Either3::A(Full::new(&b"hello"[..]));
+        let b: Body3 = Either3::B(Empty::new());
+        let c: Body3 = Either3::C(Limited::new(Full::new(&b"hello"[..]), 2));
+
+        assert_eq!(a.size_hint().exact(), Some(5));
+        assert_eq!(a.collect().await.unwrap().to_bytes(), "hello");
+        assert!(b.is_end_stream());
+        assert!(b.collect().await.unwrap().to_bytes().is_empty());
+        assert!(c.collect().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn either8() {
+        type Body8 = Either8<
+            Empty<&'static [u8]>,
+            Empty<&'static [u8]>,
+            Empty<&'static [u8]>,
+            Empty<&'static [u8]>,
+            Empty<&'static [u8]>,
+            Empty<&'static [u8]>,
+            Empty<&'static [u8]>,
+            Full<&'static [u8]>,
+        >;
+        let body: Body8 = Either8::H(Full::new(&b"hello"[..]));
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    }
+
     #[test]
     fn into_inner() {
         let a = Either::<i32, i32>::Left(2);
@@ -0,0 +1,147 @@
+use std::error::Error;
+
+use bytes::Buf;
+use http_body::Body;
+
+use crate::{Chunks, Empty, Full, ReplayBody};
+
+/// A source of fresh bodies, one per attempt.
+///
+/// Retry and redirect logic needs to send the same request body more than once. Taking a
+/// `BodyFactory` instead of a body gives such middleware a shared contract for obtaining a new body
+/// for each attempt.
+///
+/// It is implemented for:
+///
+/// - closures returning a body,
+/// - bodies that are cheap to clone, such as [`Full`], [`Chunks`] and [`Empty`],
+/// - [`ReplayBody`], which buffers any body so it can be replayed, as a fallback for bodies that
+///   can't be recreated.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body_util::{BodyExt, BodyFactory, Full};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// async fn send_with_retry<F: BodyFactory>(factory: F, attempts: usize) {
+///     for _ in 0..attempts {
+///         let body = factory.make();
+///         // Send `body`, and return on success...
+/// #       let _ = body.collect().await;
+///     }
+/// }
+///
+/// send_with_retry(Full::new(Bytes::from("hello")), 3).await;
+/// send_with_retry(|| Full::new(Bytes::from("hello")), 3).await;
+/// # }
+/// ```
+pub trait BodyFactory {
+    /// The type of the bodies made by this factory.
+    type Body: Body;
+
+    /// Make a new body.
+    fn make(&self) -> Self::Body;
+}
+
+impl<F, B> BodyFactory for F
+where
+    F: Fn() -> B,
+    B: Body,
+{
+    type Body = B;
+
+    fn make(&self) -> Self::Body {
+        self()
+    }
+}
+
+impl<D: Buf + Clone> BodyFactory for Full<D> {
+    type Body = Self;
+
+    fn make(&self) -> Self::Body {
+        self.clone()
+    }
+}
+
+impl<D: Buf + Clone> BodyFactory for Chunks<D> {
+    type Body = Self;
+
+    fn make(&self) -> Self::Body {
+        self.clone()
+    }
+}
+
+impl<D: Buf> BodyFactory for Empty<D> {
+    type Body = Self;
+
+    fn make(&self) -> Self::Body {
+        *self
+    }
+}
+
+impl<B> BodyFactory for ReplayBody<B>
+where
+    B: Body,
+    B::Data: Clone,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Body = Self;
+
+    /// Make a new handle, replaying the body from the beginning.
+    fn make(&self) -> Self::Body {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use http_body::Frame;
+
+    use super::*;
+    use crate::{BodyExt, StreamBody};
+
+    async fn make_twice<F>(factory: F) -> (Bytes, Bytes)
+    where
+        F: BodyFactory,
+        <F::Body as Body>::Error: std::fmt::Debug,
+    {
+        let first = factory.make().collect().await.unwrap().to_bytes();
+        let second = factory.make().collect().await.unwrap().to_bytes();
+        (first, second)
+    }
+
+    #[tokio::test]
+    async fn makes_fresh_bodies() {
+        let hello = Bytes::from("hello");
+        let expected = (hello.clone(), hello.clone());
+
+        assert_eq!(make_twice(Full::new(hello.clone())).await, expected);
+        assert_eq!(
+            make_twice(Chunks::new(vec![Bytes::from("he"), Bytes::from("llo")])).await,
+            expected
+        );
+        assert_eq!(make_twice(|| Full::new(hello.clone())).await, expected);
+        assert_eq!(
+            make_twice(Empty::<Bytes>::new()).await,
+            (Bytes::new(), Bytes::new())
+        );
+    }
+
+    #[tokio::test]
+    async fn replays_one_shot_bodies() {
+        let frames = futures_util::stream::iter(vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("he"))),
+            Ok(Frame::data(Bytes::from("llo"))),
+        ]);
+        let factory = ReplayBody::new(StreamBody::new(frames), 1024);
+
+        let hello = Bytes::from("hello");
+        assert_eq!(make_twice(factory).await, (hello.clone(), hello));
+    }
+}
@@ -29,6 +29,31 @@ where
     }
 }
 
+impl Full<Bytes> {
+    /// Create a new `Full` from a static slice, without copying it.
+    ///
+    /// This is a `const fn`, so canned bodies can be declared as constants.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::Full;
+    ///
+    /// const NOT_FOUND: Full<Bytes> = Full::from_static(b"not found");
+    ///
+    /// let body = NOT_FOUND;
+    /// ```
+    pub const fn from_static(bytes: &'static [u8]) -> Self {
+        let data = if bytes.is_empty() {
+            None
+        } else {
+            Some(Bytes::from_static(bytes))
+        };
+        Full { data }
+    }
+}
+
 impl<D> Body for Full<D>
 where
     D: Buf,
@@ -144,4 +169,22 @@ mod tests {
         assert!(Full::<&[u8]>::default().frame().await.is_none());
         assert!(Full::new(&b""[..]).frame().await.is_none());
     }
+
+    #[tokio::test]
+    async fn from_static() {
+        const HELLO: Full<Bytes> = Full::from_static(b"hello");
+        const EMPTY: Full<Bytes> = Full::from_static(b"");
+
+        let mut full = HELLO;
+        assert_eq!(full.size_hint().exact(), Some(5));
+        assert_eq!(
+            full.frame().await.unwrap().unwrap().into_data().unwrap(),
+            "hello"
+        );
+        assert!(full.frame().await.is_none());
+
+        let mut empty = EMPTY;
+        assert!(empty.is_end_stream());
+        assert!(empty.frame().await.is_none());
+    }
 }
@@ -0,0 +1,175 @@
+use std::{
+    error::Error,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::ready;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A body produced by a future.
+    ///
+    /// The future is awaited on the first poll, then the resulting body is polled in its place. If
+    /// the future fails, its error is yielded and the body ends. Errors of the future and of the
+    /// body are boxed.
+    ///
+    /// This avoids boxing the body or going through a channel when the body comes from an
+    /// asynchronous lookup, such as a cache or an upstream request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full, FutureBody};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// async fn lookup() -> std::io::Result<Full<Bytes>> {
+    ///     Ok(Full::new(Bytes::from("cached")))
+    /// }
+    ///
+    /// let body = FutureBody::new(lookup());
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "cached");
+    /// # }
+    /// ```
+    pub struct FutureBody<F, B> {
+        #[pin]
+        state: State<F, B>,
+    }
+}
+
+pin_project! {
+    #[project = StateProj]
+    enum State<F, B> {
+        Future {
+            #[pin]
+            future: F,
+        },
+        Body {
+            #[pin]
+            body: B,
+        },
+        Done,
+    }
+}
+
+impl<F, B> FutureBody<F, B> {
+    /// Create a new `FutureBody`.
+    pub fn new(future: F) -> Self {
+        Self {
+            state: State::Future { future },
+        }
+    }
+
+    /// Returns a reference to the body, if the future has resolved to one.
+    pub fn body(&self) -> Option<&B> {
+        match &self.state {
+            State::Body { body } => Some(body),
+            _ => None,
+        }
+    }
+}
+
+impl<F, B, E> Body for FutureBody<F, B>
+where
+    F: Future<Output = Result<B, E>>,
+    E: Into<Box<dyn Error + Send + Sync>>,
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = B::Data;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.as_mut().project();
+        if let StateProj::Future { future } = this.state.as_mut().project() {
+            match ready!(future.poll(cx)) {
+                Ok(body) => this.state.set(State::Body { body }),
+                Err(err) => {
+                    this.state.set(State::Done);
+                    return Poll::Ready(Some(Err(err.into())));
+                }
+            }
+        }
+
+        match this.state.project() {
+            StateProj::Body { body } => body
+                .poll_frame(cx)
+                .map(|frame| frame.map(|frame| frame.map_err(Into::into))),
+            _ => Poll::Ready(None),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.state {
+            State::Future { .. } => false,
+            State::Body { body } => body.is_end_stream(),
+            State::Done => true,
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.state {
+            State::Future { .. } => SizeHint::default(),
+            State::Body { body } => body.size_hint(),
+            State::Done => SizeHint::with_exact(0),
+        }
+    }
+}
+
+impl<F, B: fmt::Debug> fmt::Debug for FutureBody<F, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("FutureBody");
+        match &self.state {
+            State::Future { .. } => debug.field("state", &"Future"),
+            State::Body { body } => debug.field("body", body),
+            State::Done => debug.field("state", &"Done"),
+        };
+        debug.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::{BodyExt, Full};
+
+    #[tokio::test]
+    async fn delegates_after_future() {
+        let future = async {
+            tokio::task::yield_now().await;
+            Ok::<_, Infallible>(Full::new(Bytes::from("hello")))
+        };
+        let mut body = FutureBody::new(Box::pin(future));
+        assert!(!body.is_end_stream());
+        assert!(body.body().is_none());
+
+        let frame = body.frame().await.unwrap().unwrap();
+        assert_eq!(frame.into_data().unwrap(), "hello");
+        assert!(body.is_end_stream());
+        assert!(body.frame().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn future_error() {
+        let future = async { Err::<Full<Bytes>, _>("lookup failed") };
+        let mut body = FutureBody::new(Box::pin(future));
+
+        let err = body.frame().await.unwrap().unwrap_err();
+        assert_eq!(err.to_string(), "lookup failed");
+        assert!(body.is_end_stream());
+        assert_eq!(body.size_hint().exact(), Some(0));
+        assert!(body.frame().await.is_none());
+    }
+}
@@ -0,0 +1,150 @@
+use std::{ascii, fmt};
+
+const ROW: usize = 16;
+
+/// A bounded preview of body data, for logs and diagnostics.
+///
+/// At most `limit` bytes are shown, however large the data is, and formatting never allocates.
+/// The [`Display`](fmt::Display) implementation renders a hex dump with an ASCII column, while
+/// [`Debug`](fmt::Debug) renders a single-line escaped byte string. Both note how many bytes were
+/// left out.
+///
+/// To preview a body while it is streamed, use [`BodyExt::preview`](crate::BodyExt::preview).
+///
+/// # Example
+///
+/// ```
+/// use http_body_util::HexPreview;
+///
+/// let preview = HexPreview::new(b"hello, world!\n", 8);
+/// assert_eq!(
+///     preview.to_string(),
+///     "00000000  68 65 6c 6c 6f 2c 20 77                           |hello, w|\n\
+///      ... 6 more bytes",
+/// );
+/// assert_eq!(format!("{:?}", preview), r#"b"hello, w"... 6 more bytes"#);
+/// ```
+#[derive(Clone, Copy)]
+pub struct HexPreview<'a> {
+    data: &'a [u8],
+    total: u64,
+}
+
+impl<'a> HexPreview<'a> {
+    /// Create a new `HexPreview` of the first `limit` bytes of `data`.
+    pub fn new(data: &'a [u8], limit: usize) -> Self {
+        Self::with_total(&data[..limit.min(data.len())], data.len() as u64)
+    }
+
+    /// Create a `HexPreview` of `data`, the first bytes of `total` bytes.
+    pub(crate) fn with_total(data: &'a [u8], total: u64) -> Self {
+        Self { data, total }
+    }
+
+    /// Returns the bytes shown by this preview.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the number of bytes left out of this preview.
+    pub fn omitted(&self) -> u64 {
+        self.total - self.data.len() as u64
+    }
+
+    fn fmt_omitted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.omitted() {
+            0 => Ok(()),
+            1 => write!(f, "... 1 more byte"),
+            omitted => write!(f, "... {} more bytes", omitted),
+        }
+    }
+}
+
+impl fmt::Display for HexPreview<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.data.chunks(ROW).enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{:08x} ", i * ROW)?;
+            for col in 0..ROW {
+                if col % 8 == 0 {
+                    f.write_str(" ")?;
+                }
+                match row.get(col) {
+                    Some(byte) => write!(f, "{:02x} ", byte)?,
+                    None => f.write_str("   ")?,
+                }
+            }
+            f.write_str(" |")?;
+            for &byte in row {
+                let c = if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+            f.write_str("|")?;
+        }
+        if !self.data.is_empty() && self.omitted() > 0 {
+            f.write_str("\n")?;
+        }
+        self.fmt_omitted(f)
+    }
+}
+
+impl fmt::Debug for HexPreview<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("b\"")?;
+        for &byte in self.data {
+            write!(f, "{}", ascii::escape_default(byte))?;
+        }
+        f.write_str("\"")?;
+        self.fmt_omitted(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_rows() {
+        let data: Vec<u8> = (0..20).map(|i| b'a' + i).collect();
+        let preview = HexPreview::new(&data, 64);
+        assert_eq!(
+            preview.to_string(),
+            "00000000  61 62 63 64 65 66 67 68  69 6a 6b 6c 6d 6e 6f 70  |abcdefghijklmnop|\n\
+             00000010  71 72 73 74                                       |qrst|"
+        );
+        assert_eq!(preview.omitted(), 0);
+    }
+
+    #[test]
+    fn display_non_printable() {
+        let preview = HexPreview::new(b"\x00\xff\t ~", 64);
+        assert_eq!(
+            preview.to_string(),
+            "00000000  00 ff 09 20 7e                                    |... ~|"
+        );
+    }
+
+    #[test]
+    fn truncates() {
+        let preview = HexPreview::new(b"abc", 2);
+        assert_eq!(preview.data(), b"ab");
+        assert_eq!(preview.omitted(), 1);
+        assert_eq!(format!("{:?}", preview), "b\"ab\"... 1 more byte");
+
+        let preview = HexPreview::new(b"abc", 0);
+        assert_eq!(preview.to_string(), "... 3 more bytes");
+        assert_eq!(format!("{:?}", HexPreview::new(b"", 4)), "b\"\"");
+    }
+
+    #[test]
+    fn debug_escapes() {
+        let preview = HexPreview::new(b"a\"\\\n\x7f", 64);
+        assert_eq!(format!("{:?}", preview), r#"b"a\"\\\n\x7f""#);
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use tokio::io::AsyncRead;
+
+use super::{poll_read_into, ReadChunks, DEFAULT_CHUNK_SIZE};
+
+pin_project! {
+    /// A body backed by an [`AsyncRead`].
+    ///
+    /// The reader is read in chunks until it reaches EOF. Since the length of the reader is not
+    /// known, the size hint is unknown unless it is declared with [`with_length`]; use
+    /// [`FileBody`](crate::FileBody) for files.
+    ///
+    /// [`with_length`]: AsyncReadBody::with_length
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http_body_util::{AsyncReadBody, BodyExt};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = AsyncReadBody::new(&b"hello world"[..]);
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello world");
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct AsyncReadBody<R> {
+        #[pin]
+        reader: R,
+        chunks: ReadChunks,
+    }
+}
+
+impl<R> AsyncReadBody<R> {
+    /// Create a new `AsyncReadBody`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            chunks: ReadChunks::new(DEFAULT_CHUNK_SIZE, None),
+        }
+    }
+
+    /// Create a new `AsyncReadBody` reading exactly `len` bytes.
+    ///
+    /// The size hint is exact, so a `Content-Length` can be sent. The body ends after `len` bytes
+    /// even if the reader has more data, and yields an [`io::ErrorKind::UnexpectedEof`] error if
+    /// the reader ends early.
+    pub fn with_length(reader: R, len: u64) -> Self {
+        Self {
+            reader,
+            chunks: ReadChunks::new(DEFAULT_CHUNK_SIZE, Some(len)),
+        }
+    }
+
+    /// Set the maximum size of the chunks read from the reader.
+    ///
+    /// Defaults to 8 KiB. Larger chunks mean fewer frames when streaming large sources.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn max_chunk_size(mut self, size: usize) -> Self {
+        self.chunks.set_max_chunk_size(size);
+        self
+    }
+
+    /// Get a reference to the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Get a mutable reference to the inner reader
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Get a pinned mutable reference to the inner reader
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.project().reader
+    }
+
+    /// Consume `self`, returning the inner reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> Body for AsyncReadBody<R>
+where
+    R: AsyncRead,
+{
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let mut reader = this.reader;
+        this.chunks
+            .poll_next(|buf, size| poll_read_into(reader.as_mut(), cx, buf, size))
+            .map(|chunk| chunk.map(|chunk| chunk.map(Frame::data)))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.chunks.is_done()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.chunks.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BodyExt;
+
+    #[tokio::test]
+    async fn reads_until_eof() {
+        let data = vec![7u8; DEFAULT_CHUNK_SIZE * 2 + 1];
+        let mut body = AsyncReadBody::new(&data[..]);
+
+        let mut frames = 0;
+        let mut read = Vec::new();
+        while let Some(frame) = body.frame().await {
+            read.extend_from_slice(&frame.unwrap().into_data().unwrap());
+            frames += 1;
+        }
+        assert_eq!(frames, 3);
+        assert_eq!(read, data);
+        assert!(body.is_end_stream());
+    }
+
+    #[tokio::test]
+    async fn max_chunk_size() {
+        let mut body = AsyncReadBody::new(&b"hello world"[..]).max_chunk_size(4);
+
+        let mut frames = Vec::new();
+        while let Some(frame) = body.frame().await {
+            frames.push(frame.unwrap().into_data().unwrap());
+        }
+        assert_eq!(frames, ["hell", "o wo", "rld"]);
+    }
+
+    #[tokio::test]
+    async fn with_length_stops_at_length() {
+        let mut body = AsyncReadBody::with_length(&b"hello world"[..], 5);
+        assert_eq!(body.size_hint().exact(), Some(5));
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "hello");
+        assert!(body.is_end_stream());
+        assert_eq!(body.size_hint().exact(), Some(0));
+        assert!(body.frame().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn with_length_short_read() {
+        let mut body = AsyncReadBody::with_length(&b"hello"[..], 8);
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "hello");
+        assert_eq!(body.size_hint().exact(), Some(3));
+
+        let err = body.frame().await.unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert!(body.frame().await.is_none());
+    }
+    #[tokio::test]
+    async fn reads_into_uninitialized_buffer() {
+        struct Reader(usize);
+
+        impl AsyncRead for Reader {
+            fn poll_read(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+                buf: &mut tokio::io::ReadBuf<'_>,
+            ) -> Poll<io::Result<()>> {
+                assert!(buf.initialized().is_empty());
+                assert_eq!(buf.remaining(), 4);
+                if self.0 > 0 {
+                    self.0 -= 1;
+                    buf.put_slice(b"abc");
+                }
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        let mut body = AsyncReadBody::new(Reader(2)).max_chunk_size(4);
+        let mut frames = Vec::new();
+        while let Some(frame) = body.frame().await {
+            frames.push(frame.unwrap().into_data().unwrap());
+        }
+        assert_eq!(frames, ["abc", "abc"]);
+    }
+}
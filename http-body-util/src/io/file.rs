@@ -0,0 +1,197 @@
+use std::{
+    io::{self, SeekFrom},
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use tokio::fs::File;
+use tokio::io::AsyncSeekExt;
+
+use super::{poll_read_into, ReadChunks};
+
+/// The size of the chunks read from a file.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A body that streams a file.
+///
+/// The length of the file is taken from its metadata when the body is created, so the size hint
+/// is exact and can be used for a `Content-Length`. Exactly that many bytes are yielded: if the
+/// file is shorter by the time it is read, the body returns an [`io::ErrorKind::UnexpectedEof`]
+/// error, and data appended after the body was created is not read.
+///
+/// # Example
+///
+/// ```no_run
+/// use http_body::Body as _;
+/// use http_body_util::FileBody;
+///
+/// # async fn run() -> std::io::Result<()> {
+/// let body = FileBody::open("index.html").await?;
+/// let content_length = body.size_hint().exact();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FileBody {
+    file: File,
+    chunks: ReadChunks,
+}
+
+impl FileBody {
+    /// Open the file at `path` and create a body streaming its contents.
+    pub async fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_file(File::open(path).await?).await
+    }
+
+    /// Create a body streaming the contents of `file`.
+    ///
+    /// The file is read from its current position up to the length reported by its metadata.
+    pub async fn from_file(mut file: File) -> io::Result<Self> {
+        let len = file.metadata().await?.len();
+        let position = file.stream_position().await?;
+        Ok(Self {
+            file,
+            chunks: ReadChunks::new(CHUNK_SIZE, Some(len.saturating_sub(position))),
+        })
+    }
+
+    /// Restrict the body to `len` bytes starting at `offset` from the start of the file.
+    ///
+    /// This is the building block for serving HTTP `Range` requests. The size hint of the
+    /// returned body is exactly `len`. Returns an [`io::ErrorKind::InvalidInput`] error if the
+    /// range extends past the end of the file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use http_body::Body as _;
+    /// use http_body_util::FileBody;
+    ///
+    /// # async fn run() -> std::io::Result<()> {
+    /// // Range: bytes=100-199
+    /// let body = FileBody::open("video.mp4").await?.range(100, 100).await?;
+    /// assert_eq!(body.size_hint().exact(), Some(100));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn range(mut self, offset: u64, len: u64) -> io::Result<Self> {
+        let file_len = self.file.metadata().await?.len();
+        if offset.checked_add(len).map_or(true, |end| end > file_len) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "range exceeds the length of the file",
+            ));
+        }
+
+        self.file.seek(SeekFrom::Start(offset)).await?;
+        self.chunks.reset(Some(len));
+        Ok(self)
+    }
+
+    /// Get a reference to the inner file
+    pub fn get_ref(&self) -> &File {
+        &self.file
+    }
+
+    /// Get a mutable reference to the inner file
+    pub fn get_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+
+    /// Consume `self`, returning the inner file
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+}
+
+impl Body for FileBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        let file = &mut this.file;
+        this.chunks
+            .poll_next(|buf, size| poll_read_into(Pin::new(file), cx, buf, size))
+            .map(|chunk| chunk.map(|chunk| chunk.map(Frame::data)))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.chunks.is_done()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.chunks.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::BodyExt;
+
+    #[tokio::test]
+    async fn streams_file_with_exact_size() {
+        let data = vec![3u8; CHUNK_SIZE + 10];
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&data).unwrap();
+
+        let body = FileBody::open(file.path()).await.unwrap();
+        assert_eq!(body.size_hint().exact(), Some(data.len() as u64));
+        assert_eq!(body.collect().await.unwrap().to_bytes(), data);
+    }
+
+    #[tokio::test]
+    async fn range() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"hello world").unwrap();
+
+        let body = FileBody::open(file.path()).await.unwrap();
+        let body = body.range(6, 3).await.unwrap();
+        assert_eq!(body.size_hint().exact(), Some(3));
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "wor");
+
+        let body = FileBody::open(file.path()).await.unwrap();
+        let body = body.range(0, 0).await.unwrap();
+        assert!(body.is_end_stream());
+
+        let body = FileBody::open(file.path()).await.unwrap();
+        let err = body.range(6, 6).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn from_current_position() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"hello world").unwrap();
+
+        let mut file = File::open(file.path()).await.unwrap();
+        file.seek(SeekFrom::Start(6)).await.unwrap();
+        let body = FileBody::from_file(file).await.unwrap();
+        assert_eq!(body.size_hint().exact(), Some(5));
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "world");
+    }
+
+    #[tokio::test]
+    async fn truncated_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"hello world").unwrap();
+
+        let mut body = FileBody::open(file.path()).await.unwrap();
+        file.as_file().set_len(5).unwrap();
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "hello");
+        let err = body.frame().await.unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert!(body.frame().await.is_none());
+    }
+}
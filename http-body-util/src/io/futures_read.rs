@@ -0,0 +1,135 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_io::AsyncRead;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+use super::{ReadChunks, DEFAULT_CHUNK_SIZE};
+
+pin_project! {
+    /// A body backed by a [`futures_io::AsyncRead`].
+    ///
+    /// This is the equivalent of [`AsyncReadBody`](crate::AsyncReadBody) for readers from the
+    /// `futures` ecosystem, such as those of smol and async-std. The reader is read in chunks until
+    /// it reaches EOF, and the size hint is unknown.
+    #[derive(Debug)]
+    pub struct FuturesAsyncReadBody<R> {
+        #[pin]
+        reader: R,
+        chunks: ReadChunks,
+    }
+}
+
+impl<R> FuturesAsyncReadBody<R> {
+    /// Create a new `FuturesAsyncReadBody`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            chunks: ReadChunks::new(DEFAULT_CHUNK_SIZE, None),
+        }
+    }
+
+    /// Create a new `FuturesAsyncReadBody` reading exactly `len` bytes.
+    ///
+    /// The size hint is exact, so a `Content-Length` can be sent. The body ends after `len` bytes
+    /// even if the reader has more data, and yields an [`io::ErrorKind::UnexpectedEof`] error if
+    /// the reader ends early.
+    pub fn with_length(reader: R, len: u64) -> Self {
+        Self {
+            reader,
+            chunks: ReadChunks::new(DEFAULT_CHUNK_SIZE, Some(len)),
+        }
+    }
+
+    /// Set the maximum size of the chunks read from the reader.
+    ///
+    /// Defaults to 8 KiB. Larger chunks mean fewer frames when streaming large sources.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn max_chunk_size(mut self, size: usize) -> Self {
+        self.chunks.set_max_chunk_size(size);
+        self
+    }
+
+    /// Get a reference to the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Get a mutable reference to the inner reader
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Get a pinned mutable reference to the inner reader
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.project().reader
+    }
+
+    /// Consume `self`, returning the inner reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> Body for FuturesAsyncReadBody<R>
+where
+    R: AsyncRead,
+{
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let mut reader = this.reader;
+        this.chunks
+            .poll_next(|buf, size| {
+                // `futures_io::AsyncRead` takes an initialized buffer.
+                buf.resize(size, 0);
+                reader.as_mut().poll_read(cx, buf)
+            })
+            .map(|chunk| chunk.map(|chunk| chunk.map(Frame::data)))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.chunks.is_done()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.chunks.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::io::Cursor;
+
+    use super::*;
+    use crate::BodyExt;
+
+    #[tokio::test]
+    async fn reads_until_eof() {
+        let data = vec![7u8; DEFAULT_CHUNK_SIZE + 1];
+        let mut body = FuturesAsyncReadBody::new(Cursor::new(data.clone()));
+
+        let mut frames = 0;
+        let mut read = Vec::new();
+        while let Some(frame) = body.frame().await {
+            read.extend_from_slice(&frame.unwrap().into_data().unwrap());
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+        assert_eq!(read, data);
+        assert!(body.is_end_stream());
+    }
+}
@@ -0,0 +1,164 @@
+//! Bodies backed by I/O sources.
+
+use std::{io, task::Poll};
+
+use bytes::{Bytes, BytesMut};
+use futures_core::ready;
+use http_body::SizeHint;
+
+#[cfg(feature = "io")]
+mod async_read;
+#[cfg(feature = "fs")]
+mod file;
+#[cfg(feature = "futures-io")]
+mod futures_read;
+#[cfg(feature = "io")]
+mod reader;
+#[cfg(feature = "io")]
+mod seekable;
+#[cfg(feature = "spool")]
+mod spool;
+#[cfg(feature = "io")]
+mod write_sink;
+
+#[cfg(feature = "io")]
+pub use self::async_read::AsyncReadBody;
+#[cfg(feature = "fs")]
+pub use self::file::FileBody;
+#[cfg(feature = "futures-io")]
+pub use self::futures_read::FuturesAsyncReadBody;
+#[cfg(feature = "io")]
+pub use self::reader::BodyReader;
+#[cfg(feature = "io")]
+pub use self::seekable::SeekableBody;
+#[cfg(feature = "spool")]
+pub use self::spool::{Spooled, SpooledBody};
+#[cfg(feature = "io")]
+pub use self::write_sink::AsyncWriteSink;
+
+/// The default size of the chunks read from a source.
+const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
+/// The chunking shared by the bodies reading from an `AsyncRead`.
+///
+/// Chunks are read into a `BytesMut` and split off, so the yielded `Bytes` share its allocation
+/// instead of being copied.
+#[derive(Debug)]
+struct ReadChunks {
+    buf: BytesMut,
+    max_chunk_size: usize,
+    /// The number of bytes left to read, if the length is known.
+    remaining: Option<u64>,
+    done: bool,
+}
+
+impl ReadChunks {
+    fn new(max_chunk_size: usize, len: Option<u64>) -> Self {
+        Self {
+            buf: BytesMut::new(),
+            max_chunk_size,
+            remaining: len,
+            done: len == Some(0),
+        }
+    }
+
+    fn set_max_chunk_size(&mut self, max_chunk_size: usize) {
+        assert!(max_chunk_size > 0, "max chunk size must be at least 1");
+        self.max_chunk_size = max_chunk_size;
+    }
+
+    /// Starts reading again after the source was seeked, with a new length.
+    #[cfg(feature = "io")]
+    fn reset(&mut self, len: Option<u64>) {
+        self.remaining = len;
+        self.done = len == Some(0);
+    }
+
+    /// Reads the next chunk with `read`, returning `None` at EOF.
+    ///
+    /// `read` is given the empty buffer and the chunk size, and appends at most that many bytes.
+    /// If the length is known, reading stops after that many bytes and an EOF before that is an
+    /// error.
+    fn poll_next<F>(&mut self, read: F) -> Poll<Option<io::Result<Bytes>>>
+    where
+        F: FnOnce(&mut BytesMut, usize) -> Poll<io::Result<usize>>,
+    {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        let size = match self.remaining {
+            Some(remaining) => remaining.min(self.max_chunk_size as u64) as usize,
+            None => self.max_chunk_size,
+        };
+        self.buf.clear();
+        match ready!(read(&mut self.buf, size)) {
+            Ok(0) => {
+                self.done = true;
+                self.buf = BytesMut::new();
+                match self.remaining {
+                    Some(remaining) => Poll::Ready(Some(Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        format!("reader ended {remaining} bytes early"),
+                    )))),
+                    None => Poll::Ready(None),
+                }
+            }
+            Ok(len) => {
+                if let Some(remaining) = &mut self.remaining {
+                    *remaining -= len as u64;
+                    self.done = *remaining == 0;
+                }
+                self.buf.truncate(len);
+                Poll::Ready(Some(Ok(self.buf.split().freeze())))
+            }
+            Err(err) => Poll::Ready(Some(Err(err))),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.done
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.done {
+            return SizeHint::with_exact(0);
+        }
+        match self.remaining {
+            Some(remaining) => SizeHint::with_exact(remaining),
+            None => SizeHint::default(),
+        }
+    }
+}
+
+/// Reads up to `size` bytes from a tokio `reader` into the spare capacity of `buf`, returning the
+/// number of bytes read.
+///
+/// The spare capacity is handed to the reader uninitialized, so it isn't zeroed on each read.
+#[cfg(feature = "io")]
+fn poll_read_into<R>(
+    reader: std::pin::Pin<&mut R>,
+    cx: &mut std::task::Context<'_>,
+    buf: &mut BytesMut,
+    size: usize,
+) -> Poll<io::Result<usize>>
+where
+    R: tokio::io::AsyncRead + ?Sized,
+{
+    use bytes::BufMut;
+
+    buf.reserve(size);
+    let dst = &mut buf.chunk_mut()[..size];
+    // SAFETY: `UninitSlice` is a transparent wrapper around `[MaybeUninit<u8>]`, and `ReadBuf`
+    // never de-initializes bytes.
+    let dst = unsafe { &mut *(dst as *mut _ as *mut [std::mem::MaybeUninit<u8>]) };
+    let mut read_buf = tokio::io::ReadBuf::uninit(dst);
+    let ptr = read_buf.filled().as_ptr();
+    ready!(reader.poll_read(cx, &mut read_buf))?;
+    // The reader must not swap the buffer out.
+    assert_eq!(ptr, read_buf.filled().as_ptr());
+    let len = read_buf.filled().len();
+    // SAFETY: `ReadBuf` guarantees the filled bytes are initialized.
+    unsafe { buf.advance_mut(len) };
+    Poll::Ready(Ok(len))
+}
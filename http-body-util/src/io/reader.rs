@@ -0,0 +1,197 @@
+use std::{
+    error::Error,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http_body::Body;
+use pin_project_lite::pin_project;
+use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
+
+use crate::util::into_io_error;
+
+pin_project! {
+    /// An [`AsyncRead`] reading the data of a body.
+    ///
+    /// Frames are pulled from the body as the reader is read. Trailers are skipped, and errors
+    /// from the body are returned as [`io::Error`]s.
+    ///
+    /// It also implements [`AsyncBufRead`], whose buffer is the unread part of the current data
+    /// frame, so line based parsing does not copy the data into another buffer.
+    ///
+    /// See [`BodyExt::into_async_read`] for more details.
+    ///
+    /// [`BodyExt::into_async_read`]: crate::BodyExt::into_async_read
+    #[derive(Debug)]
+    pub struct BodyReader<B>
+    where
+        B: Body,
+    {
+        #[pin]
+        body: B,
+        chunk: Option<B::Data>,
+    }
+}
+
+impl<B: Body> BodyReader<B> {
+    /// Create a new `BodyReader`.
+    pub fn new(body: B) -> Self {
+        Self { body, chunk: None }
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.body
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.body
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().body
+    }
+
+    /// Consume `self`, returning the inner body
+    ///
+    /// Any data of the current frame that has not been read yet is lost.
+    pub fn into_inner(self) -> B {
+        self.body
+    }
+}
+
+impl<B> AsyncRead for BodyReader<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let chunk = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let len = chunk.len().min(buf.remaining());
+        buf.put_slice(&chunk[..len]);
+        self.consume(len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<B> AsyncBufRead for BodyReader<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let mut this = self.project();
+
+        loop {
+            if this.chunk.as_ref().map_or(false, Buf::has_remaining) {
+                break;
+            }
+
+            match ready!(this.body.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => *this.chunk = frame.into_data().ok(),
+                Some(Err(err)) => return Poll::Ready(Err(into_io_error(err.into()))),
+                None => {
+                    *this.chunk = None;
+                    return Poll::Ready(Ok(&[]));
+                }
+            }
+        }
+
+        Poll::Ready(Ok(this.chunk.as_ref().unwrap().chunk()))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        if let Some(chunk) = self.project().chunk {
+            chunk.advance(amt);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use futures_util::stream;
+    use http_body::Frame;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+    use crate::{BodyExt, StreamBody};
+
+    #[tokio::test]
+    async fn reads_across_frames() {
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hello "))),
+            Ok(Frame::data(Bytes::new())),
+            Ok(Frame::trailers(Default::default())),
+            Ok(Frame::data(Bytes::from("world"))),
+        ];
+        let mut reader = StreamBody::new(stream::iter(frames)).into_async_read();
+
+        let mut buf = [0; 4];
+        assert_eq!(reader.read(&mut buf).await.unwrap(), 4);
+        assert_eq!(&buf, b"hell");
+
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).await.unwrap();
+        assert_eq!(rest, "o world");
+    }
+
+    #[tokio::test]
+    async fn buf_read_lines() {
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("first\nsec"))),
+            Ok(Frame::data(Bytes::from("ond\nthird"))),
+        ];
+        let reader = StreamBody::new(stream::iter(frames)).into_async_read();
+
+        let mut lines = reader.lines();
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), "first");
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), "second");
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), "third");
+        assert!(lines.next_line().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn fill_buf_exposes_frame() {
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hello"))),
+            Ok(Frame::data(Bytes::from("world"))),
+        ];
+        let mut reader = StreamBody::new(stream::iter(frames)).into_async_read();
+
+        assert_eq!(reader.fill_buf().await.unwrap(), b"hello");
+        reader.consume(2);
+        assert_eq!(reader.fill_buf().await.unwrap(), b"llo");
+        reader.consume(3);
+        assert_eq!(reader.fill_buf().await.unwrap(), b"world");
+        reader.consume(5);
+        assert_eq!(reader.fill_buf().await.unwrap(), b"");
+    }
+
+    #[tokio::test]
+    async fn propagates_errors() {
+        let frames = vec![
+            Ok(Frame::data(Bytes::from("hello"))),
+            Err(std::io::Error::new(
+                std::io::ErrorKind::ConnectionReset,
+                "reset",
+            )),
+        ];
+        let mut reader = StreamBody::new(stream::iter(frames)).into_async_read();
+
+        let mut out = Vec::new();
+        let err = reader.read_to_end(&mut out).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::ConnectionReset);
+        assert_eq!(out, b"hello");
+    }
+}
@@ -0,0 +1,243 @@
+use std::{
+    io::{self, SeekFrom},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_core::ready;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt};
+
+use super::{poll_read_into, ReadChunks, DEFAULT_CHUNK_SIZE};
+
+pin_project! {
+    /// A body backed by an [`AsyncRead`] + [`AsyncSeek`] source that can be rewound.
+    ///
+    /// Unlike [`AsyncReadBody`](crate::AsyncReadBody), the position of the source can be changed
+    /// between reads. Retry and redirect logic can [`rewind`] the body to send it again without
+    /// buffering it, and [`range`] serves part of a file-like source with an exact size hint.
+    ///
+    /// [`rewind`]: SeekableBody::rewind
+    /// [`range`]: SeekableBody::range
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use http_body_util::{BodyExt, SeekableBody};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let mut body = SeekableBody::new(Cursor::new(b"hello world".to_vec()));
+    /// assert_eq!((&mut body).collect().await?.to_bytes(), "hello world");
+    ///
+    /// body.rewind().await?;
+    /// assert_eq!((&mut body).collect().await?.to_bytes(), "hello world");
+    ///
+    /// body.range(6, 5).await?;
+    /// assert_eq!(body.collect().await?.to_bytes(), "world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct SeekableBody<R> {
+        #[pin]
+        reader: R,
+        chunks: ReadChunks,
+        range: Option<(u64, u64)>,
+        // The position streaming started from, once known, and the bytes read since.
+        start: Option<u64>,
+        read: u64,
+    }
+}
+
+impl<R> SeekableBody<R> {
+    /// Create a new `SeekableBody` reading from the current position of `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            chunks: ReadChunks::new(DEFAULT_CHUNK_SIZE, None),
+            range: None,
+            start: None,
+            read: 0,
+        }
+    }
+
+    /// Set the maximum size of the chunks read from the reader.
+    ///
+    /// Defaults to 8 KiB.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn max_chunk_size(mut self, size: usize) -> Self {
+        self.chunks.set_max_chunk_size(size);
+        self
+    }
+
+    /// Get a reference to the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Get a mutable reference to the inner reader
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Get a pinned mutable reference to the inner reader
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.project().reader
+    }
+
+    /// Consume `self`, returning the inner reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> SeekableBody<R>
+where
+    R: AsyncSeek + Unpin,
+{
+    /// Seek the source to `pos` and stream from there until EOF.
+    ///
+    /// This clears a range set with [`range`](SeekableBody::range). Returns the new position from
+    /// the start of the source.
+    pub async fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = self.reader.seek(pos).await?;
+        self.range = None;
+        self.start = Some(pos);
+        self.read = 0;
+        self.chunks.reset(None);
+        Ok(pos)
+    }
+
+    /// Restrict the body to `len` bytes starting at `offset` from the start of the source.
+    ///
+    /// The size hint is exactly `len`, and an [`io::ErrorKind::UnexpectedEof`] error is yielded if
+    /// the source ends before the range does.
+    pub async fn range(&mut self, offset: u64, len: u64) -> io::Result<()> {
+        self.reader.seek(SeekFrom::Start(offset)).await?;
+        self.range = Some((offset, len));
+        self.start = Some(offset);
+        self.read = 0;
+        self.chunks.reset(Some(len));
+        Ok(())
+    }
+
+    /// Restart the body from where it started streaming, or from the start of the range if one
+    /// is set.
+    ///
+    /// The body starts at the position of the reader when it was created, or at the position
+    /// given to the last [`seek`](SeekableBody::seek).
+    pub async fn rewind(&mut self) -> io::Result<()> {
+        if let Some((offset, len)) = self.range {
+            return self.range(offset, len).await;
+        }
+
+        let start = match self.start {
+            Some(start) => start,
+            None => {
+                let pos = self.reader.stream_position().await?;
+                pos.checked_sub(self.read).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::Other, "reader moved before the body")
+                })?
+            }
+        };
+        self.seek(SeekFrom::Start(start)).await.map(drop)
+    }
+}
+
+impl<R> Body for SeekableBody<R>
+where
+    R: AsyncRead,
+{
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let mut reader = this.reader;
+        let chunk = ready!(this.chunks.poll_next(|buf, size| poll_read_into(
+            reader.as_mut(),
+            cx,
+            buf,
+            size
+        )));
+        if let Some(Ok(chunk)) = &chunk {
+            *this.read += chunk.len() as u64;
+        }
+        Poll::Ready(chunk.map(|chunk| chunk.map(Frame::data)))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.chunks.is_done()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.chunks.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::BodyExt;
+
+    #[tokio::test]
+    async fn rewind_after_partial_read() {
+        let mut body = SeekableBody::new(Cursor::new(b"hello world".to_vec())).max_chunk_size(4);
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "hell");
+
+        body.rewind().await.unwrap();
+        assert_eq!(
+            (&mut body).collect().await.unwrap().to_bytes(),
+            "hello world"
+        );
+        assert!(body.is_end_stream());
+    }
+
+    #[tokio::test]
+    async fn rewind_range() {
+        let mut body = SeekableBody::new(Cursor::new(b"hello world".to_vec()));
+
+        body.range(2, 3).await.unwrap();
+        assert_eq!(body.size_hint().exact(), Some(3));
+        assert_eq!((&mut body).collect().await.unwrap().to_bytes(), "llo");
+
+        body.rewind().await.unwrap();
+        assert_eq!(body.size_hint().exact(), Some(3));
+        assert_eq!((&mut body).collect().await.unwrap().to_bytes(), "llo");
+
+        body.seek(SeekFrom::End(-5)).await.unwrap();
+        assert_eq!(body.size_hint().exact(), None);
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "world");
+    }
+
+    #[tokio::test]
+    async fn rewind_to_starting_position() {
+        let mut reader = Cursor::new(b"hello world".to_vec());
+        reader.set_position(6);
+        let mut body = SeekableBody::new(reader).max_chunk_size(2);
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "wo");
+
+        body.rewind().await.unwrap();
+        assert_eq!((&mut body).collect().await.unwrap().to_bytes(), "world");
+
+        body.rewind().await.unwrap();
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "world");
+    }
+}
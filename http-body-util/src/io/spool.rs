@@ -0,0 +1,258 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, Bytes};
+use futures_core::ready;
+use http::HeaderMap;
+use http_body::{Body, Frame, SizeHint};
+use tempfile::NamedTempFile;
+use tokio::io::AsyncWriteExt;
+
+use super::FileBody;
+use crate::BodyExt;
+
+/// A body buffered in memory up to a limit, with the remainder spooled to a temporary file.
+///
+/// Once spooled, the body can be streamed any number of times with [`body`], for example to
+/// retry a large upload. The temporary file is deleted when the `Spooled` and all bodies
+/// streaming from it are dropped.
+///
+/// [`body`]: Spooled::body
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body_util::{BodyExt, Full, Spooled};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let spooled = Spooled::new(Full::new(Bytes::from("hello world")), 5).await.unwrap();
+/// assert!(spooled.is_on_disk());
+///
+/// for _ in 0..2 {
+///     let body = spooled.body().await.unwrap();
+///     assert_eq!(body.collect().await.unwrap().to_bytes(), "hello world");
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Spooled {
+    memory: Vec<Bytes>,
+    file: Option<Arc<NamedTempFile>>,
+    len: u64,
+    trailers: Option<HeaderMap>,
+}
+
+impl Spooled {
+    /// Read `body` to the end, keeping up to `memory_limit` bytes in memory.
+    ///
+    /// Data past the limit is written to a temporary file.
+    pub async fn new<B>(body: B, memory_limit: usize) -> Result<Self, Box<dyn Error + Send + Sync>>
+    where
+        B: Body,
+        B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let mut body = Box::pin(body);
+
+        let mut memory = Vec::new();
+        let mut memory_len = 0;
+        let mut file: Option<(NamedTempFile, tokio::fs::File)> = None;
+        let mut len = 0;
+        let mut trailers = None;
+
+        while let Some(frame) = body.frame().await {
+            let data = match frame.map_err(Into::into)?.into_data() {
+                Ok(mut data) => data.copy_to_bytes(data.remaining()),
+                Err(frame) => {
+                    if let Ok(frame_trailers) = frame.into_trailers() {
+                        trailers = Some(frame_trailers);
+                    }
+                    continue;
+                }
+            };
+            len += data.len() as u64;
+
+            if file.is_none() && memory_len + data.len() <= memory_limit {
+                memory_len += data.len();
+                memory.push(data);
+                continue;
+            }
+
+            let (_, writer) = match &mut file {
+                Some(file) => file,
+                None => {
+                    let temp = NamedTempFile::new()?;
+                    let writer = tokio::fs::File::from_std(temp.reopen()?);
+                    file.insert((temp, writer))
+                }
+            };
+            writer.write_all(&data).await?;
+        }
+
+        let file = match file {
+            Some((temp, mut writer)) => {
+                writer.flush().await?;
+                Some(Arc::new(temp))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            memory,
+            file,
+            len,
+            trailers,
+        })
+    }
+
+    /// Returns the number of data bytes in the body.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns whether the body has no data.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether part of the body was spooled to a temporary file.
+    pub fn is_on_disk(&self) -> bool {
+        self.file.is_some()
+    }
+
+    /// Returns a body streaming the spooled data from the beginning.
+    pub async fn body(&self) -> io::Result<SpooledBody> {
+        let file = match &self.file {
+            Some(temp) => Some(FileBody::open(temp.path()).await?),
+            None => None,
+        };
+
+        Ok(SpooledBody {
+            memory: self.memory.iter().cloned().collect(),
+            file,
+            trailers: self.trailers.clone(),
+            _temp: self.file.clone(),
+        })
+    }
+}
+
+/// A body streaming the data of a [`Spooled`].
+#[derive(Debug)]
+pub struct SpooledBody {
+    memory: VecDeque<Bytes>,
+    file: Option<FileBody>,
+    trailers: Option<HeaderMap>,
+    // Keeps the temporary file from being deleted while it is read.
+    _temp: Option<Arc<NamedTempFile>>,
+}
+
+impl Body for SpooledBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+
+        if let Some(data) = this.memory.pop_front() {
+            return Poll::Ready(Some(Ok(Frame::data(data))));
+        }
+
+        if let Some(file) = &mut this.file {
+            match ready!(Pin::new(file).poll_frame(cx)) {
+                Some(frame) => return Poll::Ready(Some(frame)),
+                None => this.file = None,
+            }
+        }
+
+        Poll::Ready(
+            this.trailers
+                .take()
+                .map(|trailers| Ok(Frame::trailers(trailers))),
+        )
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.memory.is_empty()
+            && self.file.as_ref().map_or(true, Body::is_end_stream)
+            && self.trailers.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let memory = self
+            .memory
+            .iter()
+            .map(|data| data.len() as u64)
+            .sum::<u64>();
+        let file = self
+            .file
+            .as_ref()
+            .map_or(0, |file| file.size_hint().lower());
+        SizeHint::with_exact(memory + file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use futures_util::stream;
+
+    use super::*;
+    use crate::{Full, StreamBody};
+
+    #[tokio::test]
+    async fn small_body_stays_in_memory() {
+        let spooled = Spooled::new(Full::new(Bytes::from("hello")), 5)
+            .await
+            .unwrap();
+        assert!(!spooled.is_on_disk());
+        assert_eq!(spooled.len(), 5);
+
+        let body = spooled.body().await.unwrap();
+        assert_eq!(body.size_hint().exact(), Some(5));
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    }
+
+    #[tokio::test]
+    async fn large_body_spills_to_disk() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("foo", "bar".parse().unwrap());
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hello "))),
+            Ok(Frame::data(Bytes::from("big "))),
+            Ok(Frame::data(Bytes::from("world"))),
+            Ok(Frame::trailers(trailers)),
+        ];
+        let spooled = Spooled::new(StreamBody::new(stream::iter(frames)), 8)
+            .await
+            .unwrap();
+        assert!(spooled.is_on_disk());
+        assert_eq!(spooled.len(), 15);
+
+        for _ in 0..2 {
+            let body = spooled.body().await.unwrap();
+            assert_eq!(body.size_hint().exact(), Some(15));
+
+            let collected = body.collect().await.unwrap();
+            assert_eq!(collected.trailers().unwrap()["foo"], "bar");
+            assert_eq!(collected.to_bytes(), "hello big world");
+        }
+
+        let path = spooled.file.as_ref().unwrap().path().to_owned();
+        let body = spooled.body().await.unwrap();
+        drop(spooled);
+        assert!(path.exists());
+        drop(body);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,217 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http::HeaderMap;
+use http_body::Frame;
+use pin_project_lite::pin_project;
+use tokio::io::AsyncWrite;
+
+use crate::BodySink;
+
+pin_project! {
+    /// A [`BodySink`] writing the data of the frames it receives into an [`AsyncWrite`].
+    ///
+    /// Trailers cannot be written to a byte stream, so they are kept and can be retrieved with
+    /// [`trailers`](AsyncWriteSink::trailers). Closing the sink shuts the writer down.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use futures_util::future::poll_fn;
+    /// use http_body::Frame;
+    /// use http_body_util::{AsyncWriteSink, BodySink};
+    /// use std::pin::Pin;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let mut sink = AsyncWriteSink::new(Vec::new());
+    ///
+    /// poll_fn(|cx| Pin::new(&mut sink).poll_ready(cx)).await?;
+    /// Pin::new(&mut sink).send_frame(Frame::data(Bytes::from("hello")))?;
+    /// poll_fn(|cx| Pin::new(&mut sink).poll_flush(cx)).await?;
+    ///
+    /// assert_eq!(sink.get_ref(), b"hello");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct AsyncWriteSink<W, D> {
+        #[pin]
+        writer: W,
+        chunk: Option<D>,
+        trailers: Option<HeaderMap>,
+    }
+}
+
+impl<W, D> AsyncWriteSink<W, D> {
+    /// Create a new `AsyncWriteSink`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            chunk: None,
+            trailers: None,
+        }
+    }
+
+    /// Returns the trailers received by the sink, if any.
+    pub fn trailers(&self) -> Option<&HeaderMap> {
+        self.trailers.as_ref()
+    }
+
+    /// Get a reference to the inner writer
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get a mutable reference to the inner writer
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Get a pinned mutable reference to the inner writer
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        self.project().writer
+    }
+
+    /// Consume `self`, returning the inner writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: AsyncWrite, D: Buf> AsyncWriteSink<W, D> {
+    /// Writes out the pending chunk.
+    fn poll_write_chunk(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut this = self.project();
+        if let Some(chunk) = this.chunk {
+            while chunk.has_remaining() {
+                let n = ready!(this.writer.as_mut().poll_write(cx, chunk.chunk()))?;
+                if n == 0 {
+                    return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+                }
+                chunk.advance(n);
+            }
+            *this.chunk = None;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W, D> BodySink<D> for AsyncWriteSink<W, D>
+where
+    W: AsyncWrite,
+    D: Buf,
+{
+    type Error = io::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_write_chunk(cx)
+    }
+
+    fn send_frame(self: Pin<&mut Self>, frame: Frame<D>) -> Result<(), Self::Error> {
+        let this = self.project();
+        match frame.into_data() {
+            Ok(data) => {
+                if this.chunk.is_some() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "frame sent before the sink was ready",
+                    ));
+                }
+                *this.chunk = Some(data);
+            }
+            Err(frame) => {
+                if let Ok(trailers) = frame.into_trailers() {
+                    *this.trailers = Some(trailers);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.as_mut().poll_write_chunk(cx))?;
+        self.project().writer.poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.as_mut().poll_write_chunk(cx))?;
+        self.project().writer.poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+
+    use futures_util::future::poll_fn;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use bytes::Bytes;
+
+    use super::*;
+
+    async fn send<S: BodySink<Bytes> + Unpin>(
+        sink: &mut S,
+        frame: Frame<Bytes>,
+    ) -> Result<(), S::Error> {
+        poll_fn(|cx| Pin::new(&mut *sink).poll_ready(cx)).await?;
+        Pin::new(&mut *sink).send_frame(frame)
+    }
+
+    #[tokio::test]
+    async fn writes_data_and_keeps_trailers() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("foo", "bar".parse().unwrap());
+
+        let mut sink = AsyncWriteSink::new(Vec::new());
+        send(&mut sink, Frame::data(Bytes::from("hello ")))
+            .await
+            .unwrap();
+        send(&mut sink, Frame::data(Bytes::from("world")))
+            .await
+            .unwrap();
+        send(&mut sink, Frame::trailers(trailers.clone()))
+            .await
+            .unwrap();
+        poll_fn(|cx| Pin::new(&mut sink).poll_close(cx))
+            .await
+            .unwrap();
+
+        assert_eq!(sink.get_ref(), b"hello world");
+        assert_eq!(sink.trailers(), Some(&trailers));
+    }
+
+    #[tokio::test]
+    async fn waits_for_partial_writes() {
+        let (mut client, server) = tokio::io::duplex(4);
+        let mut sink = AsyncWriteSink::new(server);
+
+        send(&mut sink, Frame::data(Bytes::from("hello world")))
+            .await
+            .unwrap();
+
+        // The pipe holds 4 bytes, so the chunk is not written out until it is read.
+        let ready = poll_fn(|cx| Pin::new(&mut sink).poll_ready(cx));
+        futures_util::pin_mut!(ready);
+        assert!(poll_fn(|cx| Poll::Ready(ready.as_mut().poll(cx).is_pending())).await);
+
+        let reader = tokio::spawn(async move {
+            let mut out = Vec::new();
+            client.read_to_end(&mut out).await.unwrap();
+            out
+        });
+        ready.await.unwrap();
+        let mut server = sink.into_inner();
+        server.shutdown().await.unwrap();
+        drop(server);
+
+        assert_eq!(reader.await.unwrap(), b"hello world");
+    }
+}
@@ -7,30 +7,130 @@
 //!
 //! [`Empty`] and [`Full`] provide simple implementations.
 
+#[macro_use]
+mod trace;
+
+mod allowed_trailers;
+#[cfg(feature = "base64")]
+mod base64;
+pub mod blocking;
+mod broadcast;
+mod buf_list;
+mod chunked;
+mod chunks;
 mod collected;
 pub mod combinators;
+#[cfg(any(
+    feature = "brotli",
+    feature = "deflate",
+    feature = "gzip",
+    feature = "zstd"
+))]
+pub mod compression;
+mod counted;
 mod either;
 mod empty;
+mod factory;
 mod full;
+mod future_body;
+mod hex_preview;
+#[cfg(any(feature = "io", feature = "futures-io"))]
+mod io;
 mod limited;
+mod limited_trailers;
+mod max_frames;
+mod message;
+mod multipart;
+mod mux;
+#[cfg(feature = "json")]
+mod ndjson;
+mod once;
+mod prebuffered;
+mod random;
+mod repeat;
+mod replay;
+mod sanitize_trailers;
+mod sink;
+mod split;
 mod stream;
+#[cfg(feature = "test-util")]
+pub mod test;
+#[cfg(test)]
+mod test_support;
+mod throttle;
+mod timed;
+mod timeout;
+pub mod timer;
+#[cfg(feature = "encoding_rs")]
+mod transcode;
 
 #[cfg(feature = "channel")]
 pub mod channel;
 
 mod util;
 
-use self::combinators::{BoxBody, MapErr, MapFrame, UnsyncBoxBody};
+use bytes::Buf;
+
+use self::combinators::{
+    BoxBody, BoxCloneBody, LocalBoxBody, MapErr, MapFrame, OnEos, OnIncomplete, Preview, Progress,
+    UnsyncBoxBody,
+};
 
+pub use self::allowed_trailers::{AllowedTrailers, UndeclaredTrailerError};
+#[cfg(feature = "base64")]
+pub use self::base64::{Base64Decode, Base64Encode};
+pub use self::broadcast::{BroadcastBody, BroadcastError};
+pub use self::buf_list::BufList;
+pub use self::chunked::{ChunkedDecodeError, ChunkedDecoder};
+pub use self::chunks::Chunks;
 pub use self::collected::Collected;
-pub use self::either::Either;
+pub use self::counted::{Counted, Counter};
+pub use self::either::{Either, Either3, Either4, Either5, Either6, Either7, Either8};
 pub use self::empty::Empty;
+pub use self::factory::BodyFactory;
 pub use self::full::Full;
+pub use self::future_body::FutureBody;
+pub use self::hex_preview::HexPreview;
+#[cfg(feature = "fs")]
+pub use self::io::FileBody;
+#[cfg(feature = "futures-io")]
+pub use self::io::FuturesAsyncReadBody;
+#[cfg(feature = "io")]
+pub use self::io::{AsyncReadBody, AsyncWriteSink, BodyReader, SeekableBody};
+#[cfg(feature = "spool")]
+pub use self::io::{Spooled, SpooledBody};
 pub use self::limited::{LengthLimitError, Limited};
-pub use self::stream::{BodyDataStream, BodyStream, StreamBody};
+pub use self::limited_trailers::{LimitedTrailers, TrailersLimitError};
+pub use self::max_frames::{FrameLimitError, MaxFrames};
+pub use self::message::{RequestExt, ResponseExt};
+pub use self::multipart::MultipartBody;
+pub use self::mux::{Mux, Tagged};
+#[cfg(feature = "json")]
+pub use self::ndjson::{ItemTooLargeError, NdjsonBody};
+pub use self::once::Once;
+pub use self::prebuffered::{Fill, Prebuffered};
+pub use self::random::RandomBody;
+pub use self::repeat::Repeat;
+pub use self::replay::{ReplayBody, ReplayError};
+pub use self::sanitize_trailers::SanitizeTrailers;
+pub use self::sink::BodySink;
+pub use self::split::{NextPart, Split, SplitPart};
+pub use self::stream::{BodyDataStream, BodyDataStreamWithTrailers, BodyStream, StreamBody};
+pub use self::throttle::Throttle;
+pub use self::timed::{Timed, Timing};
+pub use self::timeout::{FirstFrameTimeout, IdleTimeout, TimeoutError};
+#[cfg(feature = "encoding_rs")]
+pub use self::transcode::Transcode;
 
 #[cfg(feature = "channel")]
 pub use self::channel::Channel;
+#[cfg(any(
+    feature = "brotli",
+    feature = "deflate",
+    feature = "gzip",
+    feature = "zstd"
+))]
+pub use self::compression::{Compress, Decompress};
 
 /// An extension trait for [`http_body::Body`] adding various combinators and adapters
 pub trait BodyExt: http_body::Body {
@@ -63,6 +163,304 @@ pub trait BodyExt: http_body::Body {
         MapErr::new(self, f)
     }
 
+    /// Converts this body's error into `E` with [`Into`].
+    ///
+    /// This is a shorthand for `.map_err(Into::into)` that names the error type directly, for
+    /// example `.err_into::<BoxError>()`, instead of annotating the resulting body.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full};
+    /// use std::error::Error;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Full::new(Bytes::from("hello")).err_into::<Box<dyn Error + Send + Sync>>();
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    /// # }
+    /// ```
+    fn err_into<E>(self) -> combinators::ErrInto<Self, E>
+    where
+        Self: Sized,
+        Self::Error: Into<E>,
+    {
+        combinators::ErrInto::new(self)
+    }
+
+    /// Calls `f` after each data frame with the number of bytes yielded so far and a hint for
+    /// the total size of the body.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Full::new(Bytes::from("hello")).progress(|bytes, total| {
+    ///     if let Some(total) = total.exact() {
+    ///         println!("{bytes}/{total} bytes");
+    ///     }
+    /// });
+    /// # body.collect().await.unwrap();
+    /// # }
+    /// ```
+    fn progress<F>(self, f: F) -> Progress<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(u64, &http_body::SizeHint),
+    {
+        Progress::new(self, f)
+    }
+
+    /// Alternates the frames of this body with the frames of `other`, starting with this body.
+    ///
+    /// By default, once one of the bodies ends, the frames of the other are yielded until it ends
+    /// too. Use [`Interleave::on_end`] to end as soon as either body ends instead. Trailers of
+    /// both bodies are merged and yielded at the end.
+    ///
+    /// [`Interleave::on_end`]: combinators::Interleave::on_end
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Full::new(Bytes::from("data")).interleave(Full::new(Bytes::from("meta")));
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "datameta");
+    /// # }
+    /// ```
+    fn interleave<B>(self, other: B) -> combinators::Interleave<Self, B>
+    where
+        Self: Sized,
+        B: http_body::Body<Data = Self::Data, Error = Self::Error>,
+    {
+        combinators::Interleave::new(self, other)
+    }
+
+    /// Keeps a copy of the first `limit` bytes of data yielded by this body, for diagnostics.
+    ///
+    /// The copy never grows beyond `limit` bytes, and [`Preview::hex`] renders it as a
+    /// [`HexPreview`], for example to log the start of a body that failed to parse.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut body = Full::new(Bytes::from("{\"id\": 1, oops}")).preview(8);
+    /// while let Some(frame) = body.frame().await {
+    ///     frame.unwrap();
+    /// }
+    /// assert_eq!(format!("{:?}", body.hex()), r#"b"{\"id\": 1"... 7 more bytes"#);
+    /// # }
+    /// ```
+    fn preview(self, limit: usize) -> Preview<Self>
+    where
+        Self: Sized,
+    {
+        Preview::new(self, limit)
+    }
+
+    /// Calls `f` exactly once when this body successfully reaches its end.
+    ///
+    /// The callback receives [`EndOfStream`] statistics, such as the total number of bytes. It is
+    /// not called if the body errors or is dropped before the end.
+    ///
+    /// [`EndOfStream`]: combinators::EndOfStream
+    fn on_eos<F>(self, f: F) -> OnEos<Self, F>
+    where
+        Self: Sized,
+        F: FnOnce(combinators::EndOfStream),
+    {
+        OnEos::new(self, f)
+    }
+
+    /// Calls `f` if this body is dropped before reaching its end.
+    ///
+    /// This indicates that the body was not fully consumed, for example because the peer
+    /// disconnected, the request was cancelled, or the body returned an error.
+    fn on_incomplete<F>(self, f: F) -> OnIncomplete<Self, F>
+    where
+        Self: Sized,
+        F: FnOnce(),
+    {
+        OnIncomplete::new(self, f)
+    }
+
+    /// Hashes the data of this body and appends the digest as a trailer named `name`.
+    ///
+    /// The digest is hex encoded unless another encoding is set with
+    /// [`Digest::encoding`](combinators::Digest::encoding). If the body ends with trailers of its
+    /// own, the digest is added to them; otherwise a trailers frame is emitted after the last data
+    /// frame.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http::HeaderName;
+    /// use http_body_util::{BodyExt, Full};
+    /// use sha2::{Digest, Sha256};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let name = HeaderName::from_static("x-checksum-sha256");
+    /// let body = Full::new(Bytes::from("hello")).digest(Sha256::new(), name);
+    ///
+    /// let collected = body.collect().await.unwrap();
+    /// assert!(collected.trailers().unwrap().contains_key("x-checksum-sha256"));
+    /// # }
+    /// ```
+    #[cfg(feature = "digest")]
+    fn digest<H>(self, hasher: H, name: http::HeaderName) -> combinators::Digest<Self, H>
+    where
+        Self: Sized,
+        H: digest::Digest,
+    {
+        combinators::Digest::new(self, hasher, name)
+    }
+
+    /// Hashes the data of this body and verifies it against an expected digest at the end.
+    ///
+    /// The expected digest can be a known value, such as from a `Content-MD5` header, or a
+    /// trailer sent with the body. If the digests don't match, the body yields a
+    /// [`DigestError`](combinators::DigestError) instead of ending successfully.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http::HeaderName;
+    /// use http_body_util::combinators::{DigestEncoding, ExpectedDigest};
+    /// use http_body_util::{BodyExt, Full};
+    /// use sha2::{Digest, Sha256};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let name = HeaderName::from_static("x-checksum-sha256");
+    /// let body = Full::new(Bytes::from("hello")).digest(Sha256::new(), name.clone());
+    ///
+    /// let expected = ExpectedDigest::trailer(name, DigestEncoding::Hex);
+    /// let verified = body.verify_digest(Sha256::new(), expected);
+    /// assert!(verified.collect().await.is_ok());
+    /// # }
+    /// ```
+    #[cfg(feature = "digest")]
+    fn verify_digest<H>(
+        self,
+        hasher: H,
+        expected: combinators::ExpectedDigest,
+    ) -> combinators::VerifyDigest<Self, H>
+    where
+        Self: Sized,
+        H: digest::Digest,
+    {
+        combinators::VerifyDigest::new(self, hasher, expected)
+    }
+
+    /// Collects the body and deserializes it from JSON into `T`.
+    ///
+    /// The returned future can be configured with a [`limit`] on the size of the body and, with
+    /// the `cbor` feature, to deserialize CBOR instead. All failures are reported as a single
+    /// [`DeserializeError`](combinators::DeserializeError).
+    ///
+    /// [`limit`]: combinators::Deserialize::limit
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Full::new(Bytes::from(r#"{"name":"ferris"}"#));
+    /// let user = body.deserialize::<User>().limit(1024).await.unwrap();
+    /// assert_eq!(user.name, "ferris");
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    fn deserialize<T>(self) -> combinators::Deserialize<Self, T>
+    where
+        Self: Sized,
+        T: serde::de::DeserializeOwned,
+    {
+        combinators::Deserialize::new(self)
+    }
+
+    /// Writes the data of this body into `writer`, without buffering the whole body.
+    ///
+    /// The returned future resolves to the number of bytes written and the trailers of the body,
+    /// if any. The writer is flushed once the body ends.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut out = Vec::new();
+    /// let body = Full::new(Bytes::from("hello"));
+    ///
+    /// let (written, _trailers) = body.write_to(&mut out).await.unwrap();
+    /// assert_eq!(written, 5);
+    /// # }
+    /// ```
+    #[cfg(feature = "io")]
+    fn write_to<W>(self, writer: W) -> combinators::WriteTo<Self, W>
+    where
+        Self: Sized,
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        combinators::WriteTo::new(self, writer)
+    }
+
+    /// Turn this body into an [`AsyncRead`](tokio::io::AsyncRead) reading its data.
+    ///
+    /// This allows passing a body to `tokio::io::copy`, decompressors and parsers that read from
+    /// an `AsyncRead`. Trailers are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full};
+    /// use tokio::io::AsyncReadExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut reader = Full::new(Bytes::from("hello")).into_async_read();
+    ///
+    /// let mut data = String::new();
+    /// reader.read_to_string(&mut data).await.unwrap();
+    /// assert_eq!(data, "hello");
+    /// # }
+    /// ```
+    #[cfg(feature = "io")]
+    fn into_async_read(self) -> BodyReader<Self>
+    where
+        Self: Sized,
+    {
+        BodyReader::new(self)
+    }
+
     /// Turn this body into a boxed trait object.
     fn boxed(self) -> BoxBody<Self::Data, Self::Error>
     where
@@ -71,6 +469,61 @@ pub trait BodyExt: http_body::Body {
         BoxBody::new(self)
     }
 
+    /// Turn this body into a boxed trait object with its error converted into a boxed error.
+    ///
+    /// This is a shorthand for `.map_err(Into::into).boxed()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::error::Error;
+    ///
+    /// use bytes::Bytes;
+    /// use http_body_util::{combinators::BoxBody, BodyExt, Full, Limited};
+    ///
+    /// let body: BoxBody<Bytes, Box<dyn Error + Send + Sync>> =
+    ///     Full::new(Bytes::from("hello")).boxed_err();
+    /// let limited: BoxBody<Bytes, Box<dyn Error + Send + Sync>> =
+    ///     Limited::new(Full::new(Bytes::from("hello")), 1024).boxed_err();
+    /// ```
+    fn boxed_err(self) -> BoxBody<Self::Data, Box<dyn std::error::Error + Send + Sync>>
+    where
+        Self: Sized + Send + Sync + 'static,
+        Self::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.map_err(Into::into).boxed()
+    }
+
+    /// Turn this body into a boxed trait object with its data converted to [`Bytes`].
+    ///
+    /// Data that already is `Bytes` is not copied.
+    ///
+    /// [`Bytes`]: bytes::Bytes
+    fn boxed_bytes(self) -> combinators::BytesBoxBody<Self::Error>
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        // `Bytes::copy_to_bytes` returns a slice of itself instead of copying.
+        self.map_frame(|frame| frame.map_data(|mut data| data.copy_to_bytes(data.remaining())))
+            .boxed()
+    }
+
+    /// Turn this body into a boxed trait object that is !Send.
+    fn boxed_local(self) -> LocalBoxBody<Self::Data, Self::Error>
+    where
+        Self: Sized + 'static,
+    {
+        LocalBoxBody::new(self)
+    }
+
+    /// Turn this body into a boxed trait object that can be cloned.
+    fn boxed_clone(self) -> BoxCloneBody<Self::Data, Self::Error>
+    where
+        Self: Clone + Sized + Send + Sync + 'static,
+    {
+        BoxCloneBody::new(self)
+    }
+
     /// Turn this body into a boxed trait object that is !Sync.
     fn boxed_unsync(self) -> UnsyncBoxBody<Self::Data, Self::Error>
     where
@@ -91,6 +544,132 @@ pub trait BodyExt: http_body::Body {
         }
     }
 
+    /// Collect the body like [`collect`](BodyExt::collect), failing once it contains more than
+    /// `limit` bytes of data.
+    ///
+    /// Unlike combining [`Limited`] with `collect`, the error keeps the data collected so far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Full::new(Bytes::from("hello world"));
+    /// let err = body.collect_with_limit(5).await.unwrap_err();
+    ///
+    /// assert!(err.is_length_limit());
+    /// assert_eq!(err.into_collected().to_bytes(), "hello world");
+    /// # }
+    /// ```
+    fn collect_with_limit(self, limit: usize) -> combinators::CollectWithLimit<Self>
+    where
+        Self: Sized,
+    {
+        combinators::CollectWithLimit::new(self, limit)
+    }
+
+    /// Collect the data of this body into a `String`.
+    ///
+    /// Trailers are discarded. The error distinguishes body errors from invalid UTF-8, and the
+    /// returned future can be given a byte limit with [`CollectString::limit`].
+    ///
+    /// [`CollectString::limit`]: combinators::CollectString::limit
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Full::new(Bytes::from("hello"));
+    /// assert_eq!(body.collect_string().limit(1024).await.unwrap(), "hello");
+    /// # }
+    /// ```
+    fn collect_string(self) -> combinators::CollectString<Self>
+    where
+        Self: Sized,
+    {
+        combinators::CollectString::new(self)
+    }
+
+    /// Collect all the frames of this body, in order.
+    ///
+    /// Unlike [`collect`](BodyExt::collect), this keeps the frame boundaries, which is useful in
+    /// tests and for recording bodies.
+    fn collect_frames(self) -> combinators::CollectFrames<Self>
+    where
+        Self: Sized,
+    {
+        combinators::CollectFrames::new(self)
+    }
+
+    /// Drive the body to completion, discarding its frames.
+    ///
+    /// Resolves to the number of data bytes discarded. Unlike [`collect`](BodyExt::collect),
+    /// nothing is buffered, so this is the cheap way to consume an unread request body before
+    /// reusing a connection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Full::new(Bytes::from("unread"));
+    /// assert_eq!(body.drain().await.unwrap(), 6);
+    /// # }
+    /// ```
+    fn drain(self) -> combinators::Drain<Self>
+    where
+        Self: Sized,
+    {
+        combinators::Drain::new(self)
+    }
+
+    /// Drive the body to completion, resolving to its trailers.
+    ///
+    /// Data frames are discarded, or rejected with [`CollectTrailers::deny_data`]. Resolves to
+    /// `None` if the body has no trailers.
+    ///
+    /// This is the `trailers()` future of `http-body` 0.4. It is named like [`collect`] so it is
+    /// not mistaken for the `trailers` accessors of bodies that keep their trailers.
+    ///
+    /// [`CollectTrailers::deny_data`]: combinators::CollectTrailers::deny_data
+    /// [`collect`]: BodyExt::collect
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http::HeaderMap;
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut trailers = HeaderMap::new();
+    /// trailers.insert("grpc-status", "0".parse().unwrap());
+    ///
+    /// let body = Full::new(Bytes::from("hello"))
+    ///     .with_trailers(async move { Some(Ok(trailers)) });
+    ///
+    /// let trailers = body.collect_trailers().await.unwrap().unwrap();
+    /// assert_eq!(trailers["grpc-status"], "0");
+    /// # }
+    /// ```
+    fn collect_trailers(self) -> combinators::CollectTrailers<Self>
+    where
+        Self: Sized,
+    {
+        combinators::CollectTrailers::new(self)
+    }
+
     /// Add trailers to the body.
     ///
     /// The trailers will be sent when all previous frames have been sent and the `trailers` future
@@ -135,6 +714,72 @@ pub trait BodyExt: http_body::Body {
         combinators::WithTrailers::new(self, trailers)
     }
 
+    /// Merge data frames smaller than `threshold` bytes into larger frames.
+    ///
+    /// Small frames are buffered until `threshold` bytes are buffered, a larger frame or trailers
+    /// arrive, the body ends, or `max_delay` elapses since the first buffered frame. This results
+    /// in fewer, larger writes when the wrapped body yields many tiny frames.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Full::new(Bytes::from("hello")).coalesce(16 * 1024, Duration::from_millis(5));
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    /// # }
+    /// ```
+    #[cfg(feature = "time")]
+    fn coalesce(
+        self,
+        threshold: usize,
+        max_delay: std::time::Duration,
+    ) -> combinators::Coalesce<Self>
+    where
+        Self: Sized,
+    {
+        combinators::Coalesce::new(self, threshold, max_delay)
+    }
+
+    /// Fuse this body, so it keeps returning `None` once it has ended instead of polling the
+    /// wrapped body again.
+    ///
+    /// This protects against bodies that misbehave when polled after their end. To also stop after
+    /// the first error, see [`Fuse::fuse_on_error`](combinators::Fuse::fuse_on_error).
+    fn fuse(self) -> combinators::Fuse<Self>
+    where
+        Self: Sized,
+    {
+        combinators::Fuse::new(self)
+    }
+
+    /// Turn this body into a [`Peekable`](combinators::Peekable) body, which can look at the
+    /// next frame without consuming it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Empty};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut body = Empty::<Bytes>::new().peekable();
+    /// assert!(body.peek().await.is_none(), "body is empty");
+    /// # }
+    /// ```
+    fn peekable(self) -> combinators::Peekable<Self>
+    where
+        Self: Sized,
+    {
+        combinators::Peekable::new(self)
+    }
+
     /// Turn this body into [`BodyDataStream`].
     fn into_data_stream(self) -> BodyDataStream<Self>
     where
@@ -11,9 +11,15 @@ pin_project! {
     ///
     /// This body will return an error if more than the configured number
     /// of bytes are returned on polling the wrapped body.
+    ///
+    /// By default only data frames count toward the limit. See
+    /// [`count_trailers`](Limited::count_trailers) to also bound trailers, or
+    /// [`LimitedTrailers`](crate::LimitedTrailers) to bound them separately.
     #[derive(Clone, Copy, Debug)]
     pub struct Limited<B> {
+        limit: usize,
         remaining: usize,
+        count_trailers: bool,
         #[pin]
         inner: B,
     }
@@ -23,10 +29,49 @@ impl<B> Limited<B> {
     /// Create a new `Limited`.
     pub fn new(inner: B, limit: usize) -> Self {
         Self {
+            limit,
             remaining: limit,
+            count_trailers: false,
             inner,
         }
     }
+
+    /// Also count trailers toward the limit.
+    ///
+    /// Each trailer counts as its serialized size in HTTP/1.1 chunked encoding: the length of the
+    /// name and value plus four bytes for the `": "` separator and the line ending. This prevents
+    /// unbounded header data from being smuggled past the limit in trailers.
+    pub fn count_trailers(mut self, enabled: bool) -> Self {
+        self.count_trailers = enabled;
+        self
+    }
+
+    /// Returns the number of bytes that can still be read before the limit is exceeded.
+    ///
+    /// This is zero once the limit has been exceeded.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
 }
 
 impl<B> Body for Limited<B>
@@ -42,23 +87,66 @@ where
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
         let this = self.project();
+        let count_trailers = *this.count_trailers;
         let res = match this.inner.poll_frame(cx) {
             Poll::Pending => return Poll::Pending,
             Poll::Ready(None) => None,
             Poll::Ready(Some(Ok(frame))) => {
                 if let Some(data) = frame.data_ref() {
                     if data.remaining() > *this.remaining {
+                        debug!(
+                            bytes = data.remaining(),
+                            remaining = *this.remaining,
+                            "limited: length limit exceeded"
+                        );
+                        let observed = *this.limit - *this.remaining;
                         *this.remaining = 0;
-                        Some(Err(LengthLimitError.into()))
+                        Some(Err(LengthLimitError::new(
+                            *this.limit,
+                            observed.saturating_add(data.remaining()),
+                        )
+                        .into()))
                     } else {
                         *this.remaining -= data.remaining();
+                        trace!(
+                            bytes = data.remaining(),
+                            remaining = *this.remaining,
+                            "limited: data frame"
+                        );
+                        Some(Ok(frame))
+                    }
+                } else if let Some(size) = frame.trailers_len().filter(|_| count_trailers) {
+                    if size > *this.remaining {
+                        debug!(
+                            bytes = size,
+                            remaining = *this.remaining,
+                            "limited: length limit exceeded by trailers"
+                        );
+                        let observed = *this.limit - *this.remaining;
+                        *this.remaining = 0;
+                        Some(Err(LengthLimitError::new(
+                            *this.limit,
+                            observed.saturating_add(size),
+                        )
+                        .into()))
+                    } else {
+                        *this.remaining -= size;
+                        trace!(
+                            bytes = size,
+                            remaining = *this.remaining,
+                            "limited: trailers frame"
+                        );
                         Some(Ok(frame))
                     }
                 } else {
+                    trace!("limited: non-data frame");
                     Some(Ok(frame))
                 }
             }
-            Poll::Ready(Some(Err(err))) => Some(Err(err.into())),
+            Poll::Ready(Some(Err(err))) => {
+                debug!("limited: body error");
+                Some(Err(err.into()))
+            }
         };
 
         Poll::Ready(res)
@@ -89,12 +177,37 @@ where
 
 /// An error returned when body length exceeds the configured limit.
 #[derive(Debug)]
-#[non_exhaustive]
-pub struct LengthLimitError;
+pub struct LengthLimitError {
+    limit: usize,
+    observed: usize,
+}
+
+impl LengthLimitError {
+    fn new(limit: usize, observed: usize) -> Self {
+        Self { limit, observed }
+    }
+
+    /// Returns the configured limit, in bytes.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the number of bytes seen when the limit was exceeded.
+    ///
+    /// This includes the frame that exceeded the limit, so it is always greater than
+    /// [`limit`](LengthLimitError::limit).
+    pub fn observed(&self) -> usize {
+        self.observed
+    }
+}
 
 impl fmt::Display for LengthLimitError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str("length limit exceeded")
+        write!(
+            f,
+            "length limit of {} bytes exceeded ({} bytes)",
+            self.limit, self.observed
+        )
     }
 }
 
@@ -103,6 +216,7 @@ impl Error for LengthLimitError {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{test_body, TestBody};
     use crate::{BodyExt, Full, StreamBody};
     use bytes::Bytes;
     use std::convert::Infallible;
@@ -136,7 +250,10 @@ mod tests {
         assert_eq!(body.size_hint().upper(), hint.upper());
 
         let error = body.frame().await.unwrap().unwrap_err();
-        assert!(matches!(error.downcast_ref(), Some(LengthLimitError)));
+        assert!(matches!(
+            error.downcast_ref(),
+            Some(LengthLimitError { .. })
+        ));
     }
 
     fn body_from_iter<I>(into_iter: I) -> impl Body<Data = Bytes, Error = Infallible>
@@ -170,7 +287,13 @@ mod tests {
         assert_eq!(body.size_hint().upper(), hint.upper());
 
         let error = body.frame().await.unwrap().unwrap_err();
-        assert!(matches!(error.downcast_ref(), Some(LengthLimitError)));
+        let error = error.downcast_ref::<LengthLimitError>().unwrap();
+        assert_eq!(error.limit(), 8);
+        assert_eq!(error.observed(), 8 + DATA[1].len());
+        assert_eq!(
+            error.to_string(),
+            "length limit of 8 bytes exceeded (33 bytes)"
+        );
     }
 
     #[tokio::test]
@@ -184,7 +307,10 @@ mod tests {
         assert_eq!(body.size_hint().upper(), hint.upper());
 
         let error = body.frame().await.unwrap().unwrap_err();
-        assert!(matches!(error.downcast_ref(), Some(LengthLimitError)));
+        assert!(matches!(
+            error.downcast_ref(),
+            Some(LengthLimitError { .. })
+        ));
     }
 
     #[tokio::test]
@@ -231,6 +357,34 @@ mod tests {
         assert!(frame.is_trailers());
     }
 
+    fn body_with_trailers() -> TestBody {
+        let mut trailers = http::HeaderMap::new();
+        trailers.insert("x-a", http::HeaderValue::from_static("abc"));
+        test_body(&["test"], Some(trailers))
+    }
+
+    #[tokio::test]
+    async fn trailers_not_counted_by_default() {
+        let body = Limited::new(body_with_trailers(), 4);
+        let collected = body.collect().await.unwrap();
+        assert!(collected.trailers().is_some());
+    }
+
+    #[tokio::test]
+    async fn count_trailers() {
+        // "x-a: abc\r\n" is 10 bytes.
+        let body = Limited::new(body_with_trailers(), 14).count_trailers(true);
+        let collected = body.collect().await.unwrap();
+        assert!(collected.trailers().is_some());
+
+        let body = &mut Limited::new(body_with_trailers(), 13).count_trailers(true);
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(body.remaining(), 9);
+        let error = body.frame().await.unwrap().unwrap_err();
+        let error = error.downcast_ref::<LengthLimitError>().unwrap();
+        assert_eq!(error.observed(), 14);
+    }
+
     #[derive(Debug)]
     struct ErrorBodyError;
 
@@ -256,6 +410,23 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn accessors() {
+        const DATA: [&[u8]; 2] = [b"test", b"ing!"];
+        let mut body = Limited::new(body_from_iter(DATA), 6);
+        assert_eq!(body.remaining(), 6);
+
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(body.remaining(), 2);
+        assert!(!body.get_ref().is_end_stream());
+
+        body.frame().await.unwrap().unwrap_err();
+        assert_eq!(body.remaining(), 0);
+
+        let mut inner = body.into_inner();
+        assert!(inner.frame().await.is_none());
+    }
+
     #[tokio::test]
     async fn read_for_body_returning_error_propagates_error() {
         let body = &mut Limited::new(ErrorBody, 8);
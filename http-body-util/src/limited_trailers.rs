@@ -0,0 +1,241 @@
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pin_project! {
+    /// A body with limited trailers.
+    ///
+    /// This body will return an error if the trailers of the wrapped body have more than the
+    /// configured number of fields, or a larger serialized size than the configured number of
+    /// bytes. Trailers often bypass the header size limits of HTTP implementations, so this
+    /// guards against peers sending unbounded header data after the body.
+    ///
+    /// The size of the trailers is measured as by [`Frame::trailers_len`]. If the body yields
+    /// several trailers frames, the limits apply to all of them together. Data frames are not
+    /// limited; see [`Limited`](crate::Limited) for that.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::{HeaderMap, HeaderValue};
+    /// use http_body_util::{BodyExt, Empty, LimitedTrailers};
+    /// use bytes::Bytes;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut trailers = HeaderMap::new();
+    /// trailers.insert("x-large", HeaderValue::from_static("a very long trailer value"));
+    ///
+    /// let body = Empty::<Bytes>::new().with_trailers(async move {
+    ///     Some(Ok::<_, std::convert::Infallible>(trailers))
+    /// });
+    /// let body = LimitedTrailers::new(body, 10, 16);
+    /// assert!(body.collect().await.is_err());
+    /// # }
+    /// ```
+    #[derive(Clone, Copy, Debug)]
+    pub struct LimitedTrailers<B> {
+        max_fields: usize,
+        max_size: usize,
+        fields: usize,
+        size: usize,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> LimitedTrailers<B> {
+    /// Create a new `LimitedTrailers` allowing at most `max_fields` trailer fields, taking at most
+    /// `max_size` bytes.
+    pub fn new(inner: B, max_fields: usize, max_size: usize) -> Self {
+        Self {
+            max_fields,
+            max_size,
+            fields: 0,
+            size: 0,
+            inner,
+        }
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for LimitedTrailers<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = B::Data;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let res = match this.inner.poll_frame(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(None) => None,
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(trailers) = frame.trailers_ref() {
+                    *this.fields = this.fields.saturating_add(trailers.len());
+                    *this.size = this
+                        .size
+                        .saturating_add(frame.trailers_len().unwrap_or_default());
+                }
+
+                if *this.fields > *this.max_fields || *this.size > *this.max_size {
+                    debug!(
+                        fields = *this.fields,
+                        size = *this.size,
+                        "limited_trailers: trailers limit exceeded"
+                    );
+                    Some(Err(TrailersLimitError {
+                        max_fields: *this.max_fields,
+                        max_size: *this.max_size,
+                        fields: *this.fields,
+                        size: *this.size,
+                    }
+                    .into()))
+                } else {
+                    Some(Ok(frame))
+                }
+            }
+            Poll::Ready(Some(Err(err))) => Some(Err(err.into())),
+        };
+
+        Poll::Ready(res)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// An error returned when the trailers of a body exceed the configured limits.
+#[derive(Debug)]
+pub struct TrailersLimitError {
+    max_fields: usize,
+    max_size: usize,
+    fields: usize,
+    size: usize,
+}
+
+impl TrailersLimitError {
+    /// Returns the configured limit, in fields.
+    pub fn max_fields(&self) -> usize {
+        self.max_fields
+    }
+
+    /// Returns the configured limit, in bytes.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Returns the number of trailer fields seen when the limit was exceeded.
+    pub fn fields(&self) -> usize {
+        self.fields
+    }
+
+    /// Returns the size of the trailers seen when the limit was exceeded, in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl fmt::Display for TrailersLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "trailers limit of {} fields and {} bytes exceeded ({} fields, {} bytes)",
+            self.max_fields, self.max_size, self.fields, self.size
+        )
+    }
+}
+
+impl Error for TrailersLimitError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_body;
+    use crate::{BodyExt, StreamBody};
+    use bytes::Bytes;
+    use futures_util::stream;
+    use http::{HeaderMap, HeaderValue};
+    use std::convert::Infallible;
+
+    fn trailers(fields: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut trailers = HeaderMap::new();
+        for (name, value) in fields {
+            trailers.append(*name, HeaderValue::from_static(value));
+        }
+        trailers
+    }
+
+    #[tokio::test]
+    async fn under_limits() {
+        let trailers = trailers(&[("a", "1"), ("a", "2")]);
+        let body = LimitedTrailers::new(test_body(&["hello"], Some(trailers.clone())), 2, 12);
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.trailers(), Some(&trailers));
+    }
+
+    #[tokio::test]
+    async fn too_many_fields() {
+        // `test_body` ends after one trailers frame, so build the second one by hand.
+        let frames = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hello"))),
+            Ok(Frame::trailers(trailers(&[("a", "1")]))),
+            Ok(Frame::trailers(trailers(&[("b", "2")]))),
+        ];
+        let mut body = LimitedTrailers::new(StreamBody::new(stream::iter(frames)), 1, 1024);
+
+        assert!(body.frame().await.unwrap().unwrap().is_data());
+        assert!(body.frame().await.unwrap().unwrap().is_trailers());
+        let err = body.frame().await.unwrap().unwrap_err();
+        let err = err.downcast_ref::<TrailersLimitError>().unwrap();
+        assert_eq!(err.fields(), 2);
+        assert_eq!(err.max_fields(), 1);
+    }
+
+    #[tokio::test]
+    async fn too_large() {
+        let inner = test_body(&["hello"], Some(trailers(&[("name", "value")])));
+        let mut body = LimitedTrailers::new(inner, 10, 12);
+
+        body.frame().await.unwrap().unwrap();
+        let err = body.frame().await.unwrap().unwrap_err();
+        let err = err.downcast_ref::<TrailersLimitError>().unwrap();
+        assert_eq!(err.size(), 13);
+        assert_eq!(
+            err.to_string(),
+            "trailers limit of 10 fields and 12 bytes exceeded (1 fields, 13 bytes)"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pin_project! {
+    /// A body limited to a number of frames.
+    ///
+    /// This body will return an error if the wrapped body yields more than the configured number
+    /// of frames, whatever their size. Unlike [`Limited`](crate::Limited), this bounds the work
+    /// spent on bodies made of many tiny frames, such as a peer sending millions of one byte
+    /// chunks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http_body_util::{BodyExt, MaxFrames, StreamBody};
+    /// # use bytes::Bytes;
+    /// # use http_body::Frame;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let frames = futures_util::stream::iter(
+    ///     (0..1000).map(|_| Ok::<_, std::convert::Infallible>(Frame::data(Bytes::from("a")))),
+    /// );
+    /// let body = MaxFrames::new(StreamBody::new(frames), 100);
+    /// assert!(body.collect().await.is_err());
+    /// # }
+    /// ```
+    #[derive(Clone, Copy, Debug)]
+    pub struct MaxFrames<B> {
+        limit: usize,
+        remaining: usize,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> MaxFrames<B> {
+    /// Create a new `MaxFrames` allowing at most `limit` frames.
+    pub fn new(inner: B, limit: usize) -> Self {
+        Self {
+            limit,
+            remaining: limit,
+            inner,
+        }
+    }
+
+    /// Returns the number of frames that can still be yielded before the limit is exceeded.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for MaxFrames<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = B::Data;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let res = match this.inner.poll_frame(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(None) => None,
+            Poll::Ready(Some(Ok(frame))) => {
+                if *this.remaining == 0 {
+                    debug!(limit = *this.limit, "max_frames: frame limit exceeded");
+                    Some(Err(FrameLimitError { limit: *this.limit }.into()))
+                } else {
+                    *this.remaining -= 1;
+                    Some(Ok(frame))
+                }
+            }
+            Poll::Ready(Some(Err(err))) => Some(Err(err.into())),
+        };
+
+        Poll::Ready(res)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// An error returned when a body yields more frames than the configured limit.
+#[derive(Debug)]
+pub struct FrameLimitError {
+    limit: usize,
+}
+
+impl FrameLimitError {
+    /// Returns the configured limit, in frames.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl fmt::Display for FrameLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "frame limit of {} frames exceeded", self.limit)
+    }
+}
+
+impl Error for FrameLimitError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_body;
+    use crate::BodyExt;
+    use http::HeaderMap;
+
+    #[tokio::test]
+    async fn under_limit() {
+        let inner = test_body(&["a", "b"], Some(HeaderMap::new()));
+        let mut body = MaxFrames::new(inner, 3);
+        assert_eq!(body.remaining(), 3);
+
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(body.remaining(), 2);
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.to_bytes(), "b");
+    }
+
+    #[tokio::test]
+    async fn over_limit() {
+        let inner = test_body(&["a", "b"], None);
+        let mut body = MaxFrames::new(inner, 1);
+
+        body.frame().await.unwrap().unwrap();
+        let err = body.frame().await.unwrap().unwrap_err();
+        let err = err.downcast_ref::<FrameLimitError>().unwrap();
+        assert_eq!(err.limit(), 1);
+        assert_eq!(err.to_string(), "frame limit of 1 frames exceeded");
+    }
+}
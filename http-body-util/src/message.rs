@@ -0,0 +1,118 @@
+use http::{request, response, Request, Response};
+use http_body::Body;
+
+use crate::{
+    combinators::{IntoBytesWithLimit, IntoCollected},
+    BodyExt,
+};
+
+/// An extension trait for [`http::Request`] adding methods to collect its body.
+pub trait RequestExt<B: Body> {
+    /// Collect the body, resolving to a request with the same parts and a [`Collected`] body.
+    ///
+    /// [`Collected`]: crate::Collected
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http::Request;
+    /// use http_body_util::{Full, RequestExt};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let req = Request::post("/").body(Full::new(Bytes::from("hello"))).unwrap();
+    ///
+    /// let req = req.into_collected().await.unwrap();
+    /// assert_eq!(req.method(), "POST");
+    /// assert_eq!(req.into_body().to_bytes(), "hello");
+    /// # }
+    /// ```
+    fn into_collected(self) -> IntoCollected<request::Parts, B>;
+
+    /// Collect the body into [`Bytes`](bytes::Bytes), failing once it contains more than `limit`
+    /// bytes of data.
+    ///
+    /// See [`BodyExt::collect_with_limit`] for details about the error.
+    fn into_bytes_with_limit(self, limit: usize) -> IntoBytesWithLimit<request::Parts, B>;
+}
+
+impl<B: Body> RequestExt<B> for Request<B> {
+    fn into_collected(self) -> IntoCollected<request::Parts, B> {
+        let (parts, body) = self.into_parts();
+        IntoCollected::new(parts, body.collect())
+    }
+
+    fn into_bytes_with_limit(self, limit: usize) -> IntoBytesWithLimit<request::Parts, B> {
+        let (parts, body) = self.into_parts();
+        IntoBytesWithLimit::new(parts, body.collect_with_limit(limit))
+    }
+}
+
+/// An extension trait for [`http::Response`] adding methods to collect its body.
+pub trait ResponseExt<B: Body> {
+    /// Collect the body, resolving to a response with the same parts and a [`Collected`] body.
+    ///
+    /// [`Collected`]: crate::Collected
+    fn into_collected(self) -> IntoCollected<response::Parts, B>;
+
+    /// Collect the body into [`Bytes`](bytes::Bytes), failing once it contains more than `limit`
+    /// bytes of data.
+    ///
+    /// See [`BodyExt::collect_with_limit`] for details about the error.
+    fn into_bytes_with_limit(self, limit: usize) -> IntoBytesWithLimit<response::Parts, B>;
+}
+
+impl<B: Body> ResponseExt<B> for Response<B> {
+    fn into_collected(self) -> IntoCollected<response::Parts, B> {
+        let (parts, body) = self.into_parts();
+        IntoCollected::new(parts, body.collect())
+    }
+
+    fn into_bytes_with_limit(self, limit: usize) -> IntoBytesWithLimit<response::Parts, B> {
+        let (parts, body) = self.into_parts();
+        IntoBytesWithLimit::new(parts, body.collect_with_limit(limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use http::{HeaderMap, StatusCode};
+
+    use super::*;
+    use crate::{Full, StreamBody};
+
+    #[tokio::test]
+    async fn response_into_collected() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-done", "1".parse().unwrap());
+        let frames = vec![
+            Ok::<_, std::convert::Infallible>(http_body::Frame::data(Bytes::from("hello"))),
+            Ok(http_body::Frame::trailers(trailers.clone())),
+        ];
+        let res = Response::builder()
+            .status(StatusCode::CREATED)
+            .body(StreamBody::new(futures_util::stream::iter(frames)))
+            .unwrap();
+
+        let res = res.into_collected().await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(res.body().trailers(), Some(&trailers));
+        assert_eq!(res.into_body().to_bytes(), "hello");
+    }
+
+    #[tokio::test]
+    async fn into_bytes_with_limit() {
+        let req = Request::put("/upload")
+            .body(Full::new(Bytes::from("hello")))
+            .unwrap();
+        let req = req.into_bytes_with_limit(5).await.unwrap();
+        assert_eq!(req.uri(), "/upload");
+        assert_eq!(req.body(), "hello");
+
+        let res = Response::new(Full::new(Bytes::from("hello")));
+        let err = res.into_bytes_with_limit(4).await.unwrap_err();
+        assert!(err.is_length_limit());
+    }
+}
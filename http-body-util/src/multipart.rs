@@ -0,0 +1,344 @@
+use std::{
+    collections::{hash_map::RandomState, VecDeque},
+    error::Error,
+    fmt,
+    hash::{BuildHasher, Hasher},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, Bytes};
+use futures_core::ready;
+use http::HeaderValue;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+use crate::combinators::UnsyncBoxBody;
+use crate::BodyExt;
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+/// A `multipart/form-data` body.
+///
+/// Parts are added with [`text`] and [`file`], and are written in that order. File parts are
+/// themselves bodies, which are streamed rather than buffered. If every file part has an exact
+/// size hint, so does the multipart body, which allows sending it with a `Content-Length`.
+///
+/// The `Content-Type` of the request, including the boundary, is returned by [`content_type`].
+///
+/// [`text`]: MultipartBody::text
+/// [`file`]: MultipartBody::file
+/// [`content_type`]: MultipartBody::content_type
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body_util::{BodyExt, Full, MultipartBody};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let body = MultipartBody::new()
+///     .text("title", "Notes")
+///     .file("upload", "notes.txt", "text/plain", Full::new(Bytes::from("hello")));
+///
+/// let content_type = body.content_type();
+/// let bytes = body.collect().await.unwrap().to_bytes();
+/// # let _ = (content_type, bytes);
+/// # }
+/// ```
+pub struct MultipartBody {
+    boundary: String,
+    segments: VecDeque<Segment>,
+    closed: bool,
+}
+
+enum Segment {
+    Bytes(Bytes),
+    Body(UnsyncBoxBody<Bytes, BoxError>),
+}
+
+impl MultipartBody {
+    /// Create a new, empty `MultipartBody` with a random boundary.
+    pub fn new() -> Self {
+        let state = RandomState::new();
+        let (a, b) = (state.build_hasher().finish(), {
+            let mut hasher = state.build_hasher();
+            hasher.write_u8(1);
+            hasher.finish()
+        });
+        Self::with_boundary(format!("{a:016x}{b:016x}"))
+    }
+
+    /// Create a new, empty `MultipartBody` with the given boundary.
+    ///
+    /// The boundary must not occur in the contents of any part, and must be valid in a header
+    /// value.
+    pub fn with_boundary(boundary: impl Into<String>) -> Self {
+        Self {
+            boundary: boundary.into(),
+            segments: VecDeque::new(),
+            closed: false,
+        }
+    }
+
+    /// Returns the boundary separating the parts.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Returns the `Content-Type` header value for this body.
+    pub fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_str(&format!("multipart/form-data; boundary={}", self.boundary))
+            .expect("boundary is a valid header value")
+    }
+
+    /// Add a text field.
+    pub fn text(mut self, name: &str, value: impl Into<String>) -> Self {
+        let mut part = self.part_header(name, None, None);
+        part.push_str(&value.into());
+        part.push_str("\r\n");
+        self.segments.push_back(Segment::Bytes(Bytes::from(part)));
+        self
+    }
+
+    /// Add a file part whose contents are streamed from `body`.
+    pub fn file<B>(mut self, name: &str, filename: &str, content_type: &str, body: B) -> Self
+    where
+        B: Body + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        let header = self.part_header(name, Some(filename), Some(content_type));
+        let body = PartBody { inner: body }.map_err(Into::into);
+
+        self.segments.push_back(Segment::Bytes(Bytes::from(header)));
+        self.segments
+            .push_back(Segment::Body(UnsyncBoxBody::new(body)));
+        self.segments
+            .push_back(Segment::Bytes(Bytes::from_static(b"\r\n")));
+        self
+    }
+
+    fn part_header(
+        &self,
+        name: &str,
+        filename: Option<&str>,
+        content_type: Option<&str>,
+    ) -> String {
+        let mut header = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"",
+            self.boundary,
+            escape(name)
+        );
+        if let Some(filename) = filename {
+            header.push_str(&format!("; filename=\"{}\"", escape(filename)));
+        }
+        header.push_str("\r\n");
+        if let Some(content_type) = content_type {
+            header.push_str(&format!("Content-Type: {}\r\n", escape(content_type)));
+        }
+        header.push_str("\r\n");
+        header
+    }
+
+    fn closing_len(&self) -> u64 {
+        if self.closed {
+            0
+        } else {
+            // "--" boundary "--\r\n"
+            self.boundary.len() as u64 + 6
+        }
+    }
+}
+
+pin_project! {
+    /// Converts the data of a file part to `Bytes`, keeping its size hint.
+    struct PartBody<B> {
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B: Body> Body for PartBody<B> {
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let frame = ready!(self.project().inner.poll_frame(cx));
+        Poll::Ready(frame.map(|frame| {
+            frame.map(|frame| frame.map_data(|mut data| data.copy_to_bytes(data.remaining())))
+        }))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Percent-encodes the characters that would break out of a quoted header parameter.
+fn escape(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+impl Default for MultipartBody {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Body for MultipartBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+
+        loop {
+            match this.segments.front_mut() {
+                None => {
+                    if this.closed {
+                        return Poll::Ready(None);
+                    }
+                    this.closed = true;
+                    let closing = format!("--{}--\r\n", this.boundary);
+                    return Poll::Ready(Some(Ok(Frame::data(Bytes::from(closing)))));
+                }
+                Some(Segment::Bytes(_)) => {
+                    if let Some(Segment::Bytes(bytes)) = this.segments.pop_front() {
+                        return Poll::Ready(Some(Ok(Frame::data(bytes))));
+                    }
+                }
+                Some(Segment::Body(body)) => match ready!(Pin::new(body).poll_frame(cx)) {
+                    Some(Ok(frame)) => {
+                        if let Ok(data) = frame.into_data() {
+                            if !data.is_empty() {
+                                return Poll::Ready(Some(Ok(Frame::data(data))));
+                            }
+                        }
+                    }
+                    Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                    None => {
+                        this.segments.pop_front();
+                    }
+                },
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.closed && self.segments.is_empty()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let mut lower = self.closing_len();
+        let mut upper = Some(lower);
+        for segment in &self.segments {
+            match segment {
+                Segment::Bytes(bytes) => {
+                    lower += bytes.len() as u64;
+                    upper = upper.map(|upper| upper + bytes.len() as u64);
+                }
+                Segment::Body(body) => {
+                    let hint = body.size_hint();
+                    lower += hint.lower();
+                    upper = upper.zip(hint.upper()).map(|(a, b)| a + b);
+                }
+            }
+        }
+
+        let mut hint = SizeHint::new();
+        hint.set_lower(lower);
+        if let Some(upper) = upper {
+            hint.set_upper(upper);
+        }
+        hint
+    }
+}
+
+impl fmt::Debug for MultipartBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultipartBody")
+            .field("boundary", &self.boundary)
+            .field("segments", &self.segments.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use futures_util::stream;
+
+    use super::*;
+    use crate::{Full, StreamBody};
+
+    #[tokio::test]
+    async fn encodes_parts() {
+        let body = MultipartBody::with_boundary("XYZ")
+            .text("title", "Notes")
+            .file(
+                "up\"load",
+                "notes.txt",
+                "text/plain",
+                Full::new(Bytes::from("hello")),
+            );
+        assert_eq!(body.content_type(), "multipart/form-data; boundary=XYZ");
+
+        let hint = body.size_hint();
+        let bytes = body.collect().await.unwrap().to_bytes();
+        let expected = "--XYZ\r\n\
+            Content-Disposition: form-data; name=\"title\"\r\n\
+            \r\n\
+            Notes\r\n\
+            --XYZ\r\n\
+            Content-Disposition: form-data; name=\"up%22load\"; filename=\"notes.txt\"\r\n\
+            Content-Type: text/plain\r\n\
+            \r\n\
+            hello\r\n\
+            --XYZ--\r\n";
+        assert_eq!(bytes, expected);
+        assert_eq!(hint.exact(), Some(expected.len() as u64));
+    }
+
+    #[tokio::test]
+    async fn unsized_part() {
+        let chunks = vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hel"))),
+            Ok(Frame::data(Bytes::from("lo"))),
+        ];
+        let body = MultipartBody::new().file(
+            "file",
+            "a.bin",
+            "application/octet-stream",
+            StreamBody::new(stream::iter(chunks)),
+        );
+        let boundary = body.boundary().to_owned();
+        assert_eq!(boundary.len(), 32);
+        assert_eq!(body.size_hint().upper(), None);
+
+        let bytes = body.collect().await.unwrap().to_bytes();
+        assert!(bytes.ends_with(format!("hello\r\n--{boundary}--\r\n").as_bytes()));
+    }
+
+    #[test]
+    fn random_boundaries() {
+        assert_ne!(
+            MultipartBody::new().boundary(),
+            MultipartBody::new().boundary()
+        );
+    }
+}
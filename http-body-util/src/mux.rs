@@ -0,0 +1,361 @@
+use std::{
+    any::type_name,
+    fmt,
+    io::IoSlice,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use http::HeaderMap;
+use http_body::{Body, Frame, SizeHint};
+
+/// The framing of a [`Mux`] created with [`Mux::new`].
+type TagFrame<D> = fn(usize, Frame<D>) -> Frame<Tagged<D>>;
+
+/// A body that merges the frames of several bodies, by weight.
+///
+/// Bodies are polled in turn, and a body with weight `n` yields up to `n` frames before the next
+/// one gets its turn. A body that is not ready doesn't hold the others back, so frames are
+/// yielded as soon as any body has one.
+///
+/// Each frame is passed with the index of its body, in the order the bodies were added, to a
+/// framing function that tags it with its source. By default, the data is wrapped in [`Tagged`],
+/// and [`with_framing`](Mux::with_framing) allows encoding the source into the data instead, for
+/// example as a sub-stream header. Trailers returned by the framing function are merged and
+/// yielded once all bodies have ended.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body_util::{BodyExt, Full, Mux};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let mut body = Mux::new()
+///     .add(Full::new(Bytes::from("index.html")), 1)
+///     .add(Full::new(Bytes::from("style.css")), 1);
+///
+/// while let Some(frame) = body.frame().await {
+///     let data = frame.unwrap().into_data().unwrap();
+///     println!("body {}: {:?}", data.source(), data.get_ref());
+/// }
+/// # }
+/// ```
+pub struct Mux<B, F> {
+    sources: Vec<Source<B>>,
+    next: usize,
+    framing: F,
+    trailers: Option<HeaderMap>,
+}
+
+struct Source<B> {
+    body: Pin<Box<B>>,
+    weight: u32,
+    credit: u32,
+    done: bool,
+}
+
+// The bodies are pinned in their own allocation, and the framing function is never pinned.
+impl<B, F> Unpin for Mux<B, F> {}
+
+impl<B: Body> Mux<B, TagFrame<B::Data>> {
+    /// Create a new, empty `Mux` wrapping the data of each frame in [`Tagged`].
+    ///
+    /// Trailers of the bodies are merged and yielded at the end.
+    pub fn new() -> Self {
+        Self::with_framing(|source, frame| frame.map_data(|data| Tagged { source, data }))
+    }
+}
+
+impl<B: Body> Default for Mux<B, TagFrame<B::Data>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B, F> Mux<B, F> {
+    /// Create a new, empty `Mux` tagging frames with `framing`.
+    ///
+    /// The function is called with the index of the body each frame comes from.
+    pub fn with_framing(framing: F) -> Self {
+        Self {
+            sources: Vec::new(),
+            next: 0,
+            framing,
+            trailers: None,
+        }
+    }
+
+    /// Add a body with the given weight.
+    ///
+    /// The index of the body is the number of bodies added before it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weight` is 0.
+    pub fn add(mut self, body: B, weight: u32) -> Self {
+        assert!(weight > 0, "weight must be at least 1");
+        self.sources.push(Source {
+            body: Box::pin(body),
+            weight,
+            credit: weight,
+            done: false,
+        });
+        self
+    }
+
+    /// Returns the number of bodies added.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns whether no body was added.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+impl<B, F, D> Body for Mux<B, F>
+where
+    B: Body,
+    F: FnMut(usize, Frame<B::Data>) -> Frame<D>,
+    D: Buf,
+{
+    type Data = D;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        let len = this.sources.len();
+
+        'poll: loop {
+            let mut pending = false;
+            let start = this.next;
+            for i in (0..len).map(|i| (start + i) % len) {
+                let source = &mut this.sources[i];
+                if source.done {
+                    continue;
+                }
+
+                let frame = match source.body.as_mut().poll_frame(cx) {
+                    Poll::Ready(Some(Ok(frame))) => frame,
+                    Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                    Poll::Ready(None) => {
+                        source.done = true;
+                        continue;
+                    }
+                    Poll::Pending => {
+                        pending = true;
+                        continue;
+                    }
+                };
+
+                source.credit -= 1;
+                if source.credit == 0 {
+                    source.credit = source.weight;
+                    this.next = (i + 1) % len;
+                } else {
+                    this.next = i;
+                }
+
+                match (this.framing)(i, frame).into_trailers() {
+                    Ok(trailers) => {
+                        this.trailers
+                            .get_or_insert_with(HeaderMap::new)
+                            .extend(trailers);
+                        continue 'poll;
+                    }
+                    Err(frame) => return Poll::Ready(Some(Ok(frame))),
+                }
+            }
+
+            if pending {
+                return Poll::Pending;
+            }
+            return Poll::Ready(
+                this.trailers
+                    .take()
+                    .map(|trailers| Ok(Frame::trailers(trailers))),
+            );
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.trailers.is_none()
+            && self
+                .sources
+                .iter()
+                .all(|source| source.done || source.body.is_end_stream())
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        // The framing function may change the size of the data.
+        let mut hint = SizeHint::new();
+        if self.is_end_stream() {
+            hint.set_exact(0);
+        }
+        hint
+    }
+}
+
+impl<B, F> fmt::Debug for Mux<B, F>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mux")
+            .field(
+                "bodies",
+                &self
+                    .sources
+                    .iter()
+                    .map(|source| (&source.body, source.weight))
+                    .collect::<Vec<_>>(),
+            )
+            .field("framing", &type_name::<F>())
+            .finish()
+    }
+}
+
+/// Data tagged with the index of the body it comes from, yielded by a [`Mux`].
+#[derive(Clone, Debug)]
+pub struct Tagged<D> {
+    source: usize,
+    data: D,
+}
+
+impl<D> Tagged<D> {
+    /// Returns the index of the body the data comes from.
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// Get a reference to the data
+    pub fn get_ref(&self) -> &D {
+        &self.data
+    }
+
+    /// Get a mutable reference to the data
+    pub fn get_mut(&mut self) -> &mut D {
+        &mut self.data
+    }
+
+    /// Consume `self`, returning the data
+    pub fn into_inner(self) -> D {
+        self.data
+    }
+}
+
+impl<D: Buf> Buf for Tagged<D> {
+    fn remaining(&self) -> usize {
+        self.data.remaining()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.data.chunk()
+    }
+
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        self.data.chunks_vectored(dst)
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.data.advance(cnt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::{BufMut, Bytes, BytesMut};
+    use futures_util::{stream, FutureExt};
+
+    use super::*;
+    use crate::test_support::{test_body, trailer, TestBody};
+    use crate::{BodyExt, StreamBody};
+
+    async fn sources<B>(mut body: B) -> Vec<(usize, Bytes)>
+    where
+        B: Body<Data = Tagged<Bytes>> + Unpin,
+        B::Error: fmt::Debug,
+    {
+        let mut out = Vec::new();
+        while let Some(frame) = body.frame().await {
+            if let Ok(data) = frame.unwrap().into_data() {
+                out.push((data.source(), data.into_inner()));
+            }
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn weighted_round_robin() {
+        let mux = Mux::new()
+            .add(test_body(&["a1", "a2", "a3", "a4"], None), 2)
+            .add(test_body(&["b1", "b2"], None), 1);
+
+        let frames = sources(mux).await;
+        let order: Vec<_> = frames.iter().map(|(_, data)| &data[..]).collect();
+        assert_eq!(order, [&b"a1"[..], b"a2", b"b1", b"a3", b"a4", b"b2"]);
+        assert!(frames
+            .iter()
+            .all(|(source, data)| data[0] == b"ab"[*source]));
+    }
+
+    #[tokio::test]
+    async fn merges_trailers_at_end() {
+        let mut mux = Mux::new()
+            .add(test_body(&["a"], Some(trailer("x-a"))), 1)
+            .add(test_body(&["b"], Some(trailer("x-b"))), 1);
+
+        assert!(mux.frame().await.unwrap().unwrap().is_data());
+        assert!(mux.frame().await.unwrap().unwrap().is_data());
+        let trailers = mux.frame().await.unwrap().unwrap().into_trailers().unwrap();
+        assert_eq!(trailers.len(), 2);
+        assert!(mux.frame().await.is_none());
+        assert!(mux.is_end_stream());
+    }
+
+    #[tokio::test]
+    async fn pending_body_does_not_block() {
+        let slow = StreamBody::new(stream::pending::<Result<Frame<Bytes>, Infallible>>());
+        let mut mux = Mux::new()
+            .add(slow.boxed_unsync(), 1)
+            .add(test_body(&["fast"], None).boxed_unsync(), 1);
+
+        let data = mux.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data.source(), 1);
+        assert!(mux.frame().now_or_never().is_none());
+    }
+
+    #[tokio::test]
+    async fn custom_framing() {
+        let mux = Mux::with_framing(|source, frame: Frame<Bytes>| {
+            frame.map_data(|data| {
+                let mut buf = BytesMut::new();
+                buf.put_u8(source as u8);
+                buf.put_u32(data.len() as u32);
+                buf.put(data);
+                buf.freeze()
+            })
+        })
+        .add(test_body(&["hi"], None), 1)
+        .add(test_body(&["yo"], None), 1);
+
+        let bytes = mux.collect().await.unwrap().to_bytes();
+        assert_eq!(&bytes[..], b"\x00\x00\x00\x00\x02hi\x01\x00\x00\x00\x02yo");
+    }
+
+    #[tokio::test]
+    async fn empty() {
+        let mut mux = Mux::<TestBody, _>::new();
+        assert!(mux.is_empty());
+        assert!(mux.frame().await.is_none());
+    }
+}
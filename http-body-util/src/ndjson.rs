@@ -0,0 +1,206 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, Write},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_core::{ready, stream::Stream};
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use serde::Serialize;
+
+pin_project! {
+    /// A body that encodes a [`Stream`] of items as newline-delimited JSON.
+    ///
+    /// Each item is serialized into its own data frame, terminated by a newline. An item whose
+    /// serialized form is larger than `max_item_size` is not buffered further; the body returns an
+    /// [`ItemTooLargeError`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::convert::Infallible;
+    ///
+    /// use futures_util::stream;
+    /// use http_body_util::{BodyExt, NdjsonBody};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let items = stream::iter([Ok::<_, Infallible>(1), Ok(2), Ok(3)]);
+    /// let body = NdjsonBody::new(items, 1024);
+    ///
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "1\n2\n3\n");
+    /// # }
+    /// ```
+    #[derive(Clone, Copy, Debug)]
+    pub struct NdjsonBody<S> {
+        #[pin]
+        stream: S,
+        max_item_size: usize,
+        done: bool,
+    }
+}
+
+impl<S> NdjsonBody<S> {
+    /// Create a new `NdjsonBody` serializing items of at most `max_item_size` bytes.
+    ///
+    /// The limit includes the trailing newline.
+    pub fn new(stream: S, max_item_size: usize) -> Self {
+        Self {
+            stream,
+            max_item_size,
+            done: false,
+        }
+    }
+}
+
+impl<S, T, E> Body for NdjsonBody<S>
+where
+    S: Stream<Item = Result<T, E>>,
+    T: Serialize,
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = Bytes;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let item = match ready!(this.stream.poll_next(cx)) {
+            Some(Ok(item)) => item,
+            Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+            None => {
+                *this.done = true;
+                return Poll::Ready(None);
+            }
+        };
+
+        let mut writer = LimitedWriter {
+            buf: Vec::new(),
+            limit: *this.max_item_size,
+        };
+        let result = serde_json::to_writer(&mut writer, &item)
+            .map_err(|err| {
+                if err.is_io() {
+                    ItemTooLargeError.into()
+                } else {
+                    err.into()
+                }
+            })
+            .and_then(|()| {
+                writer
+                    .write_all(b"\n")
+                    .map_err(|_| ItemTooLargeError.into())
+            });
+
+        Poll::Ready(Some(result.map(|()| Frame::data(Bytes::from(writer.buf)))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.done
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.done {
+            return SizeHint::with_exact(0);
+        }
+        SizeHint::default()
+    }
+}
+
+/// A writer into a buffer that fails instead of growing past `limit`.
+struct LimitedWriter {
+    buf: Vec<u8>,
+    limit: usize,
+}
+
+impl Write for LimitedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buf.len() + buf.len() > self.limit {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// An error returned when an item of a [`NdjsonBody`] serializes to more than the maximum size.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ItemTooLargeError;
+
+impl fmt::Display for ItemTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("serialized item exceeds the maximum size")
+    }
+}
+
+impl Error for ItemTooLargeError {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+
+    use futures_util::stream;
+
+    use super::*;
+    use crate::BodyExt;
+
+    #[derive(Serialize)]
+    struct Event {
+        id: u32,
+        name: &'static str,
+    }
+
+    #[tokio::test]
+    async fn one_frame_per_item() {
+        let items = stream::iter(vec![
+            Ok::<_, Infallible>(Event { id: 1, name: "a" }),
+            Ok(Event { id: 2, name: "b" }),
+        ]);
+        let mut body = NdjsonBody::new(items, 64);
+
+        let first = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(first, "{\"id\":1,\"name\":\"a\"}\n");
+        let second = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(second, "{\"id\":2,\"name\":\"b\"}\n");
+        assert!(body.frame().await.is_none());
+        assert!(body.is_end_stream());
+    }
+
+    #[tokio::test]
+    async fn item_too_large() {
+        let items = stream::iter(vec![Ok::<_, Infallible>("1234"), Ok("12345")]);
+        let mut body = NdjsonBody::new(items, 7);
+
+        let first = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(first, "\"1234\"\n");
+        let err = body.frame().await.unwrap().unwrap_err();
+        assert!(err.downcast_ref::<ItemTooLargeError>().is_some());
+    }
+
+    #[tokio::test]
+    async fn serialization_error() {
+        let mut map = HashMap::new();
+        map.insert((1, 2), 3);
+        let items = stream::iter(vec![Ok::<_, Infallible>(map)]);
+        let mut body = NdjsonBody::new(items, 64);
+
+        let err = body.frame().await.unwrap().unwrap_err();
+        assert!(err.downcast_ref::<serde_json::Error>().is_some());
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A body that consists of a single chunk produced by a future.
+    ///
+    /// This is the asynchronous counterpart of [`Full`](crate::Full): the future is awaited on the
+    /// first poll, and its output is yielded as a single data frame. An empty chunk yields no
+    /// frame. If the future fails, its error is yielded and the body ends.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Once};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Once::new(async { Ok::<_, std::io::Error>(Bytes::from("hello")) });
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct Once<F> {
+        done: bool,
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F> Once<F> {
+    /// Create a new `Once`.
+    pub fn new(future: F) -> Self {
+        Self {
+            done: false,
+            future,
+        }
+    }
+}
+
+impl<F, D, E> Body for Once<F>
+where
+    F: Future<Output = Result<D, E>>,
+    D: Buf,
+{
+    type Data = D;
+    type Error = E;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let res = ready!(this.future.poll(cx));
+        *this.done = true;
+        Poll::Ready(match res {
+            Ok(data) if data.has_remaining() => Some(Ok(Frame::data(data))),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.done
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.done {
+            SizeHint::with_exact(0)
+        } else {
+            SizeHint::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::BodyExt;
+
+    #[tokio::test]
+    async fn single_chunk() {
+        let mut body = Once::new(Box::pin(async {
+            tokio::task::yield_now().await;
+            Ok::<_, Infallible>(Bytes::from("hello"))
+        }));
+        assert!(!body.is_end_stream());
+
+        let frame = body.frame().await.unwrap().unwrap();
+        assert_eq!(frame.into_data().unwrap(), "hello");
+        assert!(body.is_end_stream());
+        assert!(body.frame().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn empty_chunk() {
+        let mut body = Once::new(Box::pin(async { Ok::<_, Infallible>(Bytes::new()) }));
+        assert!(body.frame().await.is_none());
+        assert!(body.is_end_stream());
+    }
+
+    #[tokio::test]
+    async fn error() {
+        let mut body = Once::new(Box::pin(async { Err::<Bytes, _>("failed") }));
+        assert_eq!(body.frame().await.unwrap().unwrap_err(), "failed");
+        assert!(body.frame().await.is_none());
+    }
+}
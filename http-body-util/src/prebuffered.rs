@@ -0,0 +1,196 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A body that buffers up to a limit in memory before streaming the remainder.
+    ///
+    /// Calling [`fill`] reads frames from the wrapped body until either `limit` bytes of data are
+    /// buffered or the body ends. If it ended, [`is_complete`] returns `true` and the size hint is
+    /// exact, so a `Content-Length` can be sent for small bodies. Otherwise the buffered frames are
+    /// yielded first and the rest of the body is streamed transparently.
+    ///
+    /// [`fill`]: Prebuffered::fill
+    /// [`is_complete`]: Prebuffered::is_complete
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body::Body as _;
+    /// use http_body_util::{Full, Prebuffered};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut body = Prebuffered::new(Full::new(Bytes::from("hello")), 64 * 1024);
+    /// body.fill().await.unwrap();
+    ///
+    /// assert!(body.is_complete());
+    /// assert_eq!(body.size_hint().exact(), Some(5));
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct Prebuffered<B: Body> {
+        buffered: VecDeque<Frame<B::Data>>,
+        buffered_bytes: usize,
+        limit: usize,
+        complete: bool,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B: Body> Prebuffered<B> {
+    /// Create a new `Prebuffered` that buffers at most `limit` bytes of data.
+    ///
+    /// Nothing is read from `inner` until [`fill`](Prebuffered::fill) is called or the body is
+    /// polled.
+    pub fn new(inner: B, limit: usize) -> Self {
+        Self {
+            buffered: VecDeque::new(),
+            buffered_bytes: 0,
+            limit,
+            complete: false,
+            inner,
+        }
+    }
+
+    /// Returns whether the whole body has been buffered.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Returns a future that buffers frames until the limit is reached or the body ends.
+    pub fn fill(&mut self) -> Fill<'_, B>
+    where
+        B: Unpin,
+    {
+        Fill(self)
+    }
+
+    /// Attempt to buffer frames until the limit is reached or the body ends.
+    pub fn poll_fill(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), B::Error>> {
+        let mut this = self.project();
+
+        while !*this.complete && *this.buffered_bytes < *this.limit {
+            match this.inner.as_mut().poll_frame(cx) {
+                Poll::Ready(Some(Ok(frame))) => {
+                    if let Some(data) = frame.data_ref() {
+                        *this.buffered_bytes += data.remaining();
+                    }
+                    this.buffered.push_back(frame);
+                    *this.complete = this.inner.is_end_stream();
+                }
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Err(err)),
+                Poll::Ready(None) => *this.complete = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<B: Body> Body for Prebuffered<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+
+        if let Some(frame) = this.buffered.pop_front() {
+            if let Some(data) = frame.data_ref() {
+                *this.buffered_bytes -= data.remaining();
+            }
+            return Poll::Ready(Some(Ok(frame)));
+        }
+
+        if *this.complete {
+            return Poll::Ready(None);
+        }
+
+        this.inner.poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.buffered.is_empty() && (self.complete || self.inner.is_end_stream())
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let buffered = self.buffered_bytes as u64;
+        if self.complete {
+            return SizeHint::with_exact(buffered);
+        }
+
+        let inner = self.inner.size_hint();
+        let mut hint = SizeHint::new();
+        if let Some(upper) = inner.upper() {
+            hint.set_upper(upper + buffered);
+        }
+        hint.set_lower(inner.lower() + buffered);
+        hint
+    }
+}
+
+/// Future returned by [`Prebuffered::fill`].
+#[must_use = "futures don't do anything unless polled"]
+pub struct Fill<'a, B: Body>(&'a mut Prebuffered<B>);
+
+impl<B: Body> fmt::Debug for Fill<'_, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fill").finish()
+    }
+}
+
+impl<B: Body + Unpin> Future for Fill<'_, B> {
+    type Output = Result<(), B::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.0).poll_fill(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_body;
+    use crate::BodyExt;
+
+    #[tokio::test]
+    async fn small_body_is_complete() {
+        let mut body = Prebuffered::new(test_body(&["hello", " world"], None), 64);
+        body.fill().await.unwrap();
+
+        assert!(body.is_complete());
+        assert_eq!(body.size_hint().exact(), Some(11));
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn large_body_streams_remainder() {
+        let mut body = Prebuffered::new(test_body(&["hello", " world", "!"], None), 4);
+        body.fill().await.unwrap();
+
+        assert!(!body.is_complete());
+        assert_eq!(body.size_hint().lower(), 5);
+        assert_eq!(body.size_hint().exact(), None);
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello world!");
+    }
+
+    #[tokio::test]
+    async fn streams_without_fill() {
+        let body = Prebuffered::new(test_body(&["hello"], None), 4);
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    convert::Infallible,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use http_body::{Body, Frame, SizeHint};
+
+const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
+/// A body that yields pseudo-random bytes generated from a seed.
+///
+/// The data is generated as it is polled, so large incompressible payloads can be produced
+/// without allocating them upfront. The same seed always produces the same bytes, which makes
+/// the output reproducible. The generator is not cryptographically secure.
+///
+/// # Example
+///
+/// ```
+/// use http_body::Body as _;
+/// use http_body_util::{BodyExt, RandomBody};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let body = RandomBody::new(42, 1024);
+/// assert_eq!(body.size_hint().exact(), Some(1024));
+///
+/// let a = body.collect().await.unwrap().to_bytes();
+/// let b = RandomBody::new(42, 1024).collect().await.unwrap().to_bytes();
+/// assert_eq!(a, b);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RandomBody {
+    state: u64,
+    // Bytes of the last generated word that have not been yielded yet.
+    leftover: [u8; 8],
+    leftover_len: usize,
+    chunk_size: usize,
+    remaining: u64,
+}
+
+impl RandomBody {
+    /// Create a new `RandomBody` yielding `len` bytes generated from `seed`.
+    pub fn new(seed: u64, len: u64) -> Self {
+        Self {
+            state: seed,
+            leftover: [0; 8],
+            leftover_len: 0,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            remaining: len,
+        }
+    }
+
+    /// Set the maximum size of the data frames yielded by this body.
+    ///
+    /// Defaults to 8 KiB. The generated bytes do not depend on the chunk size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    // SplitMix64, see <https://prng.di.unimi.it/splitmix64.c>.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl Body for RandomBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        if self.remaining == 0 {
+            return Poll::Ready(None);
+        }
+
+        let n = self.remaining.min(self.chunk_size as u64) as usize;
+        let mut chunk = BytesMut::with_capacity(n);
+        while chunk.len() < n {
+            if self.leftover_len == 0 {
+                self.leftover = self.next_u64().to_le_bytes();
+                self.leftover_len = self.leftover.len();
+            }
+            let start = self.leftover.len() - self.leftover_len;
+            let take = (n - chunk.len()).min(self.leftover_len);
+            chunk.put_slice(&self.leftover[start..start + take]);
+            self.leftover_len -= take;
+        }
+        self.remaining -= n as u64;
+        Poll::Ready(Some(Ok(Frame::data(chunk.freeze()))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.remaining == 0
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BodyExt;
+
+    #[tokio::test]
+    async fn chunking_does_not_change_output() {
+        let mut body = RandomBody::new(7, 20).chunk_size(3);
+        assert_eq!(body.size_hint().exact(), Some(20));
+
+        let mut data = Vec::new();
+        while let Some(frame) = body.frame().await {
+            let chunk = frame.unwrap().into_data().unwrap();
+            assert!(chunk.len() <= 3);
+            data.extend_from_slice(&chunk);
+        }
+        assert!(body.is_end_stream());
+
+        let expected = RandomBody::new(7, 20).collect().await.unwrap().to_bytes();
+        assert_eq!(data, expected);
+    }
+
+    #[tokio::test]
+    async fn seeds_differ() {
+        let a = RandomBody::new(1, 64).collect().await.unwrap().to_bytes();
+        let b = RandomBody::new(2, 64).collect().await.unwrap().to_bytes();
+        assert_eq!(a.len(), 64);
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    convert::Infallible,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::{Bytes, BytesMut};
+use http_body::{Body, Frame, SizeHint};
+
+const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
+/// A body that yields a byte pattern repeated up to a total length.
+///
+/// The repeated data is built once, so each frame is a cheap slice of the same buffer regardless
+/// of the total length. This is useful for benchmarks and for testing length limits or flow
+/// control without allocating the whole body.
+///
+/// # Example
+///
+/// ```
+/// use http_body::Body as _;
+/// use http_body_util::{BodyExt, Repeat};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let body = Repeat::new("ab", 5);
+/// assert_eq!(body.size_hint().exact(), Some(5));
+/// assert_eq!(body.collect().await.unwrap().to_bytes(), "ababa");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Repeat {
+    pattern: Bytes,
+    block: Bytes,
+    chunk_size: usize,
+    position: u64,
+    len: u64,
+}
+
+impl Repeat {
+    /// Create a new `Repeat` yielding `pattern` repeated until `len` bytes have been produced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is empty.
+    pub fn new(pattern: impl Into<Bytes>, len: u64) -> Self {
+        let pattern = pattern.into();
+        assert!(!pattern.is_empty(), "pattern must not be empty");
+        Self {
+            block: build_block(&pattern, DEFAULT_CHUNK_SIZE),
+            pattern,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            position: 0,
+            len,
+        }
+    }
+
+    /// Set the maximum size of the data frames yielded by this body.
+    ///
+    /// Defaults to 8 KiB.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+        self.block = build_block(&self.pattern, chunk_size);
+        self.chunk_size = chunk_size;
+        self
+    }
+}
+
+/// Build a buffer of the pattern repeated, long enough to slice a chunk from any offset within
+/// the pattern.
+fn build_block(pattern: &[u8], chunk_size: usize) -> Bytes {
+    let len = chunk_size + pattern.len();
+    let mut block = BytesMut::with_capacity(len);
+    while block.len() < len {
+        block.extend_from_slice(pattern);
+    }
+    block.freeze()
+}
+
+impl Body for Repeat {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let remaining = self.len - self.position;
+        if remaining == 0 {
+            return Poll::Ready(None);
+        }
+
+        let n = remaining.min(self.chunk_size as u64) as usize;
+        let offset = (self.position % self.pattern.len() as u64) as usize;
+        let chunk = self.block.slice(offset..offset + n);
+        self.position += n as u64;
+        Poll::Ready(Some(Ok(Frame::data(chunk))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.position == self.len
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.len - self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BodyExt;
+
+    #[tokio::test]
+    async fn repeats_across_chunks() {
+        let mut body = Repeat::new("abc", 10).chunk_size(4);
+        assert_eq!(body.size_hint().exact(), Some(10));
+
+        let mut chunks = Vec::new();
+        while let Some(frame) = body.frame().await {
+            chunks.push(frame.unwrap().into_data().unwrap());
+        }
+        assert_eq!(chunks, ["abca", "bcab", "ca"]);
+        assert!(body.is_end_stream());
+        assert_eq!(body.size_hint().exact(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn large_body() {
+        let len = DEFAULT_CHUNK_SIZE as u64 * 3 + 1;
+        let data = Repeat::new(vec![7u8], len).collect().await.unwrap();
+        assert_eq!(data.to_bytes(), vec![7u8; len as usize]);
+    }
+
+    #[tokio::test]
+    async fn zero_length() {
+        let mut body = Repeat::new("abc", 0);
+        assert!(body.is_end_stream());
+        assert!(body.frame().await.is_none());
+    }
+}
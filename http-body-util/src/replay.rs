@@ -0,0 +1,254 @@
+use std::{
+    error::Error,
+    fmt,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use http_body::{Body, Frame, SizeHint};
+
+use crate::util::clone_frame;
+
+/// A body that records its frames so it can be replayed from the beginning.
+///
+/// Cloning a `ReplayBody` returns a new handle that starts over from the first frame, replaying
+/// the recorded frames before continuing with the wrapped body. This lets retry and redirect
+/// logic resend a request body without collecting it eagerly.
+///
+/// Only up to `cap` bytes of data are recorded. Once the wrapped body yields more than that, the
+/// recording is discarded and [`is_replayable`] returns `false`; a handle that then needs a frame
+/// that is no longer recorded returns a [`ReplayError`].
+///
+/// [`is_replayable`]: ReplayBody::is_replayable
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body_util::{BodyExt, Full, ReplayBody};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let body = ReplayBody::new(Full::new(Bytes::from("hello")), 1024);
+/// let retry = body.clone();
+///
+/// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+/// assert_eq!(retry.collect().await.unwrap().to_bytes(), "hello");
+/// # }
+/// ```
+pub struct ReplayBody<B: Body> {
+    shared: Arc<Mutex<Shared<B>>>,
+    /// Index of the next frame this handle will yield.
+    position: usize,
+}
+
+struct Shared<B: Body> {
+    body: Pin<Box<B>>,
+    recorded: Vec<Frame<B::Data>>,
+    recorded_bytes: usize,
+    cap: usize,
+    capped: bool,
+    /// Number of frames pulled from the wrapped body so far.
+    polled: usize,
+    ended: bool,
+}
+
+impl<B: Body> ReplayBody<B> {
+    /// Create a new `ReplayBody` recording at most `cap` bytes of data.
+    pub fn new(body: B, cap: usize) -> Self {
+        let shared = Shared {
+            body: Box::pin(body),
+            recorded: Vec::new(),
+            recorded_bytes: 0,
+            cap,
+            capped: false,
+            polled: 0,
+            ended: false,
+        };
+        Self {
+            shared: Arc::new(Mutex::new(shared)),
+            position: 0,
+        }
+    }
+
+    /// Returns whether the body can still be replayed from the beginning.
+    ///
+    /// This becomes `false` once the wrapped body has yielded more than the recording cap.
+    pub fn is_replayable(&self) -> bool {
+        !self.shared.lock().unwrap().capped
+    }
+}
+
+impl<B> Body for ReplayBody<B>
+where
+    B: Body,
+    B::Data: Clone,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = B::Data;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        let mut shared = this.shared.lock().unwrap();
+
+        if this.position < shared.polled {
+            if shared.capped {
+                return Poll::Ready(Some(Err(ReplayError.into())));
+            }
+            let frame = clone_frame(&shared.recorded[this.position]);
+            this.position += 1;
+            return Poll::Ready(Some(Ok(frame)));
+        }
+
+        if shared.ended {
+            return Poll::Ready(None);
+        }
+
+        let frame = match shared.body.as_mut().poll_frame(cx) {
+            Poll::Ready(Some(Ok(frame))) => frame,
+            Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err.into()))),
+            Poll::Ready(None) => {
+                shared.ended = true;
+                return Poll::Ready(None);
+            }
+            Poll::Pending => return Poll::Pending,
+        };
+
+        if !shared.capped {
+            let len = frame.data_ref().map_or(0, Buf::remaining);
+            if shared.recorded_bytes + len > shared.cap {
+                shared.capped = true;
+                shared.recorded = Vec::new();
+            } else {
+                shared.recorded_bytes += len;
+                shared.recorded.push(clone_frame(&frame));
+            }
+        }
+        shared.polled += 1;
+        this.position += 1;
+
+        Poll::Ready(Some(Ok(frame)))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        let shared = self.shared.lock().unwrap();
+        self.position == shared.polled && shared.ended
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let shared = self.shared.lock().unwrap();
+        let inner = if shared.ended {
+            SizeHint::with_exact(0)
+        } else {
+            shared.body.size_hint()
+        };
+        if shared.capped || self.position >= shared.polled {
+            return inner;
+        }
+
+        let replayed = shared.recorded[self.position..]
+            .iter()
+            .filter_map(|frame| frame.data_ref())
+            .map(|data| data.remaining() as u64)
+            .sum::<u64>();
+        let mut hint = SizeHint::new();
+        if let Some(upper) = inner.upper() {
+            hint.set_upper(upper + replayed);
+        }
+        hint.set_lower(inner.lower() + replayed);
+        hint
+    }
+}
+
+impl<B: Body> Clone for ReplayBody<B> {
+    /// Returns a new handle that replays the body from the beginning.
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+            position: 0,
+        }
+    }
+}
+
+impl<B: Body> fmt::Debug for ReplayBody<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplayBody")
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+/// An error returned when a [`ReplayBody`] is replayed after its recording cap was exceeded.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ReplayError;
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("replay buffer capacity exceeded")
+    }
+}
+
+impl Error for ReplayError {}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::{test_body, TestBody};
+    use crate::BodyExt;
+
+    fn body() -> TestBody {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("foo", "bar".parse().unwrap());
+        test_body(&["hello ", "world"], Some(trailers))
+    }
+
+    #[tokio::test]
+    async fn replays_full_body() {
+        let body = ReplayBody::new(body(), 64);
+        let retry = body.clone();
+
+        let first = body.collect().await.unwrap();
+        assert_eq!(first.trailers().unwrap()["foo"], "bar");
+        assert_eq!(first.to_bytes(), "hello world");
+
+        assert_eq!(retry.size_hint().exact(), Some(11));
+        let second = retry.collect().await.unwrap();
+        assert_eq!(second.trailers().unwrap()["foo"], "bar");
+        assert_eq!(second.to_bytes(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn replays_partially_consumed_body() {
+        let mut body = ReplayBody::new(body(), 64);
+        let retry = body.clone();
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "hello ");
+
+        let collected = retry.collect().await.unwrap();
+        assert_eq!(collected.to_bytes(), "hello world");
+        assert!(body.is_replayable());
+    }
+
+    #[tokio::test]
+    async fn errors_after_cap_exceeded() {
+        let mut body = ReplayBody::new(body(), 8);
+        let mut retry = body.clone();
+
+        let collected = (&mut body).collect().await.unwrap();
+        assert_eq!(collected.to_bytes(), "hello world");
+        assert!(!body.is_replayable());
+
+        let error = retry.frame().await.unwrap().unwrap_err();
+        assert!(error.downcast_ref::<ReplayError>().is_some());
+    }
+}
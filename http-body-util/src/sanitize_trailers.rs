@@ -0,0 +1,233 @@
+use http::header::HeaderName;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::util::retain_fields;
+
+pin_project! {
+    /// A body that removes trailer fields which must not be sent as trailers.
+    ///
+    /// [RFC 9110] forbids sending fields needed for message framing, routing, request modifiers,
+    /// authentication, response control or content processing as trailers, because recipients
+    /// could process them after having acted on the headers. A proxy forwarding a body it doesn't
+    /// control should remove them to avoid request smuggling and similar attacks. See
+    /// [`is_forbidden`](SanitizeTrailers::is_forbidden) for the removed fields.
+    ///
+    /// More fields can be removed with [`deny`](SanitizeTrailers::deny). A trailers frame left
+    /// empty is dropped.
+    ///
+    /// [RFC 9110]: https://www.rfc-editor.org/rfc/rfc9110#section-6.5.1
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::{HeaderMap, HeaderValue};
+    /// use http_body_util::{BodyExt, Empty, SanitizeTrailers};
+    /// use bytes::Bytes;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut trailers = HeaderMap::new();
+    /// trailers.insert("content-length", HeaderValue::from_static("0"));
+    /// trailers.insert("grpc-status", HeaderValue::from_static("0"));
+    ///
+    /// let body = Empty::<Bytes>::new().with_trailers(async move {
+    ///     Some(Ok::<_, std::convert::Infallible>(trailers))
+    /// });
+    /// let body = SanitizeTrailers::new(body);
+    ///
+    /// let trailers = body.collect().await.unwrap().into_trailers().unwrap();
+    /// assert!(!trailers.contains_key("content-length"));
+    /// assert!(trailers.contains_key("grpc-status"));
+    /// # }
+    /// ```
+    #[derive(Clone, Debug)]
+    pub struct SanitizeTrailers<B> {
+        denied: HashSet<HeaderName>,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> SanitizeTrailers<B> {
+    /// Create a new `SanitizeTrailers`.
+    pub fn new(inner: B) -> Self {
+        Self {
+            denied: HashSet::new(),
+            inner,
+        }
+    }
+
+    /// Also remove the trailer fields in `names`.
+    pub fn deny<I>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = HeaderName>,
+    {
+        self.denied.extend(names);
+        self
+    }
+
+    /// Returns whether the field `name` must not be sent as a trailer.
+    ///
+    /// These are the fields used for:
+    ///
+    /// - message framing: `Content-Length`, `Transfer-Encoding`, `Trailer`, `TE` and the
+    ///   connection-specific fields `Connection`, `Keep-Alive`, `Proxy-Connection` and `Upgrade`,
+    /// - routing: `Host`,
+    /// - request modifiers: `Cache-Control`, `Expect`, `Max-Forwards`, `Pragma`, `Range` and the
+    ///   `If-*` conditionals,
+    /// - authentication: `Authorization`, `Proxy-Authorization`, `WWW-Authenticate`,
+    ///   `Proxy-Authenticate`, `Authentication-Info`, `Proxy-Authentication-Info`, `Cookie` and
+    ///   `Set-Cookie`,
+    /// - response control: `Age`, `Date`, `Expires`, `Location`, `Retry-After`, `Vary` and
+    ///   `Warning`,
+    /// - content processing: `Content-Encoding`, `Content-Type` and `Content-Range`.
+    pub fn is_forbidden(name: &HeaderName) -> bool {
+        matches!(
+            name.as_str(),
+            "content-length"
+                | "transfer-encoding"
+                | "trailer"
+                | "te"
+                | "connection"
+                | "keep-alive"
+                | "proxy-connection"
+                | "upgrade"
+                | "host"
+                | "cache-control"
+                | "expect"
+                | "max-forwards"
+                | "pragma"
+                | "range"
+                | "if-match"
+                | "if-none-match"
+                | "if-modified-since"
+                | "if-unmodified-since"
+                | "if-range"
+                | "authorization"
+                | "proxy-authorization"
+                | "www-authenticate"
+                | "proxy-authenticate"
+                | "authentication-info"
+                | "proxy-authentication-info"
+                | "cookie"
+                | "set-cookie"
+                | "age"
+                | "date"
+                | "expires"
+                | "location"
+                | "retry-after"
+                | "vary"
+                | "warning"
+                | "content-encoding"
+                | "content-type"
+                | "content-range"
+        )
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: Body> Body for SanitizeTrailers<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        loop {
+            let frame = match this.inner.as_mut().poll_frame(cx) {
+                Poll::Ready(Some(Ok(frame))) => frame,
+                res => return res,
+            };
+
+            let trailers = match frame.into_trailers() {
+                Ok(trailers) => trailers,
+                Err(frame) => return Poll::Ready(Some(Ok(frame))),
+            };
+
+            let trailers = retain_fields(trailers, |name| {
+                let removed = Self::is_forbidden(name) || this.denied.contains(name);
+                if removed {
+                    debug!(name = name.as_str(), "sanitize_trailers: removed trailer");
+                }
+                !removed
+            });
+
+            if !trailers.is_empty() {
+                return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_body;
+    use crate::BodyExt;
+    use http::{HeaderMap, HeaderValue};
+
+    #[tokio::test]
+    async fn removes_forbidden_fields() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("Transfer-Encoding", HeaderValue::from_static("chunked"));
+        trailers.insert("host", HeaderValue::from_static("example.com"));
+        trailers.append("set-cookie", HeaderValue::from_static("a=1"));
+        trailers.append("set-cookie", HeaderValue::from_static("b=2"));
+        trailers.insert("x-checksum", HeaderValue::from_static("abc"));
+        trailers.insert("x-internal", HeaderValue::from_static("1"));
+
+        let body = SanitizeTrailers::new(test_body(&["hello"], Some(trailers)))
+            .deny(vec![HeaderName::from_static("x-internal")]);
+        let collected = body.collect().await.unwrap();
+
+        let mut expected = HeaderMap::new();
+        expected.insert("x-checksum", HeaderValue::from_static("abc"));
+        assert_eq!(collected.trailers(), Some(&expected));
+        assert_eq!(collected.to_bytes(), "hello");
+    }
+
+    #[tokio::test]
+    async fn drops_empty_trailers() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("content-length", HeaderValue::from_static("5"));
+
+        let collected = SanitizeTrailers::new(test_body(&["hello"], Some(trailers)))
+            .collect()
+            .await
+            .unwrap();
+        assert!(collected.trailers().is_none());
+    }
+}
@@ -0,0 +1,108 @@
+use std::{
+    ops::DerefMut,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use http_body::Frame;
+
+/// A consumer of body frames, the write side counterpart of [`Body`](http_body::Body).
+///
+/// This mirrors the [`Sink`] trait of the futures crate for [`Frame`]s, so middleware that
+/// consumes frames can be written once for any destination, such as a `channel::Sender` or an
+/// `AsyncWriteSink`.
+///
+/// A frame is sent in two steps: [`poll_ready`] must return `Poll::Ready(Ok(()))` before each
+/// call to [`send_frame`]. Sent frames may be buffered until [`poll_flush`] or [`poll_close`]
+/// completes.
+///
+/// [`Sink`]: https://docs.rs/futures/latest/futures/sink/trait.Sink.html
+/// [`poll_ready`]: BodySink::poll_ready
+/// [`send_frame`]: BodySink::send_frame
+/// [`poll_flush`]: BodySink::poll_flush
+/// [`poll_close`]: BodySink::poll_close
+pub trait BodySink<D> {
+    /// The error type this sink might generate.
+    type Error;
+
+    /// Attempt to prepare the sink to receive a frame.
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+
+    /// Send a frame to the sink.
+    ///
+    /// This must be preceded by a call to [`poll_ready`](BodySink::poll_ready) that returned
+    /// `Poll::Ready(Ok(()))`.
+    fn send_frame(self: Pin<&mut Self>, frame: Frame<D>) -> Result<(), Self::Error>;
+
+    /// Attempt to flush the frames sent to the sink.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+
+    /// Attempt to flush and close the sink.
+    ///
+    /// No frames can be sent once this returns `Poll::Ready(Ok(()))`.
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+}
+
+impl<S: BodySink<D> + Unpin + ?Sized, D> BodySink<D> for &mut S {
+    type Error = S::Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_ready(cx)
+    }
+
+    fn send_frame(mut self: Pin<&mut Self>, frame: Frame<D>) -> Result<(), Self::Error> {
+        Pin::new(&mut **self).send_frame(frame)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_close(cx)
+    }
+}
+
+impl<P, D> BodySink<D> for Pin<P>
+where
+    P: DerefMut + Unpin,
+    P::Target: BodySink<D>,
+{
+    type Error = <P::Target as BodySink<D>>::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::get_mut(self).as_mut().poll_ready(cx)
+    }
+
+    fn send_frame(self: Pin<&mut Self>, frame: Frame<D>) -> Result<(), Self::Error> {
+        Pin::get_mut(self).as_mut().send_frame(frame)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::get_mut(self).as_mut().poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::get_mut(self).as_mut().poll_close(cx)
+    }
+}
+
+impl<S: BodySink<D> + Unpin + ?Sized, D> BodySink<D> for Box<S> {
+    type Error = S::Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_ready(cx)
+    }
+
+    fn send_frame(mut self: Pin<&mut Self>, frame: Frame<D>) -> Result<(), Self::Error> {
+        Pin::new(&mut **self).send_frame(frame)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_close(cx)
+    }
+}
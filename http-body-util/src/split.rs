@@ -0,0 +1,335 @@
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use futures_core::{ready, Stream};
+use http::HeaderMap;
+use http_body::{Body, Frame, SizeHint};
+
+/// Splits a body into parts separated by a delimiter.
+///
+/// Each part is itself a body, [`SplitPart`], which streams its data as the wrapped body is read
+/// instead of buffering the whole part. Like [`str::split`], a body with `n` delimiters has
+/// `n + 1` parts, some of which may be empty. The trailers of the wrapped body are yielded by the
+/// last part.
+///
+/// Parts are read in order: getting the next part skips whatever is left of the current one, which
+/// then ends. Parts are returned by [`next_part`](Split::next_part), or by the [`Stream`]
+/// implementation.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body_util::{BodyExt, Full, Split};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let mut parts = Split::new(Full::new(Bytes::from("one\r\ntwo")), "\r\n");
+///
+/// let mut records = Vec::new();
+/// while let Some(part) = parts.next_part().await {
+///     records.push(part.unwrap().collect().await.unwrap().to_bytes());
+/// }
+/// assert_eq!(records, ["one", "two"]);
+/// # }
+/// ```
+pub struct Split<B> {
+    shared: Arc<Mutex<Shared<B>>>,
+    next: usize,
+}
+
+/// A part of a body split by [`Split`].
+pub struct SplitPart<B> {
+    shared: Arc<Mutex<Shared<B>>>,
+    index: usize,
+}
+
+struct Shared<B> {
+    body: Pin<Box<B>>,
+    delimiter: Bytes,
+    buf: BytesMut,
+    /// The index of the part being read.
+    part: usize,
+    trailers: Option<HeaderMap>,
+    body_done: bool,
+    /// Whether the last part has ended.
+    done: bool,
+}
+
+impl<B> Split<B> {
+    /// Create a new `Split` of `body` separated by `delimiter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delimiter` is empty.
+    pub fn new(body: B, delimiter: impl Into<Bytes>) -> Self {
+        let delimiter = delimiter.into();
+        assert!(!delimiter.is_empty(), "delimiter must not be empty");
+        Self {
+            shared: Arc::new(Mutex::new(Shared {
+                body: Box::pin(body),
+                delimiter,
+                buf: BytesMut::new(),
+                part: 0,
+                trailers: None,
+                body_done: false,
+                done: false,
+            })),
+            next: 0,
+        }
+    }
+}
+
+impl<B: Body> Split<B> {
+    /// Wait for the next part, skipping the rest of the current one.
+    ///
+    /// Returns `None` once every part was returned.
+    pub fn next_part(&mut self) -> NextPart<'_, B> {
+        NextPart(self)
+    }
+
+    /// Poll for the next part, skipping the rest of the current one.
+    pub fn poll_next_part(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<SplitPart<B>, B::Error>>> {
+        let mut shared = self.shared.lock().unwrap();
+
+        // Skip what is left of the current part.
+        while !shared.done && shared.part < self.next {
+            if let Some(Err(err)) = ready!(shared.poll_part(cx)) {
+                return Poll::Ready(Some(Err(err)));
+            }
+        }
+        if shared.done {
+            return Poll::Ready(None);
+        }
+
+        let index = self.next;
+        self.next += 1;
+        Poll::Ready(Some(Ok(SplitPart {
+            shared: self.shared.clone(),
+            index,
+        })))
+    }
+}
+
+impl<B: Body> Stream for Split<B> {
+    type Item = Result<SplitPart<B>, B::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_next_part(cx)
+    }
+}
+
+impl<B> fmt::Debug for Split<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Split").field("next", &self.next).finish()
+    }
+}
+
+impl<B: Body> Shared<B> {
+    /// Poll the next frame of the current part.
+    ///
+    /// Returns `None` once the part has ended, after which the next part is the current one.
+    fn poll_part(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, B::Error>>> {
+        loop {
+            if let Some(pos) = find(&self.buf, &self.delimiter) {
+                if pos > 0 {
+                    return Poll::Ready(Some(Ok(Frame::data(self.buf.split_to(pos).freeze()))));
+                }
+                self.buf.advance(self.delimiter.len());
+                self.part += 1;
+                return Poll::Ready(None);
+            }
+
+            if self.body_done {
+                if !self.buf.is_empty() {
+                    return Poll::Ready(Some(Ok(Frame::data(self.buf.split().freeze()))));
+                }
+                if let Some(trailers) = self.trailers.take() {
+                    return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+                }
+                self.done = true;
+                return Poll::Ready(None);
+            }
+
+            // Keep the bytes that may be the start of a delimiter split across frames.
+            let keep = self.delimiter.len() - 1;
+            if self.buf.len() > keep {
+                let len = self.buf.len() - keep;
+                return Poll::Ready(Some(Ok(Frame::data(self.buf.split_to(len).freeze()))));
+            }
+
+            match ready!(self.body.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(mut data) => {
+                        while data.has_remaining() {
+                            let chunk = data.chunk();
+                            self.buf.extend_from_slice(chunk);
+                            let len = chunk.len();
+                            data.advance(len);
+                        }
+                    }
+                    Err(frame) => {
+                        if let Ok(trailers) = frame.into_trailers() {
+                            self.trailers = Some(trailers);
+                        }
+                    }
+                },
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => self.body_done = true,
+            }
+        }
+    }
+}
+
+/// Returns the position of the first occurrence of `needle` in `haystack`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+impl<B: Body> Body for SplitPart<B> {
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut shared = self.shared.lock().unwrap();
+        if shared.done || shared.part != self.index {
+            return Poll::Ready(None);
+        }
+        shared.poll_part(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        let shared = self.shared.lock().unwrap();
+        shared.done || shared.part != self.index
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let mut hint = SizeHint::new();
+        if self.is_end_stream() {
+            hint.set_exact(0);
+        }
+        hint
+    }
+}
+
+impl<B> fmt::Debug for SplitPart<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SplitPart")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// Future returned by [`Split::next_part`].
+#[must_use = "futures don't do anything unless polled"]
+pub struct NextPart<'a, B>(&'a mut Split<B>);
+
+impl<B> fmt::Debug for NextPart<'_, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NextPart").finish()
+    }
+}
+
+impl<B: Body> Future for NextPart<'_, B> {
+    type Output = Option<Result<SplitPart<B>, B::Error>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_next_part(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+    use crate::test_support::test_body;
+    use crate::{BodyExt, Empty};
+
+    async fn parts<B>(mut split: Split<B>) -> Vec<Bytes>
+    where
+        B: Body,
+        B::Error: fmt::Debug,
+    {
+        let mut parts = Vec::new();
+        while let Some(part) = split.next_part().await {
+            parts.push(part.unwrap().collect().await.unwrap().to_bytes());
+        }
+        parts
+    }
+
+    #[tokio::test]
+    async fn delimiter_across_frames() {
+        let body = test_body(&["ab-", "-cd-", "", "-", "-ef", "-"], None);
+        let parts = parts(Split::new(body, "--")).await;
+        assert_eq!(parts, ["ab", "cd", "-ef-"]);
+    }
+
+    #[tokio::test]
+    async fn like_str_split() {
+        for input in ["", ",", "a", "a,", ",a,,b"] {
+            let parts = parts(Split::new(test_body(&[input], None), ",")).await;
+            let expected: Vec<_> = input.split(',').collect();
+            assert_eq!(parts, expected, "input: {:?}", input);
+        }
+
+        let parts = parts(Split::new(Empty::<Bytes>::new(), ",")).await;
+        assert_eq!(parts, [""]);
+    }
+
+    #[tokio::test]
+    async fn streams_parts() {
+        let body = test_body(&["hello ", "world", "|next"], None);
+        let mut split = Split::new(body, "|");
+
+        let mut part = split.next_part().await.unwrap().unwrap();
+        let data = part.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "hello ");
+        let data = part.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "world");
+        assert!(part.frame().await.is_none());
+        assert!(part.is_end_stream());
+    }
+
+    #[tokio::test]
+    async fn skips_unread_parts() {
+        let body = test_body(&["skip ", "me|", "read|", "skip|last"], None);
+        let mut split = Split::new(body, "|");
+
+        let first = split.next_part().await.unwrap().unwrap();
+        let second = split.next_part().await.unwrap().unwrap();
+        assert!(first.is_end_stream());
+        assert_eq!(second.collect().await.unwrap().to_bytes(), "read");
+
+        split.next_part().await.unwrap().unwrap();
+        let last = split.next_part().await.unwrap().unwrap();
+        assert_eq!(last.collect().await.unwrap().to_bytes(), "last");
+        assert!(split.next_part().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn last_part_has_trailers() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-done", HeaderValue::from_static("1"));
+        let mut split = Split::new(test_body(&["a|b"], Some(trailers.clone())), "|");
+
+        let first = split.next_part().await.unwrap().unwrap();
+        assert!(first.collect().await.unwrap().trailers().is_none());
+        let last = split.next_part().await.unwrap().unwrap();
+        assert_eq!(last.collect().await.unwrap().trailers(), Some(&trailers));
+    }
+}
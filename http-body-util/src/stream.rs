@@ -1,5 +1,6 @@
 use bytes::Buf;
 use futures_core::{ready, stream::Stream};
+use http::HeaderMap;
 use http_body::{Body, Frame};
 use pin_project_lite::pin_project;
 use std::{
@@ -36,8 +37,25 @@ where
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
         match self.project().stream.poll_next(cx) {
-            Poll::Ready(Some(result)) => Poll::Ready(Some(result)),
-            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Ready(Some(result)) => {
+                if result.is_ok() {
+                    trace!(
+                        bytes = result
+                            .as_ref()
+                            .ok()
+                            .and_then(Frame::data_ref)
+                            .map_or(0, Buf::remaining),
+                        "stream body: frame"
+                    );
+                } else {
+                    debug!("stream body: stream error");
+                }
+                Poll::Ready(Some(result))
+            }
+            Poll::Ready(None) => {
+                trace!("stream body: end of stream");
+                Poll::Ready(None)
+            }
             Poll::Pending => Poll::Pending,
         }
     }
@@ -103,6 +121,11 @@ where
 
 pin_project! {
     /// A data stream created from a [`Body`].
+    ///
+    /// The stream yields only the data of the body and skips trailers. To keep the trailers, see
+    /// [`keep_trailers`].
+    ///
+    /// [`keep_trailers`]: BodyDataStream::keep_trailers
     #[derive(Clone, Copy, Debug)]
     pub struct BodyDataStream<B> {
         #[pin]
@@ -115,6 +138,31 @@ impl<B> BodyDataStream<B> {
     pub fn new(body: B) -> Self {
         Self { body }
     }
+
+    /// Keep the trailers skipped by the stream, so they can be retrieved once it has ended.
+    pub fn keep_trailers(self) -> BodyDataStreamWithTrailers<B> {
+        BodyDataStreamWithTrailers::new(self.body)
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.body
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.body
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().body
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.body
+    }
 }
 
 impl<B> Stream for BodyDataStream<B>
@@ -137,11 +185,93 @@ where
     }
 }
 
+pin_project! {
+    /// A data stream created from a [`Body`], keeping its trailers.
+    ///
+    /// The stream yields only the data of the body. Trailers are not yielded, but the last
+    /// trailers frame is kept and can be retrieved with [`trailers`] once the stream has ended.
+    ///
+    /// [`trailers`]: BodyDataStreamWithTrailers::trailers
+    #[derive(Clone, Debug)]
+    pub struct BodyDataStreamWithTrailers<B> {
+        #[pin]
+        body: B,
+        trailers: Option<HeaderMap>,
+    }
+}
+
+impl<B> BodyDataStreamWithTrailers<B> {
+    /// Create a new `BodyDataStreamWithTrailers`
+    pub fn new(body: B) -> Self {
+        Self {
+            body,
+            trailers: None,
+        }
+    }
+
+    /// Returns the trailers skipped by the stream, if any.
+    pub fn trailers(&self) -> Option<&HeaderMap> {
+        self.trailers.as_ref()
+    }
+
+    /// Take the trailers skipped by the stream, if any.
+    pub fn take_trailers(&mut self) -> Option<HeaderMap> {
+        self.trailers.take()
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.body
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.body
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().body
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.body
+    }
+}
+
+impl<B> Stream for BodyDataStreamWithTrailers<B>
+where
+    B: Body,
+{
+    type Item = Result<B::Data, B::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let this = self.as_mut().project();
+            return match ready!(this.body.poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(bytes) => Poll::Ready(Some(Ok(bytes))),
+                    Err(frame) => {
+                        if let Ok(trailers) = frame.into_trailers() {
+                            *this.trailers = Some(trailers);
+                        }
+                        continue;
+                    }
+                },
+                Some(Err(err)) => Poll::Ready(Some(Err(err))),
+                None => Poll::Ready(None),
+            };
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{BodyExt, BodyStream, StreamBody};
+    use crate::{BodyDataStream, BodyExt, BodyStream, StreamBody};
     use bytes::Bytes;
     use futures_util::StreamExt;
+    use http::HeaderMap;
     use http_body::Frame;
     use std::convert::Infallible;
 
@@ -237,4 +367,34 @@ mod tests {
 
         assert!(stream.next().await.is_none());
     }
+
+    #[tokio::test]
+    async fn data_stream_keeps_trailers() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", "0".parse().unwrap());
+        let chunks: Vec<Result<_, Infallible>> = vec![
+            Ok(Frame::data(Bytes::from("hello"))),
+            Ok(Frame::data(Bytes::from(" world"))),
+            Ok(Frame::trailers(trailers)),
+        ];
+        let body = StreamBody::new(futures_util::stream::iter(chunks));
+
+        let mut stream = BodyDataStream::new(body).keep_trailers();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "hello");
+        assert_eq!(stream.next().await.unwrap().unwrap(), " world");
+        assert!(stream.trailers().is_none());
+        assert!(stream.next().await.is_none());
+
+        assert_eq!(stream.trailers().unwrap()["grpc-status"], "0");
+        assert_eq!(stream.take_trailers().unwrap()["grpc-status"], "0");
+        assert!(stream.trailers().is_none());
+    }
+
+    #[tokio::test]
+    async fn data_stream_from_body_ext() {
+        let body = crate::Full::new(Bytes::from("hello"));
+        let data: Vec<_> = body.into_data_stream().collect().await;
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0].as_ref().unwrap(), "hello");
+    }
 }
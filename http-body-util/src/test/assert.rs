@@ -0,0 +1,183 @@
+use std::fmt::{self, Write};
+
+use bytes::Bytes;
+use http::HeaderMap;
+use http_body::Body;
+
+use crate::BodyExt;
+
+/// The number of bytes shown around the first difference.
+const CONTEXT: usize = 16;
+
+/// Collect two bodies and assert that their data and trailers are equal.
+///
+/// On mismatch, the panic message shows the offset of the first differing byte and the data
+/// around it. Panics if either body yields an error.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body_util::test::{assert_body_eq, MockBody};
+/// use http_body_util::Full;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let body = MockBody::new().data("hel").data("lo");
+/// assert_body_eq(body, Full::new(Bytes::from("hello"))).await;
+/// # }
+/// ```
+pub async fn assert_body_eq<L, R>(left: L, right: R)
+where
+    L: Body,
+    L::Error: fmt::Debug,
+    R: Body,
+    R::Error: fmt::Debug,
+{
+    let (left_data, left_trailers) = collect("left", left).await;
+    let (right_data, right_trailers) = collect("right", right).await;
+    assert_parts_eq(&left_data, left_trailers, &right_data, right_trailers);
+}
+
+/// Collect a body and assert that its data and trailers are equal to the expected ones.
+///
+/// On mismatch, the panic message shows the offset of the first differing byte and the data
+/// around it. Panics if the body yields an error.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use http_body_util::{test::assert_body_data_eq, Full};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// assert_body_data_eq(Full::new(Bytes::from("hello")), "hello", None).await;
+/// # }
+/// ```
+pub async fn assert_body_data_eq<B>(
+    body: B,
+    expected: impl AsRef<[u8]>,
+    expected_trailers: Option<HeaderMap>,
+) where
+    B: Body,
+    B::Error: fmt::Debug,
+{
+    let (data, trailers) = collect("body", body).await;
+    assert_parts_eq(&data, trailers, expected.as_ref(), expected_trailers);
+}
+
+async fn collect<B>(name: &str, body: B) -> (Bytes, Option<HeaderMap>)
+where
+    B: Body,
+    B::Error: fmt::Debug,
+{
+    match body.collect().await {
+        Ok(collected) => collected.into_parts(),
+        Err(err) => panic!("{} body yielded an error: {:?}", name, err),
+    }
+}
+
+fn assert_parts_eq(
+    left: &[u8],
+    left_trailers: Option<HeaderMap>,
+    right: &[u8],
+    right_trailers: Option<HeaderMap>,
+) {
+    if let Some(message) = diff(left, right) {
+        panic!("{}", message);
+    }
+    assert_eq!(left_trailers, right_trailers, "body trailers differ");
+}
+
+/// Describe the first difference between `left` and `right`, if any.
+fn diff(left: &[u8], right: &[u8]) -> Option<String> {
+    let offset = match left.iter().zip(right).position(|(l, r)| l != r) {
+        Some(offset) => offset,
+        None if left.len() == right.len() => return None,
+        None => left.len().min(right.len()),
+    };
+
+    let start = offset.saturating_sub(CONTEXT);
+    let excerpt = |data: &[u8]| {
+        let end = data.len().min(offset + CONTEXT);
+        let mut excerpt = String::new();
+        if start > 0 {
+            excerpt.push_str("...");
+        }
+        for &byte in &data[start.min(data.len())..end] {
+            write!(excerpt, "{}", std::ascii::escape_default(byte)).unwrap();
+        }
+        if end < data.len() {
+            excerpt.push_str("...");
+        }
+        excerpt
+    };
+
+    Some(format!(
+        "body data differs at byte {} (left is {} bytes, right is {} bytes)\n  left: \"{}\"\n right: \"{}\"",
+        offset,
+        left.len(),
+        right.len(),
+        excerpt(left),
+        excerpt(right),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Full;
+
+    #[test]
+    fn diff_reports_offset() {
+        assert_eq!(diff(b"hello", b"hello"), None);
+        assert_eq!(
+            diff(b"hello", b"help!"),
+            Some(
+                "body data differs at byte 3 (left is 5 bytes, right is 5 bytes)\n  left: \"hello\"\n right: \"help!\""
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            diff(b"hello", b"hello\n"),
+            Some(
+                "body data differs at byte 5 (left is 5 bytes, right is 6 bytes)\n  left: \"hello\"\n right: \"hello\\n\""
+                    .to_owned()
+            )
+        );
+
+        let long = [b'a'; 40];
+        let mut other = long;
+        other[20] = b'b';
+        let message = diff(&long, &other).unwrap();
+        assert!(message.contains(&format!("left: \"...{}...\"", "a".repeat(32))));
+    }
+
+    #[tokio::test]
+    async fn equal_bodies() {
+        assert_body_eq(
+            Full::new(Bytes::from("hello")),
+            Full::new(Bytes::from("hello")),
+        )
+        .await;
+        assert_body_data_eq(Full::new(Bytes::from("hello")), b"hello", None).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "body data differs at byte 1")]
+    async fn different_bodies() {
+        assert_body_data_eq(Full::new(Bytes::from("hello")), "hallo", None).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "body trailers differ")]
+    async fn different_trailers() {
+        assert_body_data_eq(
+            Full::new(Bytes::from("hello")),
+            "hello",
+            Some(HeaderMap::new()),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,438 @@
+use std::{
+    any::type_name,
+    error::Error,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A body that checks that the wrapped body upholds the contract of [`Body`].
+    ///
+    /// The following invariants are checked as frames are polled:
+    ///
+    /// - no data frame is yielded after a trailers frame,
+    /// - no frame is yielded after the body returned `None`,
+    /// - no frame is yielded after `is_end_stream` returned `true`,
+    /// - data frames fit in the upper bound of the size hint, and the upper bound doesn't grow,
+    /// - the body doesn't end before the lower bound of the size hint is reached.
+    ///
+    /// By default a violation panics with a message naming the wrapped body type, the number of
+    /// frames yielded so far, and the violated invariant. With
+    /// [`error_on_violation`](Checked::error_on_violation), an [`InvariantError`] is returned
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http_body_util::test::{Checked, MockBody};
+    /// use http_body_util::BodyExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Checked::new(MockBody::new().data("hello").pending().data("world"));
+    /// body.collect().await.unwrap();
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct Checked<B> {
+        frames: usize,
+        trailers: bool,
+        ended: bool,
+        error: bool,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> Checked<B> {
+    /// Create a new `Checked`.
+    pub fn new(inner: B) -> Self {
+        Self {
+            frames: 0,
+            trailers: false,
+            ended: false,
+            error: false,
+            inner,
+        }
+    }
+
+    /// Return an [`InvariantError`] instead of panicking when an invariant is violated.
+    pub fn error_on_violation(mut self, enabled: bool) -> Self {
+        self.error = enabled;
+        self
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for Checked<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = B::Data;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        let end_stream = this.inner.is_end_stream();
+        let before = this.inner.size_hint();
+
+        let violation = match this.inner.as_mut().poll_frame(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err.into()))),
+            Poll::Ready(None) => {
+                *this.ended = true;
+                if before.lower() > 0 {
+                    Violation::EndedBeforeLowerBound {
+                        lower: before.lower(),
+                    }
+                } else {
+                    return Poll::Ready(None);
+                }
+            }
+            Poll::Ready(Some(Ok(frame))) => {
+                let violation = if *this.ended {
+                    Some(Violation::FrameAfterEnd)
+                } else if end_stream {
+                    Some(Violation::FrameAfterEndStream)
+                } else if let Some(data) = frame.data_ref() {
+                    check_data(*this.trailers, data.remaining() as u64, &before, || {
+                        this.inner.size_hint()
+                    })
+                } else {
+                    *this.trailers |= frame.is_trailers();
+                    None
+                };
+
+                match violation {
+                    Some(violation) => violation,
+                    None => {
+                        *this.frames += 1;
+                        return Poll::Ready(Some(Ok(frame)));
+                    }
+                }
+            }
+        };
+
+        if !*this.error {
+            panic!(
+                "body invariant violated by `{}` after {} frames: {}",
+                type_name::<B>(),
+                this.frames,
+                violation
+            );
+        }
+        let err = InvariantError {
+            frames: *this.frames,
+            violation,
+        };
+        Poll::Ready(Some(Err(err.into())))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+fn check_data(
+    trailers: bool,
+    len: u64,
+    before: &SizeHint,
+    after: impl FnOnce() -> SizeHint,
+) -> Option<Violation> {
+    if trailers {
+        return Some(Violation::DataAfterTrailers);
+    }
+
+    let upper = before.upper()?;
+    if len > upper {
+        return Some(Violation::SizeHintExceeded { upper, len });
+    }
+    let after = after().upper();
+    if after.map_or(true, |after| after > upper - len) {
+        return Some(Violation::SizeHintGrew { upper, len, after });
+    }
+    None
+}
+
+/// A violation of the contract of [`Body`], detected by [`Checked`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Violation {
+    /// A data frame was yielded after a trailers frame.
+    DataAfterTrailers,
+    /// A frame was yielded after the body returned `None`.
+    FrameAfterEnd,
+    /// A frame was yielded after `is_end_stream` returned `true`.
+    FrameAfterEndStream,
+    /// A data frame was larger than the upper bound of the size hint.
+    SizeHintExceeded {
+        /// The upper bound before the frame.
+        upper: u64,
+        /// The length of the frame.
+        len: u64,
+    },
+    /// The upper bound of the size hint grew after a data frame.
+    SizeHintGrew {
+        /// The upper bound before the frame.
+        upper: u64,
+        /// The length of the frame.
+        len: u64,
+        /// The upper bound after the frame.
+        after: Option<u64>,
+    },
+    /// The body ended before the lower bound of the size hint was reached.
+    EndedBeforeLowerBound {
+        /// The lower bound before the end.
+        lower: u64,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::DataAfterTrailers => f.write_str("data frame after trailers"),
+            Violation::FrameAfterEnd => f.write_str("frame after the end of the body"),
+            Violation::FrameAfterEndStream => {
+                f.write_str("frame after `is_end_stream` returned `true`")
+            }
+            Violation::SizeHintExceeded { upper, len } => write!(
+                f,
+                "data frame of {len} bytes exceeds the size hint upper bound of {upper} bytes"
+            ),
+            Violation::SizeHintGrew { upper, len, after } => {
+                write!(
+                    f,
+                    "size hint upper bound grew from {upper} bytes to {} after a data frame of {len} bytes",
+                    match after {
+                        Some(after) => format!("{after} bytes"),
+                        None => "unbounded".to_owned(),
+                    }
+                )
+            }
+            Violation::EndedBeforeLowerBound { lower } => write!(
+                f,
+                "body ended before the size hint lower bound of {lower} more bytes"
+            ),
+        }
+    }
+}
+
+/// An error returned by [`Checked`] when the wrapped body violates the contract of [`Body`].
+#[derive(Debug)]
+pub struct InvariantError {
+    frames: usize,
+    violation: Violation,
+}
+
+impl InvariantError {
+    /// Returns the violated invariant.
+    pub fn violation(&self) -> &Violation {
+        &self.violation
+    }
+
+    /// Returns the number of frames yielded before the violation.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "body invariant violated after {} frames: {}",
+            self.frames, self.violation
+        )
+    }
+}
+
+impl Error for InvariantError {}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::{test::MockBody, BodyExt, Full};
+
+    async fn violation<B>(body: B) -> Violation
+    where
+        B: Body + Unpin,
+        B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let mut body = Checked::new(body).error_on_violation(true);
+        loop {
+            match body.frame().await {
+                Some(Ok(_)) => {}
+                Some(Err(err)) => {
+                    return err.downcast::<InvariantError>().unwrap().violation;
+                }
+                None => panic!("no violation"),
+            }
+        }
+    }
+
+    /// A body with a fixed size hint.
+    struct Hinted<B>(B, SizeHint);
+
+    impl<B: Body + Unpin> Body for Hinted<B> {
+        type Data = B::Data;
+        type Error = B::Error;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+            Pin::new(&mut self.0).poll_frame(cx)
+        }
+
+        fn size_hint(&self) -> SizeHint {
+            self.1.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn valid_bodies() {
+        let body = MockBody::new()
+            .data("a")
+            .pending()
+            .trailers(HeaderMap::new());
+        Checked::new(body).collect().await.unwrap();
+        Checked::new(Full::new(Bytes::from("abc")))
+            .collect()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn data_after_trailers() {
+        let body = MockBody::new().trailers(HeaderMap::new()).data("a");
+        assert_eq!(violation(body).await, Violation::DataAfterTrailers);
+    }
+
+    #[tokio::test]
+    async fn frame_after_end() {
+        /// A body which yields a frame after ending.
+        struct Resumed(bool);
+
+        impl Body for Resumed {
+            type Data = Bytes;
+            type Error = Infallible;
+
+            fn poll_frame(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+                if std::mem::replace(&mut self.0, true) {
+                    Poll::Ready(Some(Ok(Frame::data(Bytes::from("a")))))
+                } else {
+                    Poll::Ready(None)
+                }
+            }
+        }
+
+        let mut body = Checked::new(Resumed(false)).error_on_violation(true);
+        assert!(body.frame().await.is_none());
+        let err = body.frame().await.unwrap().unwrap_err();
+        let err = err.downcast_ref::<InvariantError>().unwrap();
+        assert_eq!(err.violation(), &Violation::FrameAfterEnd);
+        assert_eq!(err.frames(), 0);
+    }
+
+    #[tokio::test]
+    async fn frame_after_end_stream() {
+        /// A body which claims to be done after its first frame.
+        struct Lying(MockBody);
+
+        impl Body for Lying {
+            type Data = Bytes;
+            type Error = Infallible;
+
+            fn poll_frame(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+                Pin::new(&mut self.0).poll_frame(cx)
+            }
+
+            fn is_end_stream(&self) -> bool {
+                self.0.polls() > 0
+            }
+        }
+
+        let body = Lying(MockBody::new().data("a").data("b"));
+        assert_eq!(violation(body).await, Violation::FrameAfterEndStream);
+    }
+
+    #[tokio::test]
+    async fn size_hint() {
+        let body = Hinted(MockBody::new().data("abc"), SizeHint::with_exact(2));
+        assert_eq!(
+            violation(body).await,
+            Violation::SizeHintExceeded { upper: 2, len: 3 }
+        );
+
+        let body = Hinted(MockBody::new().data("a"), SizeHint::with_exact(2));
+        assert_eq!(
+            violation(body).await,
+            Violation::SizeHintGrew {
+                upper: 2,
+                len: 1,
+                after: Some(2)
+            }
+        );
+
+        let mut hint = SizeHint::new();
+        hint.set_lower(1);
+        let body = Hinted(MockBody::new(), hint);
+        assert_eq!(
+            violation(body).await,
+            Violation::EndedBeforeLowerBound { lower: 1 }
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "after 1 frames: data frame after trailers")]
+    async fn panics_by_default() {
+        let body = MockBody::new()
+            .trailers(HeaderMap::new())
+            .data("a")
+            .allow_unconsumed();
+        let _ = Checked::new(body).collect().await;
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    fmt,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::ready;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+use crate::timer::{Sleep, Timer, TokioTimer};
+
+type DelayFn = Box<dyn FnMut(usize) -> Duration + Send>;
+
+pin_project! {
+    /// A body that waits before yielding each frame of the wrapped body.
+    ///
+    /// The delay is applied before the wrapped body is polled for each frame, and before it is
+    /// polled for its end. With the default [`TokioTimer`], the delays follow tokio's paused time,
+    /// which makes tests of timeouts and throttling deterministic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use http_body_util::test::{Delayed, MockBody};
+    /// use http_body_util::BodyExt;
+    ///
+    /// # #[tokio::main(flavor = "current_thread", start_paused = true)]
+    /// # async fn main() {
+    /// let body = Delayed::new(MockBody::new().data("a").data("b"), Duration::from_secs(1));
+    ///
+    /// let start = tokio::time::Instant::now();
+    /// body.collect().await.unwrap();
+    /// assert_eq!(start.elapsed(), Duration::from_secs(3));
+    /// # }
+    /// ```
+    pub struct Delayed<B> {
+        delay: DelayFn,
+        frames: usize,
+        timer: Arc<dyn Timer + Send + Sync>,
+        sleep: Option<Pin<Box<dyn Sleep>>>,
+        slept: bool,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> Delayed<B> {
+    /// Create a new `Delayed` waiting for `delay` before each frame.
+    pub fn new(inner: B, delay: Duration) -> Self {
+        Self::with_delays(inner, move |_| delay)
+    }
+
+    /// Create a new `Delayed` waiting before each frame for the duration returned by `delay`.
+    ///
+    /// `delay` is called with the index of the frame, starting at zero.
+    pub fn with_delays<F>(inner: B, delay: F) -> Self
+    where
+        F: FnMut(usize) -> Duration + Send + 'static,
+    {
+        Self {
+            delay: Box::new(delay),
+            frames: 0,
+            timer: Arc::new(TokioTimer),
+            sleep: None,
+            slept: false,
+            inner,
+        }
+    }
+
+    /// Set the timer used to wait.
+    pub fn timer<T>(mut self, timer: T) -> Self
+    where
+        T: Timer + Send + Sync + 'static,
+    {
+        self.timer = Arc::new(timer);
+        self.sleep = None;
+        self
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: Body> Body for Delayed<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+
+        if !*this.slept {
+            if this.sleep.is_none() {
+                let delay = (this.delay)(*this.frames);
+                let deadline = this.timer.now() + delay;
+                *this.sleep = Some(this.timer.sleep_until(deadline));
+            }
+            ready!(this.sleep.as_mut().unwrap().as_mut().poll(cx));
+            *this.sleep = None;
+            *this.slept = true;
+        }
+
+        let frame = ready!(this.inner.poll_frame(cx));
+        *this.frames += 1;
+        *this.slept = false;
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B: fmt::Debug> fmt::Debug for Delayed<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Delayed")
+            .field("frames", &self.frames)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::MockBody, BodyExt};
+
+    #[tokio::test(start_paused = true)]
+    async fn per_frame_delays() {
+        let mut body = Delayed::with_delays(MockBody::new().data("a").data("b"), |i| {
+            Duration::from_secs(i as u64 * 10)
+        });
+        let start = tokio::time::Instant::now();
+
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+        assert!(body.frame().await.is_none());
+        assert_eq!(start.elapsed(), Duration::from_secs(30));
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A body that returns `Poll::Pending` once before every frame of the wrapped body.
+    ///
+    /// The task is woken immediately, so the body makes progress, but every consumer state
+    /// machine has to handle being suspended and resumed between frames. This exercises
+    /// cancel-safety and resumption bugs in code polling bodies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{test::InterleavePending, BodyExt, Full};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = InterleavePending::new(Full::new(Bytes::from("hello")));
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    /// # }
+    /// ```
+    #[derive(Clone, Copy, Debug)]
+    pub struct InterleavePending<B> {
+        pended: bool,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> InterleavePending<B> {
+    /// Create a new `InterleavePending`.
+    pub fn new(inner: B) -> Self {
+        Self {
+            pended: false,
+            inner,
+        }
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: Body> Body for InterleavePending<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+
+        if !*this.pended {
+            *this.pended = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let frame = futures_core::ready!(this.inner.poll_frame(cx));
+        *this.pended = false;
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::FutureExt;
+
+    use super::*;
+    use crate::{test::MockBody, BodyExt};
+
+    #[test]
+    fn pends_before_every_frame() {
+        let mut body = InterleavePending::new(MockBody::new().data("a").data("b"));
+
+        assert!(body.frame().now_or_never().is_none());
+        let frame = body.frame().now_or_never().unwrap().unwrap().unwrap();
+        assert_eq!(frame.into_data().unwrap(), "a");
+
+        assert!(body.frame().now_or_never().is_none());
+        assert!(body.frame().now_or_never().unwrap().is_some());
+
+        assert!(body.frame().now_or_never().is_none());
+        assert!(body.frame().now_or_never().unwrap().is_none());
+        assert_eq!(body.get_ref().polls(), 3);
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use http::HeaderMap;
+use http_body::{Body, Frame};
+
+/// A body that yields a scripted sequence of outcomes.
+///
+/// Each call to `poll_frame` consumes the next step of the script, which can be a data frame,
+/// trailers, an error, or `Poll::Pending`. Pending steps wake the task immediately, so the body is
+/// polled again. Once the script is consumed, the body ends.
+///
+/// The number of calls to `poll_frame` is recorded, and dropping the body panics if the script
+/// was not fully consumed, unless the thread is already panicking or
+/// [`allow_unconsumed`](MockBody::allow_unconsumed) was called.
+///
+/// With the `arbitrary` feature, `MockBody` implements `arbitrary::Arbitrary`, generating
+/// random sequences of data frames and pending steps, ending with trailers, an error, or nothing.
+/// This allows property testing code that consumes bodies.
+///
+/// # Example
+///
+/// ```
+/// use http_body_util::{test::MockBody, BodyExt};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let body = MockBody::new().data("hello").pending().data(" world");
+/// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello world");
+/// # }
+/// ```
+pub struct MockBody<E = Infallible> {
+    script: VecDeque<Step<E>>,
+    polls: usize,
+    check_consumed: bool,
+}
+
+enum Step<E> {
+    Data(Bytes),
+    Trailers(HeaderMap),
+    Error(E),
+    Pending,
+}
+
+impl MockBody {
+    /// Create a new `MockBody` with an empty script, which never yields an error.
+    ///
+    /// Use [`MockBody::default`] to create a body whose script can contain errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<E> MockBody<E> {
+    /// Add a data frame to the script.
+    pub fn data(mut self, data: impl Into<Bytes>) -> Self {
+        self.script.push_back(Step::Data(data.into()));
+        self
+    }
+
+    /// Add a trailers frame to the script.
+    pub fn trailers(mut self, trailers: HeaderMap) -> Self {
+        self.script.push_back(Step::Trailers(trailers));
+        self
+    }
+
+    /// Add an error to the script.
+    pub fn error(mut self, error: E) -> Self {
+        self.script.push_back(Step::Error(error));
+        self
+    }
+
+    /// Add a `Poll::Pending` to the script.
+    pub fn pending(mut self) -> Self {
+        self.script.push_back(Step::Pending);
+        self
+    }
+
+    /// Don't panic if the body is dropped before its script is consumed.
+    pub fn allow_unconsumed(mut self) -> Self {
+        self.check_consumed = false;
+        self
+    }
+
+    /// Returns the number of times the body has been polled.
+    pub fn polls(&self) -> usize {
+        self.polls
+    }
+
+    /// Returns the number of steps of the script that have not been consumed.
+    pub fn remaining(&self) -> usize {
+        self.script.len()
+    }
+}
+
+impl<E> Body for MockBody<E> {
+    type Data = Bytes;
+    type Error = E;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.polls += 1;
+        let step = match self.script.pop_front() {
+            Some(step) => step,
+            None => return Poll::Ready(None),
+        };
+
+        match step {
+            Step::Data(data) => Poll::Ready(Some(Ok(Frame::data(data)))),
+            Step::Trailers(trailers) => Poll::Ready(Some(Ok(Frame::trailers(trailers)))),
+            Step::Error(err) => Poll::Ready(Some(Err(err))),
+            Step::Pending => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.script.is_empty()
+    }
+}
+
+impl<E> Default for MockBody<E> {
+    /// Create a new `MockBody` with an empty script.
+    fn default() -> Self {
+        Self {
+            script: VecDeque::new(),
+            polls: 0,
+            check_consumed: true,
+        }
+    }
+}
+
+impl<E> Unpin for MockBody<E> {}
+
+impl<E> Drop for MockBody<E> {
+    fn drop(&mut self) {
+        if self.check_consumed && !std::thread::panicking() {
+            assert!(
+                self.script.is_empty(),
+                "MockBody dropped with {} unconsumed steps",
+                self.script.len()
+            );
+        }
+    }
+}
+
+impl<E> fmt::Debug for MockBody<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockBody")
+            .field("remaining", &self.script.len())
+            .field("polls", &self.polls)
+            .finish()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, E: arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for MockBody<E> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // Dropping a partially built body on error must not panic.
+        let mut body = MockBody::default().allow_unconsumed();
+        for _ in 0..u.arbitrary_len::<u8>()?.min(64) {
+            body = if u.ratio(1, 4)? {
+                body.pending()
+            } else {
+                let len = u.int_in_range(0..=64)?;
+                body.data(u.bytes(len)?.to_vec())
+            };
+        }
+
+        body = match u.int_in_range(0..=2)? {
+            0 => body,
+            1 => body.trailers(arbitrary_trailers(u)?),
+            _ => body.error(E::arbitrary(u)?),
+        };
+        body.check_consumed = true;
+        Ok(body)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+fn arbitrary_trailers(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<HeaderMap> {
+    use http::header::{HeaderName, HeaderValue};
+
+    let mut trailers = HeaderMap::new();
+    for _ in 0..u.int_in_range(0..=4)? {
+        let name = format!("x-trailer-{}", u.int_in_range(0..=9u8)?);
+        let value: Vec<u8> = (0..u.int_in_range(0..=16)?)
+            .map(|_| u.int_in_range(b' '..=b'~'))
+            .collect::<arbitrary::Result<_>>()?;
+        trailers.append(
+            HeaderName::from_bytes(name.as_bytes()).expect("valid header name"),
+            HeaderValue::from_bytes(&value).expect("valid header value"),
+        );
+    }
+    Ok(trailers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BodyExt;
+
+    #[tokio::test]
+    async fn follows_script() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-a", "b".parse().unwrap());
+        let mut body = MockBody::default()
+            .data("a")
+            .pending()
+            .error("oops")
+            .trailers(trailers.clone());
+
+        assert_eq!(
+            body.frame().await.unwrap().unwrap().into_data().unwrap(),
+            "a"
+        );
+        assert_eq!(body.frame().await.unwrap().unwrap_err(), "oops");
+        assert_eq!(body.polls(), 3);
+
+        let frame = body.frame().await.unwrap().unwrap();
+        assert_eq!(frame.into_trailers().unwrap(), trailers);
+        assert!(body.is_end_stream());
+        assert!(body.frame().await.is_none());
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[tokio::test]
+    async fn arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let seed: Vec<u8> = (0..4096u32).map(|i| (i * 7919 % 251) as u8).collect();
+        let mut u = Unstructured::new(&seed);
+        while !u.is_empty() {
+            let body = match MockBody::<u8>::arbitrary(&mut u) {
+                Ok(body) => body,
+                Err(_) => break,
+            };
+            let steps = body.remaining();
+            let mut body = body.allow_unconsumed();
+            while let Some(frame) = body.frame().await {
+                if frame.is_err() {
+                    break;
+                }
+            }
+            assert!(body.polls() >= steps);
+        }
+    }
+
+    #[test]
+    fn allow_unconsumed() {
+        drop(MockBody::new().data("a").allow_unconsumed());
+    }
+
+    #[test]
+    #[should_panic(expected = "MockBody dropped with 1 unconsumed steps")]
+    fn panics_if_not_consumed() {
+        drop(MockBody::new().data("a"));
+    }
+}
@@ -0,0 +1,23 @@
+//! Utilities for testing bodies and code consuming them.
+//!
+//! These are available with the `test-util` feature.
+
+mod assert;
+mod checked;
+#[cfg(feature = "time")]
+mod delayed;
+mod interleave_pending;
+mod mock;
+mod pending;
+mod poll_counted;
+mod record;
+
+pub use self::assert::{assert_body_data_eq, assert_body_eq};
+pub use self::checked::{Checked, InvariantError, Violation};
+#[cfg(feature = "time")]
+pub use self::delayed::Delayed;
+pub use self::interleave_pending::InterleavePending;
+pub use self::mock::MockBody;
+pub use self::pending::{NeverEnding, PendingBody};
+pub use self::poll_counted::{PollCounted, PollCounts};
+pub use self::record::{RecordedError, RecordedFrame, Recorder, Recording, Replay};
@@ -0,0 +1,148 @@
+use std::{
+    convert::Infallible,
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use bytes::{Buf, Bytes};
+use http_body::{Body, Frame};
+
+/// A body that is always pending.
+///
+/// The waker of the last poll is kept until the body is dropped, like a real body waiting for
+/// data would, but it is never woken. This is useful to test timeouts and cancellation.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use futures_util::FutureExt;
+/// use http_body_util::{test::PendingBody, BodyExt};
+///
+/// let mut body = PendingBody::<Bytes>::new();
+/// assert!(body.frame().now_or_never().is_none());
+/// ```
+pub struct PendingBody<D = Bytes, E = Infallible> {
+    waker: Option<Waker>,
+    _marker: PhantomData<fn() -> (D, E)>,
+}
+
+impl<D, E> PendingBody<D, E> {
+    /// Create a new `PendingBody`.
+    pub fn new() -> Self {
+        Self {
+            waker: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the body has been polled, and holds a waker.
+    pub fn has_waker(&self) -> bool {
+        self.waker.is_some()
+    }
+}
+
+impl<D: Buf, E> Body for PendingBody<D, E> {
+    type Data = D;
+    type Error = E;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match &mut self.waker {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            waker => *waker = Some(cx.waker().clone()),
+        }
+        Poll::Pending
+    }
+}
+
+impl<D, E> Default for PendingBody<D, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D, E> fmt::Debug for PendingBody<D, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingBody")
+            .field("has_waker", &self.has_waker())
+            .finish()
+    }
+}
+
+/// A body that never ends, yielding the same data frame forever.
+///
+/// Every poll is ready immediately. This is useful to test length limits and code that must stop
+/// reading a body early.
+///
+/// # Example
+///
+/// ```
+/// use http_body_util::{test::NeverEnding, BodyExt, Limited};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let body = Limited::new(NeverEnding::new("data"), 1024);
+/// assert!(body.collect().await.is_err());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct NeverEnding {
+    chunk: Bytes,
+}
+
+impl NeverEnding {
+    /// Create a new `NeverEnding` yielding `chunk` forever.
+    pub fn new(chunk: impl Into<Bytes>) -> Self {
+        Self {
+            chunk: chunk.into(),
+        }
+    }
+}
+
+impl Body for NeverEnding {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Poll::Ready(Some(Ok(Frame::data(self.chunk.clone()))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::FutureExt;
+
+    use super::*;
+    use crate::BodyExt;
+
+    #[test]
+    fn pending_keeps_waker() {
+        let mut body = PendingBody::<Bytes>::new();
+        assert!(!body.has_waker());
+        assert!(body.frame().now_or_never().is_none());
+        assert!(body.has_waker());
+        assert!(!body.is_end_stream());
+    }
+
+    #[tokio::test]
+    async fn never_ending() {
+        let mut body = NeverEnding::new("a");
+        for _ in 0..100 {
+            let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+            assert_eq!(data, "a");
+        }
+        assert!(!body.is_end_stream());
+    }
+}
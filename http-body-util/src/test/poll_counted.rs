@@ -0,0 +1,203 @@
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A body that counts how it is polled.
+    ///
+    /// The number of calls to `poll_frame`, `Poll::Pending` results, wake-ups of the task, and
+    /// polls after the end of the body are kept in a [`PollCounts`] handle, which can be read
+    /// after the body has been moved into the code under test. This allows asserting that a
+    /// consumer doesn't busy-poll or poll again after the body ended.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http_body_util::test::{MockBody, PollCounted};
+    /// use http_body_util::BodyExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = PollCounted::new(MockBody::new().data("hello").pending());
+    /// let counts = body.counts();
+    ///
+    /// body.collect().await.unwrap();
+    /// assert_eq!(counts.polls(), 3);
+    /// assert_eq!(counts.pending(), 1);
+    /// assert_eq!(counts.wakes(), 1);
+    /// assert_eq!(counts.polls_after_end(), 0);
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct PollCounted<B> {
+        counts: PollCounts,
+        // The waker of the last poll, and the counting waker wrapping it.
+        waker: Option<(Waker, Waker)>,
+        ended: bool,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> PollCounted<B> {
+    /// Create a new `PollCounted`.
+    pub fn new(inner: B) -> Self {
+        Self {
+            counts: PollCounts::default(),
+            waker: None,
+            ended: false,
+            inner,
+        }
+    }
+
+    /// Returns a handle to the counts of this body.
+    pub fn counts(&self) -> PollCounts {
+        self.counts.clone()
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: Body> Body for PollCounted<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let counts = &this.counts.inner;
+
+        counts.polls.fetch_add(1, Ordering::Relaxed);
+        if *this.ended {
+            counts.polls_after_end.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let waker = match this.waker {
+            Some((original, wrapped)) if original.will_wake(cx.waker()) => wrapped,
+            waker => {
+                let wrapped = Waker::from(Arc::new(CountingWaker {
+                    inner: cx.waker().clone(),
+                    counts: counts.clone(),
+                }));
+                &waker.insert((cx.waker().clone(), wrapped)).1
+            }
+        };
+
+        let frame = this.inner.poll_frame(&mut Context::from_waker(waker));
+        match &frame {
+            Poll::Pending => {
+                counts.pending.fetch_add(1, Ordering::Relaxed);
+            }
+            Poll::Ready(None) => *this.ended = true,
+            Poll::Ready(Some(_)) => {}
+        }
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+struct CountingWaker {
+    inner: Waker,
+    counts: Arc<Counts>,
+}
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.counts.wakes.fetch_add(1, Ordering::Relaxed);
+        self.inner.wake_by_ref();
+    }
+}
+
+/// A shared handle to the counts of a [`PollCounted`] body.
+#[derive(Clone, Debug, Default)]
+pub struct PollCounts {
+    inner: Arc<Counts>,
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    polls: AtomicU64,
+    pending: AtomicU64,
+    wakes: AtomicU64,
+    polls_after_end: AtomicU64,
+}
+
+impl PollCounts {
+    /// Returns the number of calls to `poll_frame`.
+    pub fn polls(&self) -> u64 {
+        self.inner.polls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of times the wrapped body returned `Poll::Pending`.
+    pub fn pending(&self) -> u64 {
+        self.inner.pending.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of times the wrapped body woke the task.
+    pub fn wakes(&self) -> u64 {
+        self.inner.wakes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of calls to `poll_frame` after the wrapped body returned `None`.
+    pub fn polls_after_end(&self) -> u64 {
+        self.inner.polls_after_end.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::MockBody, BodyExt};
+
+    #[tokio::test]
+    async fn counts_polls_after_end() {
+        let mut body = PollCounted::new(MockBody::new().data("a"));
+        let counts = body.counts();
+
+        assert!(body.frame().await.is_some());
+        assert!(body.frame().await.is_none());
+        assert_eq!(counts.polls_after_end(), 0);
+        assert!(body.frame().await.is_none());
+        assert_eq!(counts.polls(), 3);
+        assert_eq!(counts.polls_after_end(), 1);
+    }
+}
@@ -0,0 +1,619 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::{self, Write as _},
+    fs,
+    io::{self, BufRead, Write},
+    path::Path,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use bytes::{Buf, Bytes};
+use futures_core::ready;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+use crate::timer::Timer;
+
+pin_project! {
+    /// A body that records the frames of the wrapped body as they are yielded.
+    ///
+    /// Each frame, error and the end of the body is kept in a [`Recording`] together with the time
+    /// since the `Recorder` was created. The recording can be replayed as an identical body, and
+    /// saved to disk for golden tests or offline debugging.
+    ///
+    /// With the `time` feature, the time is read from tokio, so recordings made with paused time
+    /// match [`Recording::replay_timed`]. Use [`Recorder::with_timer`] to read it from another
+    /// [`Timer`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http_body_util::test::{assert_body_eq, MockBody, Recorder};
+    /// use http_body_util::BodyExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Recorder::new(MockBody::new().data("hello").data("world"));
+    /// let recording = body.recording();
+    ///
+    /// body.collect().await.unwrap();
+    /// assert_body_eq(recording.replay(), MockBody::new().data("hello").data("world")).await;
+    /// # }
+    /// ```
+    pub struct Recorder<B> {
+        start: Instant,
+        timer: Option<Arc<dyn Timer + Send + Sync>>,
+        recording: Recording,
+        ended: bool,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> Recorder<B> {
+    /// Create a new `Recorder`, starting the clock now.
+    pub fn new(inner: B) -> Self {
+        Self {
+            start: now(None),
+            timer: None,
+            recording: Recording::default(),
+            ended: false,
+            inner,
+        }
+    }
+
+    /// Create a new `Recorder` measuring time with `timer`, starting the clock now.
+    pub fn with_timer<T>(inner: B, timer: T) -> Self
+    where
+        T: Timer + Send + Sync + 'static,
+    {
+        let timer: Arc<dyn Timer + Send + Sync> = Arc::new(timer);
+        Self {
+            start: timer.now(),
+            timer: Some(timer),
+            recording: Recording::default(),
+            ended: false,
+            inner,
+        }
+    }
+
+    /// Returns a handle to the recording of this body.
+    pub fn recording(&self) -> Recording {
+        self.recording.clone()
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for Recorder<B>
+where
+    B: Body,
+    B::Error: fmt::Display,
+{
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        if *this.ended {
+            return Poll::Ready(None);
+        }
+
+        let frame = ready!(this.inner.poll_frame(cx)).map(|frame| {
+            frame.map(|frame| frame.map_data(|mut data| data.copy_to_bytes(data.remaining())))
+        });
+
+        let recorded = match &frame {
+            Some(Ok(item)) => match (item.data_ref(), item.trailers_ref()) {
+                (Some(data), _) => RecordedFrame::Data(data.clone()),
+                (_, Some(trailers)) => RecordedFrame::Trailers(trailers.clone()),
+                _ => return Poll::Ready(frame),
+            },
+            Some(Err(err)) => RecordedFrame::Error(err.to_string()),
+            None => {
+                *this.ended = true;
+                RecordedFrame::End
+            }
+        };
+
+        let elapsed = now(this.timer.as_deref()).saturating_duration_since(*this.start);
+        this.recording
+            .frames
+            .lock()
+            .unwrap()
+            .push((elapsed, recorded));
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.ended || self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B: fmt::Debug> fmt::Debug for Recorder<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("start", &self.start)
+            .field("recording", &self.recording)
+            .field("ended", &self.ended)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+/// Returns the current time from `timer`, or from tokio with the `time` feature.
+fn now(timer: Option<&(dyn Timer + Send + Sync)>) -> Instant {
+    match timer {
+        Some(timer) => timer.now(),
+        #[cfg(feature = "time")]
+        None => tokio::time::Instant::now().into_std(),
+        #[cfg(not(feature = "time"))]
+        None => Instant::now(),
+    }
+}
+
+/// An item of a [`Recording`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum RecordedFrame {
+    /// A data frame.
+    Data(Bytes),
+    /// A trailers frame.
+    Trailers(HeaderMap),
+    /// An error, recorded as its message.
+    Error(String),
+    /// The end of the body.
+    End,
+}
+
+/// A shared handle to the frames recorded by a [`Recorder`].
+///
+/// # Format
+///
+/// [`write_to`](Recording::write_to) writes one line per recorded item, starting with the time
+/// since the recorder was created in nanoseconds, followed by the kind of the item:
+///
+/// ```text
+/// 1200 data 68656c6c6f
+/// 1500 trailers grpc-status=30
+/// 1700 end
+/// ```
+///
+/// Data, header values and error messages are hex encoded. Trailer fields are separated by `,`.
+///
+/// With the `serde` feature, `Recording` and [`RecordedFrame`] also implement `Serialize` and
+/// `Deserialize`, so recordings can be stored in any format supported by serde. A recording is
+/// represented as a sequence of `(elapsed, frame)` pairs.
+#[derive(Clone, Debug, Default)]
+pub struct Recording {
+    frames: Arc<Mutex<Vec<(Duration, RecordedFrame)>>>,
+}
+
+impl Recording {
+    /// Returns the recorded items, with the time since the recorder was created.
+    pub fn frames(&self) -> Vec<(Duration, RecordedFrame)> {
+        self.frames.lock().unwrap().clone()
+    }
+
+    /// Returns a body yielding the recorded frames immediately.
+    ///
+    /// Recorded errors are yielded as a [`RecordedError`]. If the recording stopped before the
+    /// end of the body, the replayed body ends after the last recorded item.
+    pub fn replay(&self) -> Replay {
+        Replay {
+            frames: self.frames().into_iter().map(|(_, frame)| frame).collect(),
+        }
+    }
+
+    /// Returns a body yielding the recorded frames at the recorded times.
+    ///
+    /// The times are relative to the first poll of the returned body.
+    #[cfg(feature = "time")]
+    pub fn replay_timed(&self) -> super::Delayed<Replay> {
+        let mut last = Duration::ZERO;
+        let delays: Vec<_> = self
+            .frames()
+            .into_iter()
+            .map(|(elapsed, _)| {
+                let delay = elapsed.saturating_sub(last);
+                last = elapsed;
+                delay
+            })
+            .collect();
+        super::Delayed::with_delays(self.replay(), move |i| {
+            delays.get(i).copied().unwrap_or_default()
+        })
+    }
+
+    /// Write the recording to `writer`.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for (elapsed, frame) in self.frames.lock().unwrap().iter() {
+            let mut line = elapsed.as_nanos().to_string();
+            match frame {
+                RecordedFrame::Data(data) => {
+                    line.push_str(" data ");
+                    push_hex(&mut line, data);
+                }
+                RecordedFrame::Trailers(trailers) => {
+                    line.push_str(" trailers ");
+                    for (i, (name, value)) in trailers.iter().enumerate() {
+                        if i > 0 {
+                            line.push(',');
+                        }
+                        line.push_str(name.as_str());
+                        line.push('=');
+                        push_hex(&mut line, value.as_bytes());
+                    }
+                }
+                RecordedFrame::Error(message) => {
+                    line.push_str(" error ");
+                    push_hex(&mut line, message.as_bytes());
+                }
+                RecordedFrame::End => line.push_str(" end"),
+            }
+            writeln!(writer, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+
+    /// Read a recording written by [`write_to`](Recording::write_to) from `reader`.
+    pub fn read_from<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut frames = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            frames.push(parse_line(&line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid recording line: {line:?}"),
+                )
+            })?);
+        }
+        Ok(Self {
+            frames: Arc::new(Mutex::new(frames)),
+        })
+    }
+
+    /// Save the recording to the file at `path`, replacing it if it exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut file = io::BufWriter::new(fs::File::create(path)?);
+        self.write_to(&mut file)?;
+        file.flush()
+    }
+
+    /// Load a recording saved with [`save`](Recording::save) from the file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read_from(io::BufReader::new(fs::File::open(path)?))
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use bytes::Bytes;
+    use http_body::Frame;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{RecordedFrame, Recording};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "RecordedFrame", rename_all = "lowercase")]
+    enum Repr {
+        Frame(Frame<Bytes>),
+        Error(String),
+        End,
+    }
+
+    impl Serialize for RecordedFrame {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let repr = match self {
+                RecordedFrame::Data(data) => Repr::Frame(Frame::data(data.clone())),
+                RecordedFrame::Trailers(trailers) => Repr::Frame(Frame::trailers(trailers.clone())),
+                RecordedFrame::Error(message) => Repr::Error(message.clone()),
+                RecordedFrame::End => Repr::End,
+            };
+            repr.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for RecordedFrame {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Ok(match Repr::deserialize(deserializer)? {
+                Repr::Frame(frame) => match frame.into_data() {
+                    Ok(data) => RecordedFrame::Data(data),
+                    Err(frame) => {
+                        RecordedFrame::Trailers(frame.into_trailers().unwrap_or_default())
+                    }
+                },
+                Repr::Error(message) => RecordedFrame::Error(message),
+                Repr::End => RecordedFrame::End,
+            })
+        }
+    }
+
+    impl Serialize for Recording {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.frames.lock().unwrap().serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Recording {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let frames = Vec::<(Duration, RecordedFrame)>::deserialize(deserializer)?;
+            Ok(Recording {
+                frames: Arc::new(Mutex::new(frames)),
+            })
+        }
+    }
+}
+
+fn push_hex(line: &mut String, bytes: &[u8]) {
+    for byte in bytes {
+        let _ = write!(line, "{byte:02x}");
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn parse_line(line: &str) -> Option<(Duration, RecordedFrame)> {
+    let mut parts = line.splitn(3, ' ');
+    let elapsed = Duration::from_nanos(parts.next()?.parse().ok()?);
+    let rest = parts.next().zip(Some(parts.next().unwrap_or("")))?;
+    let frame = match rest {
+        ("data", hex) => RecordedFrame::Data(parse_hex(hex)?.into()),
+        ("trailers", fields) => {
+            let mut trailers = HeaderMap::new();
+            for field in fields.split(',').filter(|field| !field.is_empty()) {
+                let (name, value) = field.split_once('=')?;
+                trailers.append(
+                    HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    HeaderValue::from_bytes(&parse_hex(value)?).ok()?,
+                );
+            }
+            RecordedFrame::Trailers(trailers)
+        }
+        ("error", hex) => RecordedFrame::Error(String::from_utf8(parse_hex(hex)?).ok()?),
+        ("end", "") => RecordedFrame::End,
+        _ => return None,
+    };
+    Some((elapsed, frame))
+}
+
+/// A body replaying a [`Recording`].
+#[derive(Debug)]
+pub struct Replay {
+    frames: VecDeque<RecordedFrame>,
+}
+
+impl Body for Replay {
+    type Data = Bytes;
+    type Error = RecordedError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let frame = match self.frames.pop_front() {
+            Some(RecordedFrame::Data(data)) => Ok(Frame::data(data)),
+            Some(RecordedFrame::Trailers(trailers)) => Ok(Frame::trailers(trailers)),
+            Some(RecordedFrame::Error(message)) => Err(RecordedError { message }),
+            Some(RecordedFrame::End) | None => {
+                self.frames.clear();
+                return Poll::Ready(None);
+            }
+        };
+        Poll::Ready(Some(frame))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        matches!(self.frames.front(), None | Some(RecordedFrame::End))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let mut len = 0;
+        for frame in &self.frames {
+            match frame {
+                RecordedFrame::Data(data) => len += data.len() as u64,
+                RecordedFrame::Trailers(_) => {}
+                RecordedFrame::Error(_) => break,
+                RecordedFrame::End => return SizeHint::with_exact(len),
+            }
+        }
+        let mut hint = SizeHint::new();
+        hint.set_lower(len);
+        hint
+    }
+}
+
+/// An error replayed from a [`Recording`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedError {
+    message: String,
+}
+
+impl RecordedError {
+    /// Returns the message of the recorded error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for RecordedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for RecordedError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::MockBody, BodyExt};
+
+    fn trailers() -> HeaderMap {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+        trailers.append("x-note", HeaderValue::from_static("a, b"));
+        trailers
+    }
+
+    #[tokio::test]
+    async fn records_and_replays() {
+        let mut body = Recorder::new(
+            MockBody::<&'static str>::default()
+                .data("hello")
+                .pending()
+                .error("boom"),
+        );
+        let recording = body.recording();
+
+        assert_eq!(
+            body.frame().await.unwrap().unwrap().into_data().unwrap(),
+            "hello"
+        );
+        assert_eq!(body.frame().await.unwrap().unwrap_err(), "boom");
+        let frames: Vec<_> = recording.frames().into_iter().map(|(_, f)| f).collect();
+        assert_eq!(
+            frames,
+            vec![
+                RecordedFrame::Data(Bytes::from("hello")),
+                RecordedFrame::Error("boom".into()),
+            ]
+        );
+
+        let mut replay = recording.replay();
+        assert_eq!(
+            replay.frame().await.unwrap().unwrap().into_data().unwrap(),
+            "hello"
+        );
+        assert_eq!(replay.frame().await.unwrap().unwrap_err().message(), "boom");
+        assert!(replay.frame().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn round_trips_through_text() {
+        let body = Recorder::new(MockBody::new().data("hi\n").data("").trailers(trailers()));
+        let recording = body.recording();
+        body.collect().await.unwrap();
+
+        let mut text = Vec::new();
+        recording.write_to(&mut text).unwrap();
+        let read = Recording::read_from(&text[..]).unwrap();
+        assert_eq!(read.frames(), recording.frames());
+
+        let replay = read.replay();
+        assert_eq!(replay.size_hint().exact(), Some(3));
+        let collected = replay.collect().await.unwrap();
+        assert_eq!(collected.trailers(), Some(&trailers()));
+        assert_eq!(collected.to_bytes(), "hi\n");
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn serde_round_trip() {
+        let body = Recorder::new(
+            MockBody::<&'static str>::default()
+                .data("hi")
+                .trailers(trailers())
+                .error("boom"),
+        );
+        let recording = body.recording();
+        let _ = body.collect().await;
+
+        let json = serde_json::to_string(&recording).unwrap();
+        let read: Recording = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.frames(), recording.frames());
+
+        let end = serde_json::to_string(&RecordedFrame::End).unwrap();
+        assert_eq!(end, r#""end""#);
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        for line in [
+            "1",
+            "x end",
+            "1 data 6",
+            "1 trailers a",
+            "1 end x",
+            "1 nope",
+        ] {
+            let err = Recording::read_from(line.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{line:?}");
+        }
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn records_with_paused_time() {
+        let body = crate::test::Delayed::new(MockBody::new().data("a"), Duration::from_secs(3));
+        let body = Recorder::new(body);
+        let recording = body.recording();
+        body.collect().await.unwrap();
+
+        let times: Vec<_> = recording.frames().into_iter().map(|(t, _)| t).collect();
+        assert_eq!(times, [Duration::from_secs(3), Duration::from_secs(6)]);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn replays_at_recorded_times() {
+        let text = "0 data 61\n5000000000 data 62\n7000000000 end\n";
+        let mut body = Recording::read_from(text.as_bytes())
+            .unwrap()
+            .replay_timed();
+        let start = tokio::time::Instant::now();
+
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::from_secs(5));
+        assert!(body.frame().await.is_none());
+        assert_eq!(start.elapsed(), Duration::from_secs(7));
+    }
+}
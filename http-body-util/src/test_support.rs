@@ -0,0 +1,87 @@
+//! Bodies shared by the unit tests.
+
+use std::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+    vec,
+};
+
+use bytes::Bytes;
+use futures_util::stream;
+use http::{HeaderMap, HeaderValue};
+use http_body::Frame;
+
+use crate::timer::{Sleep, Timer};
+use crate::StreamBody;
+
+/// The body returned by [`test_body`].
+pub(crate) type TestBody =
+    StreamBody<stream::Iter<vec::IntoIter<Result<Frame<Bytes>, Infallible>>>>;
+
+/// Returns a body yielding each chunk as a data frame, followed by `trailers` if any.
+pub(crate) fn test_body<C: AsRef<[u8]>>(chunks: &[C], trailers: Option<HeaderMap>) -> TestBody {
+    let frames: Vec<_> = chunks
+        .iter()
+        .map(|chunk| Frame::data(Bytes::copy_from_slice(chunk.as_ref())))
+        .chain(trailers.map(Frame::trailers))
+        .map(Ok)
+        .collect();
+    StreamBody::new(stream::iter(frames))
+}
+
+/// Returns trailers with a single field, `name`, set to `1`.
+pub(crate) fn trailer(name: &'static str) -> HeaderMap {
+    let mut trailers = HeaderMap::new();
+    trailers.insert(name, HeaderValue::from_static("1"));
+    trailers
+}
+
+/// A [`Timer`] whose clock only moves when advanced by the test.
+#[derive(Clone)]
+pub(crate) struct ManualTimer(Arc<Mutex<Instant>>);
+
+impl ManualTimer {
+    pub(crate) fn new() -> Self {
+        ManualTimer(Arc::new(Mutex::new(Instant::now())))
+    }
+
+    pub(crate) fn advance(&self, by: Duration) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+impl Timer for ManualTimer {
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Sleep>> {
+        Box::pin(ManualSleep {
+            clock: self.clone(),
+            deadline,
+        })
+    }
+
+    fn now(&self) -> Instant {
+        *self.0.lock().unwrap()
+    }
+}
+
+struct ManualSleep {
+    clock: ManualTimer,
+    deadline: Instant,
+}
+
+impl Future for ManualSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.clock.now() >= self.deadline {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl Sleep for ManualSleep {}
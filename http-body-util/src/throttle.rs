@@ -0,0 +1,386 @@
+use std::{
+    convert::TryFrom,
+    fmt,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use bytes::Buf;
+use futures_core::ready;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+#[cfg(feature = "time")]
+use crate::timer::TokioTimer;
+use crate::timer::{Sleep, Timer};
+
+pin_project! {
+    /// A body that limits the rate at which data is yielded, using a token bucket.
+    ///
+    /// The bucket holds up to `burst` tokens and starts full. Every `interval`, `rate` tokens are
+    /// added back, up to the burst capacity. Each byte of data costs one token, or each data frame
+    /// when created with [`Throttle::frames`]. A frame is polled from the wrapped body whenever at
+    /// least one token is available, and the frame is yielded whole even if it costs more tokens
+    /// than are left; the following frames are then delayed until the debt has been refilled.
+    /// Trailers don't cost any tokens.
+    ///
+    /// The clock is read from a [`Timer`], given to [`Throttle::with_timer`]. With the `time`
+    /// feature, `Throttle::new` uses a `TokioTimer`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full, Throttle};
+    ///
+    /// # #[cfg(feature = "time")]
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// // 64 KiB every 100ms, with bursts of up to 256 KiB.
+    /// let body = Throttle::new(Full::new(Bytes::from("hello")), 64 * 1024, Duration::from_millis(100))
+    ///     .burst(256 * 1024);
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    /// # }
+    /// # #[cfg(not(feature = "time"))]
+    /// # fn main() {}
+    /// ```
+    pub struct Throttle<B> {
+        rate: u64,
+        interval: Duration,
+        burst: u64,
+        per_frame: bool,
+        // Negative when a frame cost more tokens than were available.
+        tokens: i128,
+        last_refill: Instant,
+        timer: Arc<dyn Timer + Send + Sync>,
+        sleep: Option<Pin<Box<dyn Sleep>>>,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> Throttle<B> {
+    /// Create a new `Throttle` adding `rate` tokens every `interval`.
+    ///
+    /// The burst capacity defaults to `rate`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero or `interval` is zero.
+    #[cfg(feature = "time")]
+    pub fn new(inner: B, rate: u64, interval: Duration) -> Self {
+        Self::with_timer(inner, rate, interval, TokioTimer)
+    }
+
+    /// Create a new `Throttle` adding `rate` tokens every `interval`, as measured by `timer`.
+    ///
+    /// The burst capacity defaults to `rate`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero or `interval` is zero.
+    pub fn with_timer<T>(inner: B, rate: u64, interval: Duration, timer: T) -> Self
+    where
+        T: Timer + Send + Sync + 'static,
+    {
+        assert!(rate > 0, "rate must be greater than zero");
+        assert!(!interval.is_zero(), "interval must be greater than zero");
+        Self {
+            rate,
+            interval,
+            burst: rate,
+            per_frame: false,
+            tokens: i128::from(rate),
+            last_refill: timer.now(),
+            timer: Arc::new(timer),
+            sleep: None,
+            inner,
+        }
+    }
+
+    /// Create a new `Throttle` limiting the number of data frames rather than bytes, adding
+    /// `rate` tokens every `interval`.
+    ///
+    /// Each data frame costs one token regardless of its size. This is useful for streams of
+    /// messages, such as server-sent events, where the quota is a message rate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero or `interval` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bytes::Bytes;
+    /// use http_body_util::{Full, Throttle};
+    ///
+    /// // At most 10 messages per second.
+    /// let body = Throttle::frames(Full::new(Bytes::from("data: hello\n\n")), 10, Duration::from_secs(1));
+    /// ```
+    #[cfg(feature = "time")]
+    pub fn frames(inner: B, rate: u64, interval: Duration) -> Self {
+        Self::frames_with_timer(inner, rate, interval, TokioTimer)
+    }
+
+    /// Create a new `Throttle` limiting the number of data frames rather than bytes, adding
+    /// `rate` tokens every `interval`, as measured by `timer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero or `interval` is zero.
+    pub fn frames_with_timer<T>(inner: B, rate: u64, interval: Duration, timer: T) -> Self
+    where
+        T: Timer + Send + Sync + 'static,
+    {
+        Self {
+            per_frame: true,
+            ..Self::with_timer(inner, rate, interval, timer)
+        }
+    }
+
+    /// Set the capacity of the bucket, which is the most data that can be yielded at once after
+    /// the body has been idle.
+    ///
+    /// The bucket is refilled to the new capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is zero.
+    pub fn burst(mut self, burst: u64) -> Self {
+        assert!(burst > 0, "burst must be greater than zero");
+        self.burst = burst;
+        self.tokens = i128::from(burst);
+        self
+    }
+
+    /// Set the timer used to read the current time and wait for tokens.
+    pub fn timer<T>(mut self, timer: T) -> Self
+    where
+        T: Timer + Send + Sync + 'static,
+    {
+        self.last_refill = timer.now();
+        self.timer = Arc::new(timer);
+        self.sleep = None;
+        self
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: Body> Body for Throttle<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+
+        loop {
+            let now = this.timer.now();
+            let refills =
+                now.duration_since(*this.last_refill).as_nanos() / this.interval.as_nanos();
+            if refills > 0 {
+                let added = refills.saturating_mul(u128::from(*this.rate));
+                let added = i128::try_from(added).unwrap_or(i128::MAX);
+                let burst = i128::from(*this.burst);
+                if this.tokens.saturating_add(added) >= burst {
+                    *this.tokens = burst;
+                    *this.last_refill = now;
+                } else {
+                    *this.tokens += added;
+                    *this.last_refill = after(*this.last_refill, *this.interval, refills);
+                }
+            }
+
+            if *this.tokens > 0 {
+                break;
+            }
+
+            // Wait for enough refills to pay back the debt.
+            let needed = (1 - *this.tokens) as u128;
+            let refills = (needed + u128::from(*this.rate) - 1) / u128::from(*this.rate);
+            let deadline = after(*this.last_refill, *this.interval, refills);
+            trace!(tokens = *this.tokens as i64, "throttle: waiting for tokens");
+            let sleep = match this.sleep {
+                Some(sleep) => {
+                    this.timer.reset(sleep, deadline);
+                    sleep
+                }
+                None => this.sleep.insert(this.timer.sleep_until(deadline)),
+            };
+            ready!(sleep.as_mut().poll(cx));
+        }
+
+        let frame = ready!(this.inner.poll_frame(cx));
+        if let Some(Ok(frame)) = &frame {
+            if let Some(data) = frame.data_ref() {
+                *this.tokens -= if *this.per_frame {
+                    1
+                } else {
+                    data.remaining() as i128
+                };
+            }
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B: fmt::Debug> fmt::Debug for Throttle<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Throttle")
+            .field("rate", &self.rate)
+            .field("interval", &self.interval)
+            .field("burst", &self.burst)
+            .field("per_frame", &self.per_frame)
+            .field("tokens", &self.tokens)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+/// Returns the instant `n` intervals after `start`.
+fn after(start: Instant, interval: Duration, n: u128) -> Instant {
+    u32::try_from(n)
+        .ok()
+        .and_then(|n| interval.checked_mul(n))
+        .and_then(|wait| start.checked_add(wait))
+        // Only reachable with absurd configurations, so waiting for a day is good enough.
+        .unwrap_or_else(|| start + Duration::from_secs(60 * 60 * 24))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::test_support::{test_body, ManualTimer};
+    use crate::BodyExt;
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn limits_rate() {
+        let mut body = Throttle::new(
+            test_body(&["aaaa", "bbbb", "cccc"], None),
+            4,
+            Duration::from_secs(1),
+        );
+        let start = tokio::time::Instant::now();
+
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+        assert!(body.frame().await.is_none());
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn burst_after_idle() {
+        let mut body = Throttle::new(
+            test_body(&["aa", "bb", "cc", "dd"], None),
+            2,
+            Duration::from_secs(1),
+        )
+        .burst(4);
+        let start = tokio::time::Instant::now();
+
+        // The bucket starts full.
+        body.frame().await.unwrap().unwrap();
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // Idling for longer than needed does not exceed the burst capacity.
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let start = tokio::time::Instant::now();
+        body.frame().await.unwrap().unwrap();
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn limits_frames() {
+        let chunks = &["a", "bbbbbbbb", "c", "d"];
+        let mut body = Throttle::frames(test_body(chunks, None), 2, Duration::from_secs(1));
+        let start = tokio::time::Instant::now();
+
+        body.frame().await.unwrap().unwrap();
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        body.frame().await.unwrap().unwrap();
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn custom_timer() {
+        use futures_util::FutureExt;
+
+        let timer = ManualTimer::new();
+        let mut body = Throttle::with_timer(
+            test_body(&["aa", "bb"], None),
+            2,
+            Duration::from_secs(1),
+            timer.clone(),
+        );
+
+        assert!(body.frame().now_or_never().is_some());
+        assert!(body.frame().now_or_never().is_none());
+
+        timer.advance(Duration::from_secs(1));
+        assert!(body.frame().now_or_never().is_some());
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn large_frame_creates_debt() {
+        let mut body = Throttle::new(
+            test_body(&["aaaaaaaaaa", "b"], None),
+            2,
+            Duration::from_secs(1),
+        );
+        let start = tokio::time::Instant::now();
+
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        // 8 tokens in debt, so it takes 5 refills to get a positive balance.
+        body.frame().await.unwrap().unwrap();
+        assert_eq!(start.elapsed(), Duration::from_secs(5));
+    }
+}
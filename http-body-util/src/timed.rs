@@ -0,0 +1,195 @@
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+pin_project! {
+    /// A body that records how long it takes to yield its first data frame and to finish.
+    ///
+    /// Both durations are measured from when the `Timed` is created, and are available through a
+    /// [`Timing`] handle which can be read after the body has been moved elsewhere.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full, Timed};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = Timed::new(Full::new(Bytes::from("hello")));
+    /// let timing = body.timing();
+    /// assert!(timing.first_byte().is_none());
+    ///
+    /// body.collect().await.unwrap();
+    /// assert!(timing.first_byte().is_some());
+    /// assert!(timing.end().is_some());
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct Timed<B> {
+        start: Instant,
+        timing: Timing,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> Timed<B> {
+    /// Create a new `Timed`, starting the clock now.
+    pub fn new(inner: B) -> Self {
+        Self {
+            start: Instant::now(),
+            timing: Timing::default(),
+            inner,
+        }
+    }
+
+    /// Returns a handle to the timing of this body.
+    pub fn timing(&self) -> Timing {
+        self.timing.clone()
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for Timed<B>
+where
+    B: Body,
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        let frame = this.inner.as_mut().poll_frame(cx);
+
+        let (data, ended) = match &frame {
+            // The end time is taken here because callers like hyper drop the body after a frame
+            // that ends it, so there may never be a poll returning `None` to record it at.
+            Poll::Ready(Some(Ok(frame))) => (frame.is_data(), this.inner.is_end_stream()),
+            Poll::Ready(None) => (false, true),
+            Poll::Ready(Some(Err(_))) | Poll::Pending => (false, false),
+        };
+
+        if data || ended {
+            let elapsed = this.start.elapsed();
+            let mut times = this.timing.inner.lock().unwrap();
+            if data && times.first_byte.is_none() {
+                times.first_byte = Some(elapsed);
+            }
+            if ended && times.end.is_none() {
+                times.end = Some(elapsed);
+            }
+        }
+
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// A shared handle to the timing of a [`Timed`] body.
+#[derive(Clone, Debug, Default)]
+pub struct Timing {
+    inner: Arc<Mutex<Times>>,
+}
+
+#[derive(Debug, Default)]
+struct Times {
+    first_byte: Option<Duration>,
+    end: Option<Duration>,
+}
+
+impl Timing {
+    /// Returns the time until the first data frame was yielded, if it has been yet.
+    pub fn first_byte(&self) -> Option<Duration> {
+        self.inner.lock().unwrap().first_byte
+    }
+
+    /// Returns the time until the body reached its end, if it has yet.
+    pub fn end(&self) -> Option<Duration> {
+        self.inner.lock().unwrap().end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::{BodyExt, StreamBody};
+
+    #[tokio::test]
+    async fn trailers_are_not_first_byte() {
+        let one = futures_util::stream::once(async {
+            Ok::<_, Infallible>(Frame::<Bytes>::trailers(HeaderMap::new()))
+        });
+        let body = Timed::new(StreamBody::new(one));
+        let timing = body.timing();
+
+        body.collect().await.unwrap();
+        assert!(timing.first_byte().is_none());
+        assert!(timing.end().is_some());
+    }
+
+    #[tokio::test]
+    async fn first_byte_before_end() {
+        let two = futures_util::stream::iter(vec![
+            Ok::<_, Infallible>(Frame::data(Bytes::from("a"))),
+            Ok(Frame::data(Bytes::from("b"))),
+        ]);
+        let mut body = Timed::new(StreamBody::new(two));
+        let timing = body.timing();
+
+        body.frame().await.unwrap().unwrap();
+        let first_byte = timing.first_byte().unwrap();
+        assert!(timing.end().is_none());
+
+        while body.frame().await.is_some() {}
+        assert_eq!(timing.first_byte(), Some(first_byte));
+        assert!(timing.end().unwrap() >= first_byte);
+    }
+
+    #[tokio::test]
+    async fn end_at_end_of_stream() {
+        let mut body = Timed::new(crate::Full::new(Bytes::from("hello")));
+        let timing = body.timing();
+
+        body.frame().await.unwrap().unwrap();
+        assert!(body.is_end_stream());
+        assert_eq!(timing.end(), timing.first_byte());
+    }
+}
@@ -0,0 +1,394 @@
+use std::{
+    error::Error,
+    fmt,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+#[cfg(feature = "time")]
+use crate::timer::TokioTimer;
+use crate::timer::{Sleep, Timer};
+
+pin_project! {
+    /// A body that fails if its first frame doesn't arrive within a duration.
+    ///
+    /// The clock starts when the body is first polled. Once a frame has arrived, no further
+    /// timeout applies, so slow downloads are allowed after a strict deadline for the response to
+    /// start. If the deadline passes, the body yields a [`TimeoutError`].
+    ///
+    /// The deadline is measured by a [`Timer`], given to [`FirstFrameTimeout::with_timer`]. With
+    /// the `time` feature, `FirstFrameTimeout::new` uses a `TokioTimer`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, FirstFrameTimeout, Full};
+    ///
+    /// # #[cfg(feature = "time")]
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = FirstFrameTimeout::new(Full::new(Bytes::from("hello")), Duration::from_secs(5));
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    /// # }
+    /// # #[cfg(not(feature = "time"))]
+    /// # fn main() {}
+    /// ```
+    pub struct FirstFrameTimeout<B> {
+        duration: Duration,
+        timer: Arc<dyn Timer + Send + Sync>,
+        sleep: Option<Pin<Box<dyn Sleep>>>,
+        started: bool,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> FirstFrameTimeout<B> {
+    /// Create a new `FirstFrameTimeout` failing if the first frame takes longer than `duration`.
+    #[cfg(feature = "time")]
+    pub fn new(inner: B, duration: Duration) -> Self {
+        Self::with_timer(inner, duration, TokioTimer)
+    }
+
+    /// Create a new `FirstFrameTimeout` failing if the first frame takes longer than `duration`,
+    /// as measured by `timer`.
+    pub fn with_timer<T>(inner: B, duration: Duration, timer: T) -> Self
+    where
+        T: Timer + Send + Sync + 'static,
+    {
+        Self {
+            duration,
+            timer: Arc::new(timer),
+            sleep: None,
+            started: false,
+            inner,
+        }
+    }
+
+    /// Set the timer used to wait for the first frame.
+    pub fn timer<T>(mut self, timer: T) -> Self
+    where
+        T: Timer + Send + Sync + 'static,
+    {
+        self.timer = Arc::new(timer);
+        self.sleep = None;
+        self
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for FirstFrameTimeout<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = B::Data;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+
+        if *this.started {
+            return this.inner.poll_frame(cx).map_err(Into::into);
+        }
+
+        if let Poll::Ready(frame) = this.inner.poll_frame(cx) {
+            *this.started = true;
+            *this.sleep = None;
+            return Poll::Ready(frame.map(|frame| frame.map_err(Into::into)));
+        }
+
+        let sleep = match this.sleep {
+            Some(sleep) => sleep,
+            None => {
+                let deadline = this.timer.now() + *this.duration;
+                this.sleep.insert(this.timer.sleep_until(deadline))
+            }
+        };
+        if sleep.as_mut().poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        debug!("first frame timeout elapsed");
+        *this.started = true;
+        *this.sleep = None;
+        Poll::Ready(Some(Err(TimeoutError.into())))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B: fmt::Debug> fmt::Debug for FirstFrameTimeout<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FirstFrameTimeout")
+            .field("duration", &self.duration)
+            .field("started", &self.started)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+pin_project! {
+    /// A body that fails if it waits longer than a duration for any frame.
+    ///
+    /// The timer is restarted every time the wrapped body is polled after yielding a frame, so
+    /// this bounds the gap between consecutive frames rather than the total duration. This
+    /// protects against stalled streams. If the duration elapses, the body yields a
+    /// [`TimeoutError`].
+    ///
+    /// The duration is measured by a [`Timer`], given to [`IdleTimeout::with_timer`]. With the
+    /// `time` feature, `IdleTimeout::new` uses a `TokioTimer`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bytes::Bytes;
+    /// use http_body_util::{BodyExt, Full, IdleTimeout};
+    ///
+    /// # #[cfg(feature = "time")]
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let body = IdleTimeout::new(Full::new(Bytes::from("hello")), Duration::from_secs(30));
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "hello");
+    /// # }
+    /// # #[cfg(not(feature = "time"))]
+    /// # fn main() {}
+    /// ```
+    pub struct IdleTimeout<B> {
+        duration: Duration,
+        timer: Arc<dyn Timer + Send + Sync>,
+        sleep: Option<Pin<Box<dyn Sleep>>>,
+        armed: bool,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> IdleTimeout<B> {
+    /// Create a new `IdleTimeout` failing if any frame takes longer than `duration` to arrive.
+    #[cfg(feature = "time")]
+    pub fn new(inner: B, duration: Duration) -> Self {
+        Self::with_timer(inner, duration, TokioTimer)
+    }
+
+    /// Create a new `IdleTimeout` failing if any frame takes longer than `duration` to arrive, as
+    /// measured by `timer`.
+    pub fn with_timer<T>(inner: B, duration: Duration, timer: T) -> Self
+    where
+        T: Timer + Send + Sync + 'static,
+    {
+        Self {
+            duration,
+            timer: Arc::new(timer),
+            sleep: None,
+            armed: false,
+            inner,
+        }
+    }
+
+    /// Set the timer used to wait for frames.
+    pub fn timer<T>(mut self, timer: T) -> Self
+    where
+        T: Timer + Send + Sync + 'static,
+    {
+        self.timer = Arc::new(timer);
+        self.sleep = None;
+        self.armed = false;
+        self
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for IdleTimeout<B>
+where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Data = B::Data;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+
+        if let Poll::Ready(frame) = this.inner.poll_frame(cx) {
+            *this.armed = false;
+            return Poll::Ready(frame.map(|frame| frame.map_err(Into::into)));
+        }
+
+        if !*this.armed {
+            let deadline = this.timer.now() + *this.duration;
+            match this.sleep {
+                Some(sleep) => this.timer.reset(sleep, deadline),
+                None => *this.sleep = Some(this.timer.sleep_until(deadline)),
+            }
+            *this.armed = true;
+        }
+
+        let sleep = this.sleep.as_mut().expect("armed without a sleep");
+        if sleep.as_mut().poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        debug!("idle timeout elapsed");
+        *this.armed = false;
+        Poll::Ready(Some(Err(TimeoutError.into())))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B: fmt::Debug> fmt::Debug for IdleTimeout<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdleTimeout")
+            .field("duration", &self.duration)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+/// An error returned when a body times out.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct TimeoutError;
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("body timed out")
+    }
+}
+
+impl Error for TimeoutError {}
+
+#[cfg(all(test, feature = "time"))]
+mod tests {
+    use std::{convert::Infallible, time::Duration};
+
+    use bytes::Bytes;
+    use futures_util::StreamExt;
+    use http_body::Frame;
+
+    use super::*;
+    use crate::{BodyExt, StreamBody};
+
+    fn data(chunk: &'static str) -> Result<Frame<Bytes>, Infallible> {
+        Ok(Frame::data(Bytes::from(chunk)))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn first_frame_times_out() {
+        let body = StreamBody::new(futures_util::stream::pending::<
+            Result<Frame<Bytes>, Infallible>,
+        >());
+        let mut body = FirstFrameTimeout::new(body, Duration::from_secs(1));
+
+        let start = tokio::time::Instant::now();
+        let err = body.frame().await.unwrap().unwrap_err();
+        assert!(err.is::<TimeoutError>());
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    fn delayed(
+        delays: &'static [u64],
+    ) -> StreamBody<impl futures_core::Stream<Item = Result<Frame<Bytes>, Infallible>>> {
+        StreamBody::new(futures_util::stream::iter(delays).then(|secs| async move {
+            tokio::time::sleep(Duration::from_secs(*secs)).await;
+            data("x")
+        }))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_timeout_resets_on_frames() {
+        let body = IdleTimeout::new(delayed(&[1, 1, 1]), Duration::from_secs(2));
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "xxx");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_timeout_elapses() {
+        let mut body = Box::pin(IdleTimeout::new(delayed(&[1, 3]), Duration::from_secs(2)));
+        let start = tokio::time::Instant::now();
+
+        body.frame().await.unwrap().unwrap();
+        let err = body.frame().await.unwrap().unwrap_err();
+        assert!(err.is::<TimeoutError>());
+        assert_eq!(start.elapsed(), Duration::from_secs(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn no_timeout_after_first_frame() {
+        let frames = futures_util::stream::iter(vec![data("hello")]).chain(
+            futures_util::stream::once(async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                data("world")
+            }),
+        );
+        let body = FirstFrameTimeout::new(StreamBody::new(frames), Duration::from_secs(1));
+
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "helloworld");
+    }
+}
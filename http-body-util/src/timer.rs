@@ -0,0 +1,167 @@
+//! Timers used by time-based bodies, such as [`Throttle`](crate::Throttle).
+//!
+//! With the `time` feature, bodies use a `TokioTimer` by default. Implement [`Timer`] to use
+//! another runtime, or to control the clock in tests.
+
+use std::{any::TypeId, future::Future, pin::Pin, time::Instant};
+
+/// A timer which provides the current time and creates sleeps.
+pub trait Timer {
+    /// Returns a future that resolves at `deadline`.
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Sleep>>;
+
+    /// Reset `sleep` to resolve at `new_deadline` instead.
+    ///
+    /// The default implementation replaces it with a new sleep.
+    fn reset(&self, sleep: &mut Pin<Box<dyn Sleep>>, new_deadline: Instant) {
+        *sleep = self.sleep_until(new_deadline);
+    }
+
+    /// Returns the current time.
+    ///
+    /// The default implementation returns [`Instant::now`].
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A future returned by a [`Timer`].
+pub trait Sleep: Send + Sync + Future<Output = ()> {
+    #[doc(hidden)]
+    /// This method is private and can not be implemented by downstream crates.
+    fn __type_id(&self, _: private::Sealed) -> TypeId
+    where
+        Self: 'static,
+    {
+        TypeId::of::<Self>()
+    }
+}
+
+impl dyn Sleep {
+    /// Check whether the type is the same as `T`.
+    pub fn is<T>(&self) -> bool
+    where
+        T: Sleep + 'static,
+    {
+        self.__type_id(private::Sealed {}) == TypeId::of::<T>()
+    }
+
+    /// Downcast a pinned `&mut Sleep` object to its original type.
+    ///
+    /// A [`Timer`] can use this in [`Timer::reset`] to reset its own sleeps in place.
+    pub fn downcast_mut_pin<T>(self: Pin<&mut Self>) -> Option<Pin<&mut T>>
+    where
+        T: Sleep + 'static,
+    {
+        if self.is::<T>() {
+            // SAFETY: `T` is the type behind the trait object, and the pin is passed on.
+            unsafe {
+                let inner = Pin::into_inner_unchecked(self);
+                Some(Pin::new_unchecked(
+                    &mut *(&mut *inner as *mut dyn Sleep as *mut T),
+                ))
+            }
+        } else {
+            None
+        }
+    }
+}
+
+mod private {
+    #[allow(missing_debug_implementations, unreachable_pub)]
+    pub struct Sealed {}
+}
+
+#[cfg(feature = "time")]
+pub use self::tokio_timer::TokioTimer;
+
+#[cfg(feature = "time")]
+mod tokio_timer {
+    use std::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+        time::Instant,
+    };
+
+    use pin_project_lite::pin_project;
+
+    use super::{Sleep, Timer};
+
+    /// A [`Timer`] backed by `tokio::time`.
+    ///
+    /// The current time is read from tokio, so paused time in tests is respected.
+    #[derive(Clone, Copy, Debug, Default)]
+    #[non_exhaustive]
+    pub struct TokioTimer;
+
+    impl TokioTimer {
+        /// Create a new `TokioTimer`.
+        pub fn new() -> Self {
+            TokioTimer
+        }
+    }
+
+    impl Timer for TokioTimer {
+        fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Sleep>> {
+            Box::pin(TokioSleep {
+                inner: tokio::time::sleep_until(deadline.into()),
+            })
+        }
+
+        fn reset(&self, sleep: &mut Pin<Box<dyn Sleep>>, new_deadline: Instant) {
+            match sleep.as_mut().downcast_mut_pin::<TokioSleep>() {
+                Some(sleep) => sleep.reset(new_deadline),
+                None => *sleep = self.sleep_until(new_deadline),
+            }
+        }
+
+        fn now(&self) -> Instant {
+            tokio::time::Instant::now().into_std()
+        }
+    }
+
+    pin_project! {
+        struct TokioSleep {
+            #[pin]
+            inner: tokio::time::Sleep,
+        }
+    }
+
+    impl TokioSleep {
+        fn reset(self: Pin<&mut Self>, deadline: Instant) {
+            self.project().inner.reset(deadline.into());
+        }
+    }
+
+    impl Future for TokioSleep {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            self.project().inner.poll(cx)
+        }
+    }
+
+    impl Sleep for TokioSleep {}
+}
+
+#[cfg(all(test, feature = "time"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn tokio_timer_resets_in_place() {
+        let timer = TokioTimer::new();
+        let start = timer.now();
+        let mut sleep = timer.sleep_until(start + Duration::from_secs(10));
+        let before = &*sleep as *const dyn Sleep as *const ();
+
+        timer.reset(&mut sleep, start + Duration::from_secs(1));
+        assert_eq!(&*sleep as *const dyn Sleep as *const (), before);
+
+        sleep.await;
+        assert_eq!(timer.now() - start, Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, Bytes};
+use encoding_rs::{CoderResult, Decoder, Encoding, UTF_8};
+use futures_core::ready;
+use http::{header::CONTENT_TYPE, HeaderMap};
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A body that decodes the wrapped body from a character encoding into UTF-8.
+    ///
+    /// Data is decoded as it is received, so multi-byte sequences split across frames are handled
+    /// without buffering the whole body. Malformed sequences are replaced with U+FFFD, and a byte
+    /// order mark at the start of the body overrides the declared encoding, as specified by the
+    /// [WHATWG Encoding Standard].
+    ///
+    /// [WHATWG Encoding Standard]: https://encoding.spec.whatwg.org/#decode
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http::{header::CONTENT_TYPE, HeaderMap, HeaderValue};
+    /// use http_body_util::{BodyExt, Full, Transcode};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain; charset=ISO-8859-1"));
+    ///
+    /// let body = Transcode::from_headers(Full::new(Bytes::from_static(b"caf\xe9")), &headers);
+    /// assert_eq!(body.collect().await.unwrap().to_bytes(), "café");
+    /// # }
+    /// ```
+    pub struct Transcode<B> {
+        decoder: Option<Decoder>,
+        trailers: Option<HeaderMap>,
+        #[pin]
+        inner: B,
+    }
+}
+
+impl<B> Transcode<B> {
+    /// Create a new `Transcode` decoding from `encoding`.
+    pub fn new(inner: B, encoding: &'static Encoding) -> Self {
+        Self {
+            decoder: Some(encoding.new_decoder()),
+            trailers: None,
+            inner,
+        }
+    }
+
+    /// Create a new `Transcode` decoding from the `charset` parameter of the `Content-Type` header
+    /// in `headers`.
+    ///
+    /// UTF-8 is used if there is no `charset` parameter, or if it names an unknown encoding.
+    pub fn from_headers(inner: B, headers: &HeaderMap) -> Self {
+        let encoding = headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(charset)
+            .and_then(|label| Encoding::for_label(label.as_bytes()))
+            .unwrap_or(UTF_8);
+        Self::new(inner, encoding)
+    }
+
+    /// Get a reference to the inner body
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner body
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Get a pinned mutable reference to the inner body
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut B> {
+        self.project().inner
+    }
+
+    /// Consume `self`, returning the inner body
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+/// Returns the value of the `charset` parameter of a media type.
+fn charset(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if name.trim().eq_ignore_ascii_case("charset") {
+            Some(value.trim().trim_matches('"'))
+        } else {
+            None
+        }
+    })
+}
+
+/// Decode `src`, or finish decoding if `last` is set, appending to `dst`.
+fn decode(decoder: &mut Decoder, mut src: &[u8], dst: &mut String, last: bool) {
+    loop {
+        let needed = decoder
+            .max_utf8_buffer_length(src.len())
+            .unwrap_or(src.len().saturating_mul(3).saturating_add(16));
+        dst.reserve(needed);
+        let (result, read, _) = decoder.decode_to_string(src, dst, last);
+        src = &src[read..];
+        if let CoderResult::InputEmpty = result {
+            return;
+        }
+    }
+}
+
+impl<B> Body for Transcode<B>
+where
+    B: Body,
+{
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        if let Some(trailers) = this.trailers.take() {
+            return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+        }
+
+        loop {
+            let decoder = match this.decoder.as_mut() {
+                Some(decoder) => decoder,
+                // Decoding has finished, so the body is expected to end.
+                None => {
+                    let frame = ready!(this.inner.as_mut().poll_frame(cx));
+                    return Poll::Ready(frame.map(|frame| {
+                        frame.map(|frame| {
+                            frame.map_data(|mut data| data.copy_to_bytes(data.remaining()))
+                        })
+                    }));
+                }
+            };
+
+            let mut out = String::new();
+            match ready!(this.inner.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(mut data) => {
+                        while data.has_remaining() {
+                            let chunk = data.chunk();
+                            decode(decoder, chunk, &mut out, false);
+                            let len = chunk.len();
+                            data.advance(len);
+                        }
+                    }
+                    Err(frame) => {
+                        decode(decoder, &[], &mut out, true);
+                        *this.decoder = None;
+                        let trailers = match frame.into_trailers() {
+                            Ok(trailers) => trailers,
+                            Err(_) => continue,
+                        };
+                        if out.is_empty() {
+                            return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+                        }
+                        *this.trailers = Some(trailers);
+                    }
+                },
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => {
+                    decode(decoder, &[], &mut out, true);
+                    *this.decoder = None;
+                    if out.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                }
+            }
+
+            if !out.is_empty() {
+                return Poll::Ready(Some(Ok(Frame::data(Bytes::from(out)))));
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.decoder.is_none() && self.trailers.is_none() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        // The decoded length may differ from the encoded length in either direction.
+        let mut hint = SizeHint::new();
+        if self.is_end_stream() {
+            hint.set_exact(0);
+        }
+        hint
+    }
+}
+
+impl<B: fmt::Debug> fmt::Debug for Transcode<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transcode")
+            .field("encoding", &self.decoder.as_ref().map(Decoder::encoding))
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use encoding_rs::{SHIFT_JIS, UTF_16LE};
+    use http::HeaderValue;
+
+    use super::*;
+    use crate::test_support::test_body;
+    use crate::BodyExt;
+
+    #[tokio::test]
+    async fn split_sequences() {
+        // "日本" in Shift_JIS, split inside both characters.
+        let chunks: Vec<&'static [u8]> = vec![b"\x93", b"\xfa\x96", b"\x7b"];
+        let text = Transcode::new(test_body(&chunks, None), SHIFT_JIS)
+            .collect_string()
+            .await
+            .unwrap();
+        assert_eq!(text, "日本");
+    }
+
+    #[tokio::test]
+    async fn truncated_sequence_before_trailers() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-done", HeaderValue::from_static("1"));
+
+        let chunks: Vec<&'static [u8]> = vec![b"a\x00", b"b"];
+        let mut body = Transcode::new(test_body(&chunks, Some(trailers.clone())), UTF_16LE);
+
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "a");
+        let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(data, "\u{fffd}");
+        let frame = body.frame().await.unwrap().unwrap();
+        assert_eq!(frame.into_trailers().unwrap(), trailers);
+        assert!(body.frame().await.is_none());
+    }
+
+    #[test]
+    fn charset_from_headers() {
+        let encoding = |content_type: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+            let body = Transcode::from_headers(crate::Empty::<Bytes>::new(), &headers);
+            body.decoder.unwrap().encoding()
+        };
+
+        assert_eq!(encoding("text/html; charset=\"Shift_JIS\""), SHIFT_JIS);
+        assert_eq!(
+            encoding("text/plain;format=flowed; CHARSET=utf-16le"),
+            UTF_16LE
+        );
+        assert_eq!(encoding("text/plain"), UTF_8);
+        assert_eq!(encoding("text/plain; charset=unknown"), UTF_8);
+    }
+}
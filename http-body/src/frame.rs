@@ -1,12 +1,19 @@
+use bytes::Buf;
 use http::HeaderMap;
 
 /// A frame of any kind related to an HTTP stream (body).
-#[derive(Debug)]
+///
+/// Frames can be cloned and compared when their data can be.
+///
+/// With the `serde` feature, `Frame<Bytes>` implements `Serialize` and `Deserialize`. A frame is
+/// represented as an enum with a `data` variant holding the bytes of the frame, and a `trailers`
+/// variant holding a sequence of `(name, value)` pairs, where values are bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Frame<T> {
     kind: Kind<T>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 enum Kind<T> {
     // The first two variants are "inlined" since they are undoubtedly
     // the most common. This saves us from having to allocate a
@@ -32,6 +39,23 @@ impl<T> Frame<T> {
     }
 
     /// Maps this frame's data to a different type.
+    ///
+    /// `f` is only called for DATA frames. Frames of any other kind are returned unchanged, so
+    /// the kind of the frame is always preserved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use http::HeaderMap;
+    /// use http_body::Frame;
+    ///
+    /// let frame = Frame::data("hello").map_data(Bytes::from);
+    /// assert_eq!(frame.into_data().unwrap(), "hello");
+    ///
+    /// let frame = Frame::<&str>::trailers(HeaderMap::new()).map_data(Bytes::from);
+    /// assert!(frame.is_trailers());
+    /// ```
     pub fn map_data<F, D>(self, f: F) -> Frame<D>
     where
         F: FnOnce(T) -> D,
@@ -117,4 +141,135 @@ impl<T> Frame<T> {
             _ => None,
         }
     }
+
+    /// If this is a trailers frame, returns its approximate serialized size in bytes.
+    ///
+    /// Each field counts as its size in HTTP/1.1 chunked encoding: the length of the name and
+    /// value plus four bytes for the `": "` separator and the line ending.
+    ///
+    /// Returns `None` if not a trailers frame.
+    pub fn trailers_len(&self) -> Option<usize> {
+        self.trailers_ref().map(|trailers| {
+            trailers
+                .iter()
+                .map(|(name, value)| name.as_str().len() + value.len() + 4)
+                .fold(0usize, usize::saturating_add)
+        })
+    }
+}
+
+impl<T: Buf> Frame<T> {
+    /// If this is a DATA frame, returns the number of bytes remaining in it.
+    ///
+    /// Returns `None` if not a DATA frame.
+    pub fn data_len(&self) -> Option<usize> {
+        self.data_ref().map(Buf::remaining)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use bytes::Bytes;
+    use http::header::{HeaderName, HeaderValue};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{Frame, HeaderMap, Kind};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Frame", rename_all = "lowercase")]
+    enum Repr {
+        Data(Bytes),
+        Trailers(Vec<(String, Bytes)>),
+    }
+
+    impl Serialize for Frame<Bytes> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let repr = match &self.kind {
+                Kind::Data(data) => Repr::Data(data.clone()),
+                Kind::Trailers(trailers) => Repr::Trailers(
+                    trailers
+                        .iter()
+                        .map(|(name, value)| {
+                            (name.to_string(), Bytes::copy_from_slice(value.as_bytes()))
+                        })
+                        .collect(),
+                ),
+            };
+            repr.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Frame<Bytes> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            match Repr::deserialize(deserializer)? {
+                Repr::Data(data) => Ok(Frame::data(data)),
+                Repr::Trailers(fields) => {
+                    let mut trailers = HeaderMap::with_capacity(fields.len());
+                    for (name, value) in fields {
+                        let name =
+                            HeaderName::from_bytes(name.as_bytes()).map_err(de::Error::custom)?;
+                        let value =
+                            HeaderValue::from_maybe_shared(value).map_err(de::Error::custom)?;
+                        trailers.append(name, value);
+                    }
+                    Ok(Frame::trailers(trailers))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    #[test]
+    fn lengths() {
+        let frame = Frame::data(&b"hello"[..]);
+        assert_eq!(frame.data_len(), Some(5));
+        assert_eq!(frame.trailers_len(), None);
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+        let frame = Frame::<&[u8]>::trailers(trailers);
+        assert_eq!(frame.data_len(), None);
+        assert_eq!(frame.trailers_len(), Some("grpc-status: 0\r\n".len()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use bytes::Bytes;
+
+        let frame = Frame::data(Bytes::from("hi"));
+        let json = serde_json::to_string(&frame).unwrap();
+        assert_eq!(json, r#"{"data":[104,105]}"#);
+        assert_eq!(serde_json::from_str::<Frame<Bytes>>(&json).unwrap(), frame);
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+        trailers.append("x-note", HeaderValue::from_static("a"));
+        trailers.append("x-note", HeaderValue::from_static("b"));
+        let frame = Frame::<Bytes>::trailers(trailers);
+        let json = serde_json::to_string(&frame).unwrap();
+        assert_eq!(serde_json::from_str::<Frame<Bytes>>(&json).unwrap(), frame);
+
+        let invalid = r#"{"trailers":[["bad name",[]]]}"#;
+        assert!(serde_json::from_str::<Frame<Bytes>>(invalid).is_err());
+    }
+
+    #[test]
+    fn clone_and_eq() {
+        let frame = Frame::data("hello");
+        assert_eq!(frame.clone(), Frame::data("hello"));
+        assert_ne!(frame, Frame::data("world"));
+        assert_ne!(frame, Frame::trailers(HeaderMap::new()));
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+        let frame = Frame::<&str>::trailers(trailers.clone());
+        assert_eq!(frame.clone(), Frame::trailers(trailers));
+        assert_ne!(frame, Frame::trailers(HeaderMap::new()));
+    }
 }
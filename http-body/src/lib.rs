@@ -17,7 +17,7 @@ mod frame;
 mod size_hint;
 
 pub use self::frame::Frame;
-pub use self::size_hint::SizeHint;
+pub use self::size_hint::{InvalidContentLength, SizeHint};
 
 use bytes::{Buf, Bytes};
 use std::convert::Infallible;
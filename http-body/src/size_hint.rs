@@ -1,3 +1,9 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use http::header::{HeaderMap, HeaderValue, CONTENT_LENGTH};
+
 /// A `Body` size hint
 ///
 /// The default implementation returns:
@@ -81,4 +87,194 @@ impl SizeHint {
         self.lower = value;
         self.upper = Some(value);
     }
+
+    /// Returns an exact hint from the `Content-Length` header in `headers`.
+    ///
+    /// Returns `None` if the header is missing or invalid, including when it appears several
+    /// times with different values.
+    pub fn from_content_length(headers: &HeaderMap) -> Option<SizeHint> {
+        let mut values = headers.get_all(CONTENT_LENGTH).iter();
+        let hint = SizeHint::try_from(values.next()?).ok()?;
+        for value in values {
+            if SizeHint::try_from(value).ok()?.exact() != hint.exact() {
+                return None;
+            }
+        }
+        Some(hint)
+    }
+
+    /// Returns the hint for a body yielding the data of both `self` and `other`, or `None` if
+    /// either bound overflows.
+    ///
+    /// The upper bound is only known if both upper bounds are known.
+    #[inline]
+    pub fn checked_add(&self, other: &SizeHint) -> Option<SizeHint> {
+        let lower = self.lower.checked_add(other.lower)?;
+        let upper = match (self.upper, other.upper) {
+            (Some(a), Some(b)) => Some(a.checked_add(b)?),
+            _ => None,
+        };
+        Some(SizeHint { lower, upper })
+    }
+
+    /// Returns the hint for a body yielding the data of both `self` and `other`.
+    ///
+    /// The lower bound saturates at `u64::MAX`, and the upper bound becomes unknown if it
+    /// overflows.
+    #[inline]
+    pub fn saturating_add(&self, other: &SizeHint) -> SizeHint {
+        let upper = match (self.upper, other.upper) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _ => None,
+        };
+        SizeHint {
+            lower: self.lower.saturating_add(other.lower),
+            upper,
+        }
+    }
+
+    /// Returns the hint after `n` bytes have been consumed, or `None` if `n` is greater than
+    /// the upper bound.
+    ///
+    /// The lower bound saturates at zero.
+    #[inline]
+    pub fn checked_sub(&self, n: u64) -> Option<SizeHint> {
+        let upper = match self.upper {
+            Some(upper) => Some(upper.checked_sub(n)?),
+            None => None,
+        };
+        Some(SizeHint {
+            lower: self.lower.saturating_sub(n),
+            upper,
+        })
+    }
+
+    /// Returns the hint after `n` bytes have been consumed.
+    ///
+    /// Both bounds saturate at zero.
+    #[inline]
+    pub fn saturating_sub(&self, n: u64) -> SizeHint {
+        SizeHint {
+            lower: self.lower.saturating_sub(n),
+            upper: self.upper.map(|upper| upper.saturating_sub(n)),
+        }
+    }
+}
+
+impl TryFrom<&HeaderValue> for SizeHint {
+    type Error = InvalidContentLength;
+
+    /// Parse a `Content-Length` header value into an exact hint.
+    fn try_from(value: &HeaderValue) -> Result<Self, Self::Error> {
+        let bytes = value.as_bytes();
+        if bytes.is_empty() || !bytes.iter().all(u8::is_ascii_digit) {
+            return Err(InvalidContentLength);
+        }
+        std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .map(SizeHint::with_exact)
+            .ok_or(InvalidContentLength)
+    }
+}
+
+impl From<SizeHint> for (usize, Option<usize>) {
+    /// Convert the hint into the form used by `Iterator::size_hint` and `Stream::size_hint`.
+    ///
+    /// The lower bound saturates at `usize::MAX`, and the upper bound is `None` if it does not fit
+    /// in a `usize`.
+    fn from(hint: SizeHint) -> Self {
+        let lower = usize::try_from(hint.lower).unwrap_or(usize::MAX);
+        let upper = hint.upper.and_then(|upper| usize::try_from(upper).ok());
+        (lower, upper)
+    }
+}
+
+/// An error returned when a `Content-Length` value cannot be parsed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct InvalidContentLength;
+
+impl fmt::Display for InvalidContentLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid content-length")
+    }
+}
+
+impl Error for InvalidContentLength {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hint(lower: u64, upper: Option<u64>) -> SizeHint {
+        SizeHint { lower, upper }
+    }
+
+    #[test]
+    fn add() {
+        let sum = hint(1, Some(2)).checked_add(&hint(3, Some(4))).unwrap();
+        assert_eq!((sum.lower(), sum.upper()), (4, Some(6)));
+
+        let sum = hint(1, Some(2)).checked_add(&hint(3, None)).unwrap();
+        assert_eq!((sum.lower(), sum.upper()), (4, None));
+
+        assert!(hint(0, Some(u64::MAX))
+            .checked_add(&SizeHint::with_exact(1))
+            .is_none());
+
+        let sum = SizeHint::with_exact(u64::MAX).saturating_add(&SizeHint::with_exact(1));
+        assert_eq!((sum.lower(), sum.upper()), (u64::MAX, None));
+    }
+
+    #[test]
+    fn content_length() {
+        let hint = SizeHint::try_from(&HeaderValue::from_static("42")).unwrap();
+        assert_eq!(hint.exact(), Some(42));
+
+        for invalid in ["", "-1", "+1", "1 2", "0x10", "18446744073709551616"] {
+            assert!(SizeHint::try_from(&HeaderValue::from_static(invalid)).is_err());
+        }
+
+        let mut headers = HeaderMap::new();
+        assert!(SizeHint::from_content_length(&headers).is_none());
+
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("5"));
+        assert_eq!(
+            SizeHint::from_content_length(&headers).unwrap().exact(),
+            Some(5)
+        );
+
+        headers.append(CONTENT_LENGTH, HeaderValue::from_static("5"));
+        assert_eq!(
+            SizeHint::from_content_length(&headers).unwrap().exact(),
+            Some(5)
+        );
+
+        headers.append(CONTENT_LENGTH, HeaderValue::from_static("6"));
+        assert!(SizeHint::from_content_length(&headers).is_none());
+    }
+
+    #[test]
+    fn into_tuple() {
+        assert_eq!(
+            <(usize, Option<usize>)>::from(SizeHint::with_exact(3)),
+            (3, Some(3))
+        );
+        assert_eq!(<(usize, Option<usize>)>::from(SizeHint::new()), (0, None));
+    }
+
+    #[test]
+    fn sub() {
+        let diff = hint(2, Some(10)).checked_sub(5).unwrap();
+        assert_eq!((diff.lower(), diff.upper()), (0, Some(5)));
+
+        let diff = hint(2, None).checked_sub(5).unwrap();
+        assert_eq!((diff.lower(), diff.upper()), (0, None));
+
+        assert!(SizeHint::with_exact(3).checked_sub(4).is_none());
+
+        let diff = SizeHint::with_exact(3).saturating_sub(4);
+        assert_eq!(diff.exact(), Some(0));
+    }
 }